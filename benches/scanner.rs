@@ -0,0 +1,66 @@
+// benches/scanner.rs
+// Compara Scanner (Vec<char> + String por lexema) con ByteScanner (bytes + &str),
+// tanto con la tabla densa como con el DFA generado por build.rs
+//
+// Ejecutar con: cargo bench --bench scanner
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use interprete_topologias::config::load_automaton;
+use interprete_topologias::{ByteScanner, Scanner};
+use std::fmt::Write;
+
+/// Programa `.net` generado con `machines` máquinas conectadas a concentradores
+fn generated_program(machines: usize) -> String {
+    let hubs = machines.div_ceil(8);
+    let mut source = String::from("programa generado;\n");
+
+    source.push_str("define maquinas\n  ");
+    let names: Vec<String> = (0..machines).map(|i| format!("m{}", i)).collect();
+    source.push_str(&names.join(", "));
+    source.push_str(";\n");
+
+    source.push_str("define concentradores\n  ");
+    let hub_decls: Vec<String> = (0..hubs).map(|i| format!("h{}=8", i)).collect();
+    source.push_str(&hub_decls.join(", "));
+    source.push_str(";\n\ninicio\n");
+
+    for i in 0..hubs {
+        writeln!(source, "  coloca(h{}, {}, {});", i, (i % 100) * 10, (i / 100) * 10).unwrap();
+    }
+    for i in 0..machines {
+        // Comentarios y acentos para que ambos scanners recorran trivia y UTF-8
+        writeln!(source, "  coloca(m{}, {}, {}); // máquina {}", i, i % 500, i / 500, i).unwrap();
+        writeln!(source, "  uneMaquinaPuerto(m{}, h{}, {});", i, i / 8, i % 8 + 1).unwrap();
+    }
+
+    source.push_str("fin.\n");
+    source
+}
+
+fn bench_scanners(c: &mut Criterion) {
+    let automaton = load_automaton();
+    // La tabla densa se calcula una vez por autómata; no se mide aquí
+    automaton.dense_table();
+
+    let mut group = c.benchmark_group("scan_all");
+    for machines in [1_000, 10_000, 50_000] {
+        let source = generated_program(machines);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("Scanner", machines), &source, |b, source| {
+            b.iter(|| Scanner::new(source, automaton).scan_all().unwrap().len())
+        });
+
+        group.bench_with_input(BenchmarkId::new("ByteScanner", machines), &source, |b, source| {
+            b.iter(|| ByteScanner::new(source, automaton).scan_all().unwrap().len())
+        });
+
+        group.bench_with_input(BenchmarkId::new("Generado", machines), &source, |b, source| {
+            b.iter(|| ByteScanner::generated(source).scan_all().unwrap().len())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_scanners);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+// build.rs
+// Genera el DFA de config/automaton.aut como código Rust (OUT_DIR/automaton_dfa.rs)
+//
+// Reutiliza el parser de autómatas del propio crate, igual que main.rs
+// reutiliza los módulos de la biblioteca con #[path].
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code, unused_imports)]
+#[path = "src/lexer_new"]
+mod lexer_new {
+    pub mod automaton;
+    pub mod byte_scanner;
+    pub mod codegen;
+    pub mod error;
+    pub mod spec;
+    pub mod token;
+}
+
+const AUTOMATON_PATH: &str = "config/automaton.aut";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", AUTOMATON_PATH);
+    println!("cargo:rerun-if-changed=src/lexer_new");
+
+    let source = fs::read_to_string(AUTOMATON_PATH)
+        .unwrap_or_else(|e| panic!("Error leyendo {}: {}", AUTOMATON_PATH, e));
+
+    let automaton = lexer_new::automaton::Automaton::parse(&source)
+        .unwrap_or_else(|e| panic!("{} es inválido: {}", AUTOMATON_PATH, e));
+
+    let out_dir = env::var("OUT_DIR").expect("cargo define OUT_DIR para build.rs");
+    fs::write(Path::new(&out_dir).join("automaton_dfa.rs"), automaton.to_rust())
+        .expect("Error escribiendo automaton_dfa.rs");
+}
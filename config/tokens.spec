@@ -0,0 +1,151 @@
+# config/tokens.spec
+# Especificación de tokens equivalente a config/automaton.aut
+#
+# Formato de cada regla:  NOMBRE = expresión regular
+# Cuando dos reglas reconocen el mismo lexema gana la que aparece primero;
+# siempre se prefiere el lexema más largo.
+#
+# Sintaxis: literales, . (cualquier carácter excepto newline), [a-z_], [^"\\],
+# ( ), |, *, +, ?. Escapes: \n \t \r \s (espacio) y \x para un metacarácter x.
+# \p{L} y \p{N} son las letras y los dígitos Unicode (también dentro de [ ]).
+#
+# Compilar con: cargo run --bin interprete ejemplo1.net --automaton config/tokens.spec
+
+METADATA
+# Comentarios de bloque /* ... */ (el scanner los reconoce antes del DFA)
+block_comment: /* */
+nested_comments: true
+END_METADATA
+
+TOKENS
+# Identificadores y palabras reservadas (admiten letras con tilde y ñ)
+IDENTIFIER = [a-zA-Z_\p{L}][a-zA-Z0-9_\p{L}\p{N}]*
+
+# Números enteros
+NUMBER = [0-9]+
+
+# Cadenas literales con escapes
+STRING = "([^"\\]|\\.)*"
+
+# Operadores relacionales
+LESS_EQUAL = <=
+NOT_EQUAL = <>
+GREATER_EQUAL = >=
+EQUAL = =
+LESS = <
+GREATER = >
+
+# Operadores lógicos
+AND = &&
+OR = \|\|
+NOT = !
+
+# Operadores aritméticos (SLASH pierde ante // por ser más corto)
+PLUS = \+
+MINUS = -
+STAR = \*
+SLASH = /
+PERCENT = %
+
+# Delimitadores
+COMMA = ,
+SEMICOLON = ;
+DOT = \.
+LPAREN = \(
+RPAREN = \)
+LBRACKET = \[
+RBRACKET = \]
+
+# Espacios en blanco (espacio, tab, retorno de carro, nueva línea)
+WHITESPACE = [ \t\r\n]+
+
+# Comentarios de documentación /// (antes que COMMENT para que tengan prioridad)
+DOC_COMMENT = ///.*
+
+# Comentarios de línea //
+COMMENT = //.*
+END_TOKENS
+
+# Dialecto predeterminado (espanol)
+KEYWORDS
+# Estructura del programa (sin distinguir mayúsculas ni acentos: módulo = modulo)
+programa, PROGRAMA
+define, DEFINE
+maquinas, MAQUINAS
+concentradores, CONCENTRADORES
+coaxial, COAXIAL
+segmento, SEGMENTO
+enteros, ENTEROS
+modulo, MODULO
+incluye, INCLUYE
+inicio, INICIO
+fin, FIN
+
+# Control de flujo (case-insensitive)
+si, SI
+sino, SINO
+mientras, MIENTRAS
+para, PARA
+hasta, HASTA
+
+# Tipos de los parámetros de un módulo
+maquina, MAQUINA
+concentrador, CONCENTRADOR
+entero, ENTERO
+
+# Funciones del lenguaje (case-sensitive - estas deben coincidir exactamente)
+coloca, COLOCA
+colocaCoaxial, COLOCA_COAXIAL
+colocaCoaxialConcentrador, COLOCA_COAXIAL_CONCENTRADOR
+uneMaquinaPuerto, UNE_MAQUINA_PUERTO
+asignaPuerto, ASIGNA_PUERTO
+maquinaCoaxial, MAQUINA_COAXIAL
+asignaMaquinaCoaxial, ASIGNA_MAQUINA_COAXIAL
+escribe, ESCRIBE
+
+# Direcciones (case-insensitive)
+arriba, ARRIBA
+abajo, ABAJO
+izquierda, IZQUIERDA
+derecha, DERECHA
+END_KEYWORDS
+
+KEYWORDS english
+# Mismos tipos de token que la sección anterior; se elige con el pragma
+# `// @dialecto english` antes de `program` o con `--dialect english`
+program, PROGRAMA
+define, DEFINE
+machines, MAQUINAS
+hubs, CONCENTRADORES
+coaxial, COAXIAL
+segment, SEGMENTO
+integers, ENTEROS
+module, MODULO
+include, INCLUYE
+begin, INICIO
+end, FIN
+
+if, SI
+else, SINO
+while, MIENTRAS
+for, PARA
+to, HASTA
+
+machine, MAQUINA
+concentrator, CONCENTRADOR
+integer, ENTERO
+
+place, COLOCA
+placeCoaxial, COLOCA_COAXIAL
+placeCoaxialHub, COLOCA_COAXIAL_CONCENTRADOR
+connectPort, UNE_MAQUINA_PUERTO
+assignPort, ASIGNA_PUERTO
+machineCoaxial, MAQUINA_COAXIAL
+assignMachineCoaxial, ASIGNA_MAQUINA_COAXIAL
+write, ESCRIBE
+
+up, ARRIBA
+down, ABAJO
+left, IZQUIERDA
+right, DERECHA
+END_KEYWORDS
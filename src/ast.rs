@@ -1,7 +1,8 @@
 // AST - Abstract Syntax Tree
 // Representa la estructura sintáctica del programa
 
-use crate::lexer::TokenInfo;
+use crate::lexer_new::Token;
+use std::fmt;
 
 // ============================================================================
 // UBICACIÓN EN EL CÓDIGO FUENTE
@@ -12,14 +13,16 @@ pub struct Location {
     pub line: usize,
     pub column: usize,
     pub length: usize,
+    pub archivo: usize, // Índice en el `SourceMap` (0 = archivo principal)
 }
 
 impl Location {
-    pub fn from_token(token: &TokenInfo) -> Self {
+    pub fn from_token(token: &Token) -> Self {
         Self {
             line: token.line,
             column: token.column,
             length: token.length,
+            archivo: 0,
         }
     }
 
@@ -28,6 +31,7 @@ impl Location {
             line: 0,
             column: 0,
             length: 0,
+            archivo: 0,
         }
     }
 }
@@ -39,9 +43,74 @@ impl Location {
 #[derive(Debug, Clone)]
 pub struct Program {
     pub nombre: String,
+    pub inclusiones: Vec<Inclusion>,
     pub definiciones: Definitions,
     pub modulos: Vec<Modulo>,
     pub sentencias: Vec<Statement>,
+    pub biblioteca: bool, // true si no tiene `programa` ni bloque principal
+    pub location: Location,
+}
+
+impl Program {
+    /// Marca todas las ubicaciones del programa como del archivo `archivo`
+    pub fn asignar_archivo(&mut self, archivo: usize) {
+        self.location.archivo = archivo;
+        for inclusion in &mut self.inclusiones {
+            inclusion.location.archivo = archivo;
+        }
+
+        let definiciones = &mut self.definiciones;
+        definiciones.location.archivo = archivo;
+        for decl in &mut definiciones.maquinas {
+            decl.location.archivo = archivo;
+        }
+        for decl in &mut definiciones.concentradores {
+            decl.location.archivo = archivo;
+        }
+        for decl in &mut definiciones.coaxiales {
+            decl.location.archivo = archivo;
+        }
+        for decl in &mut definiciones.enteros {
+            decl.location.archivo = archivo;
+        }
+
+        for modulo in &mut self.modulos {
+            modulo.location.archivo = archivo;
+            for parametro in &mut modulo.parametros {
+                parametro.location.archivo = archivo;
+            }
+            asignar_archivo_sentencias(&mut modulo.sentencias, archivo);
+        }
+        asignar_archivo_sentencias(&mut self.sentencias, archivo);
+    }
+}
+
+fn asignar_archivo_sentencias(sentencias: &mut [Statement], archivo: usize) {
+    for sentencia in sentencias {
+        sentencia.location_mut().archivo = archivo;
+        match sentencia {
+            Statement::Si { entonces, sino_si, sino, .. } => {
+                asignar_archivo_sentencias(entonces, archivo);
+                for rama in sino_si {
+                    rama.location.archivo = archivo;
+                    asignar_archivo_sentencias(&mut rama.sentencias, archivo);
+                }
+                if let Some(sino) = sino {
+                    asignar_archivo_sentencias(sino, archivo);
+                }
+            }
+            Statement::Mientras { cuerpo, .. } | Statement::Para { cuerpo, .. } => {
+                asignar_archivo_sentencias(cuerpo, archivo);
+            }
+            _ => {}
+        }
+    }
+}
+
+// incluye "comun.net";
+#[derive(Debug, Clone)]
+pub struct Inclusion {
+    pub ruta: String,
     pub location: Location,
 }
 
@@ -54,6 +123,7 @@ pub struct Definitions {
     pub maquinas: Vec<MaquinaDecl>,
     pub concentradores: Vec<ConcentradorDecl>,
     pub coaxiales: Vec<CoaxialDecl>,
+    pub enteros: Vec<VariableDecl>,
     pub location: Location,
 }
 
@@ -63,6 +133,7 @@ impl Definitions {
             maquinas: Vec::new(),
             concentradores: Vec::new(),
             coaxiales: Vec::new(),
+            enteros: Vec::new(),
             location: Location::unknown(),
         }
     }
@@ -75,6 +146,7 @@ impl Definitions {
 #[derive(Debug, Clone)]
 pub struct MaquinaDecl {
     pub nombre: String,
+    pub doc: Option<String>, // Comentarios /// previos
     pub location: Location,
 }
 
@@ -87,6 +159,7 @@ pub struct ConcentradorDecl {
     pub nombre: String,
     pub puertos: i32,
     pub tiene_coaxial: bool, // true si se declara con .1
+    pub doc: Option<String>,
     pub location: Location,
 }
 
@@ -98,6 +171,18 @@ pub struct ConcentradorDecl {
 pub struct CoaxialDecl {
     pub nombre: String,
     pub longitud: i32,
+    pub doc: Option<String>,
+    pub location: Location,
+}
+
+// ============================================================================
+// DECLARACIÓN DE VARIABLE ENTERA
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct VariableDecl {
+    pub nombre: String,
+    pub doc: Option<String>,
     pub location: Location,
 }
 
@@ -108,10 +193,40 @@ pub struct CoaxialDecl {
 #[derive(Debug, Clone)]
 pub struct Modulo {
     pub nombre: String,
+    pub parametros: Vec<Parametro>,
     pub sentencias: Vec<Statement>,
+    pub doc: Option<String>,
     pub location: Location,
 }
 
+// modulo conectaPC(maquina m, concentrador hub, entero p);
+#[derive(Debug, Clone)]
+pub struct Parametro {
+    pub nombre: String,
+    pub tipo: TipoParametro,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TipoParametro {
+    Maquina,
+    Concentrador,
+    Coaxial,
+    Entero,
+}
+
+impl TipoParametro {
+    /// Palabra reservada del tipo, como se escribe en la declaración
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            TipoParametro::Maquina => "maquina",
+            TipoParametro::Concentrador => "concentrador",
+            TipoParametro::Coaxial => "coaxial",
+            TipoParametro::Entero => "entero",
+        }
+    }
+}
+
 // ============================================================================
 // SENTENCIAS
 // ============================================================================
@@ -178,21 +293,76 @@ pub enum Statement {
         location: Location,
     },
 
-    // si (condicion) inicio sentencias fin sino inicio sentencias fin
+    // si (condicion) inicio sentencias fin
+    // sino si (condicion) inicio sentencias fin ...
+    // sino inicio sentencias fin
     Si {
         condicion: Expr,
         entonces: Vec<Statement>,
+        sino_si: Vec<RamaSi>,   // Las ramas `sino si`, en orden y sin anidar
         sino: Option<Vec<Statement>>,
         location: Location,
     },
 
-    // Llamada a módulo
+    // mientras (condicion) inicio sentencias fin
+    Mientras {
+        condicion: Expr,
+        cuerpo: Vec<Statement>,
+        location: Location,
+    },
+
+    // para variable = desde hasta hasta inicio sentencias fin
+    Para {
+        variable: String,
+        desde: Expr,
+        hasta: Expr,
+        cuerpo: Vec<Statement>,
+        location: Location,
+    },
+
+    // Llamada a módulo: nombre; o nombre(arg1, arg2, ...);
     LlamadaModulo {
         nombre: String,
+        argumentos: Vec<Expr>,
+        location: Location,
+    },
+
+    // variable = expr;
+    Asignacion {
+        variable: String,
+        valor: Expr,
         location: Location,
     },
 }
 
+impl Statement {
+    pub fn location_mut(&mut self) -> &mut Location {
+        match self {
+            Statement::Coloca { location, .. }
+            | Statement::ColocaCoaxial { location, .. }
+            | Statement::ColocaCoaxialConcentrador { location, .. }
+            | Statement::UneMaquinaPuerto { location, .. }
+            | Statement::AsignaPuerto { location, .. }
+            | Statement::MaquinaCoaxial { location, .. }
+            | Statement::AsignaMaquinaCoaxial { location, .. }
+            | Statement::Escribe { location, .. }
+            | Statement::Si { location, .. }
+            | Statement::Mientras { location, .. }
+            | Statement::Para { location, .. }
+            | Statement::LlamadaModulo { location, .. }
+            | Statement::Asignacion { location, .. } => location,
+        }
+    }
+}
+
+// sino si (condicion) inicio sentencias fin
+#[derive(Debug, Clone)]
+pub struct RamaSi {
+    pub condicion: Expr,
+    pub sentencias: Vec<Statement>,
+    pub location: Location,
+}
+
 // ============================================================================
 // EXPRESIONES
 // ============================================================================
@@ -232,6 +402,60 @@ pub enum Expr {
 
     // Negación lógica: !expr
     Not(Box<Expr>),
+
+    // Expresiones aritméticas: a + b, a * b, etc.
+    Aritmetico {
+        izq: Box<Expr>,
+        op: OpAritmetico,
+        der: Box<Expr>,
+    },
+
+    // Menos unario: -expr
+    Negativo(Box<Expr>),
+}
+
+/// La expresión como se escribiría en el código; las subexpresiones
+/// binarias van entre paréntesis
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn operando(f: &mut fmt::Formatter, expr: &Expr) -> fmt::Result {
+            match expr {
+                Expr::Relacional { .. } | Expr::Logico { .. } | Expr::Aritmetico { .. } => write!(f, "({})", expr),
+                _ => write!(f, "{}", expr),
+            }
+        }
+
+        match self {
+            Expr::Numero(n) => write!(f, "{}", n),
+            Expr::Cadena(s) => write!(f, "\"{}\"", s),
+            Expr::Identificador(nombre) => write!(f, "{}", nombre),
+            Expr::AccesoCampo { objeto, campo } => write!(f, "{}.{}", objeto, campo),
+            Expr::AccesoArreglo { objeto, indice } => write!(f, "{}[{}]", objeto, indice),
+            Expr::Relacional { izq, op, der } => {
+                operando(f, izq)?;
+                write!(f, " {} ", op.simbolo())?;
+                operando(f, der)
+            }
+            Expr::Logico { izq, op, der } => {
+                operando(f, izq)?;
+                write!(f, " {} ", op.simbolo())?;
+                operando(f, der)
+            }
+            Expr::Aritmetico { izq, op, der } => {
+                operando(f, izq)?;
+                write!(f, " {} ", op.simbolo())?;
+                operando(f, der)
+            }
+            Expr::Not(expr) => {
+                write!(f, "!")?;
+                operando(f, expr)
+            }
+            Expr::Negativo(expr) => {
+                write!(f, "-")?;
+                operando(f, expr)
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -248,12 +472,55 @@ pub enum OpRelacional {
     MayorIgual, // >=
 }
 
+impl OpRelacional {
+    pub fn simbolo(&self) -> &'static str {
+        match self {
+            OpRelacional::Igual => "=",
+            OpRelacional::Diferente => "<>",
+            OpRelacional::Menor => "<",
+            OpRelacional::Mayor => ">",
+            OpRelacional::MenorIgual => "<=",
+            OpRelacional::MayorIgual => ">=",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum OpLogico {
     And, // &&
     Or,  // ||
 }
 
+impl OpLogico {
+    pub fn simbolo(&self) -> &'static str {
+        match self {
+            OpLogico::And => "&&",
+            OpLogico::Or => "||",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OpAritmetico {
+    Suma,           // +
+    Resta,          // -
+    Multiplicacion, // *
+    Division,       // /
+    Modulo,         // %
+}
+
+impl OpAritmetico {
+    pub fn simbolo(&self) -> &'static str {
+        match self {
+            OpAritmetico::Suma => "+",
+            OpAritmetico::Resta => "-",
+            OpAritmetico::Multiplicacion => "*",
+            OpAritmetico::Division => "/",
+            OpAritmetico::Modulo => "%",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Direccion {
     Arriba,
@@ -279,15 +546,19 @@ impl Program {
         if !self.modulos.is_empty() {
             println!("\n Módulos definidos: {}", self.modulos.len());
             for (i, modulo) in self.modulos.iter().enumerate() {
-                println!("   {}. modulo {} (línea {}) - {} sentencias",
-                         i + 1, modulo.nombre, modulo.location.line, modulo.sentencias.len());
+                let parametros: Vec<String> = modulo.parametros.iter()
+                    .map(|p| format!("{} {}", p.tipo.nombre(), p.nombre))
+                    .collect();
+                println!("   {}. modulo {}({}) (línea {}) - {} sentencias",
+                         i + 1, modulo.nombre, parametros.join(", "), modulo.location.line, modulo.sentencias.len());
+                print_doc(&modulo.doc);
             }
         }
 
         if !self.sentencias.is_empty() {
             println!("\n Sentencias principales: {}", self.sentencias.len());
             for (i, stmt) in self.sentencias.iter().enumerate() {
-                println!("   {}. {:?}", i + 1, format!("{:?}", stmt).chars().take(60).collect::<String>());
+                print_statement(i + 1, stmt);
             }
         }
 
@@ -296,6 +567,32 @@ impl Program {
     }
 }
 
+/// Una sentencia en una línea; una cadena `si ... sino si ... sino` muestra
+/// cada rama al mismo nivel, sin anidar
+fn print_statement(numero: usize, stmt: &Statement) {
+    match stmt {
+        Statement::Si { condicion, entonces, sino_si, sino, .. } => {
+            println!("   {}. si ({}) - {} sentencias", numero, condicion, entonces.len());
+            for rama in sino_si {
+                println!("      sino si ({}) - {} sentencias", rama.condicion, rama.sentencias.len());
+            }
+            if let Some(sino) = sino {
+                println!("      sino - {} sentencias", sino.len());
+            }
+        }
+        _ => println!("   {}. {:?}", numero, format!("{:?}", stmt).chars().take(60).collect::<String>()),
+    }
+}
+
+/// Muestra los comentarios /// de una declaración debajo de ella
+fn print_doc(doc: &Option<String>) {
+    if let Some(doc) = doc {
+        for line in doc.lines() {
+            println!("        /// {}", line);
+        }
+    }
+}
+
 impl Definitions {
     pub fn pretty_print(&self) {
         use std::io::{self, Write};
@@ -304,6 +601,7 @@ impl Definitions {
             println!("\n Máquinas declaradas: {}", self.maquinas.len());
             for (i, maq) in self.maquinas.iter().enumerate() {
                 println!("   {}. {} (línea {})", i + 1, maq.nombre, maq.location.line);
+                print_doc(&maq.doc);
             }
         }
 
@@ -313,6 +611,7 @@ impl Definitions {
                 let coax_info = if conc.tiene_coaxial { " + coaxial" } else { "" };
                 println!("   {}. {} = {} puertos{} (línea {})",
                          i + 1, conc.nombre, conc.puertos, coax_info, conc.location.line);
+                print_doc(&conc.doc);
             }
         }
 
@@ -321,6 +620,15 @@ impl Definitions {
             for (i, coax) in self.coaxiales.iter().enumerate() {
                 println!("   {}. {} = {}m (línea {})",
                          i + 1, coax.nombre, coax.longitud, coax.location.line);
+                print_doc(&coax.doc);
+            }
+        }
+
+        if !self.enteros.is_empty() {
+            println!("\n Variables enteras declaradas: {}", self.enteros.len());
+            for (i, var) in self.enteros.iter().enumerate() {
+                println!("   {}. {} (línea {})", i + 1, var.nombre, var.location.line);
+                print_doc(&var.doc);
             }
         }
 
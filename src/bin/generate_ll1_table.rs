@@ -1,30 +1,69 @@
-// Utility to generate config/ll1_table.txt
-// This creates a human-readable export of the LL(1) parsing table
+// Utility to generate config/ll1_table.txt and check the grammar's health
+// Reports LL(1) conflicts, left recursion, unreachable and non-productive
+// non-terminals and unused productions, each pointing at its line in the
+// grammar file; exits with code 1 if any are found, without writing the table
 
-use interprete_topologias::parser_ll1::LL1Table;
+use interprete_topologias::config::loader::DEFAULT_GRAMMAR_SOURCE;
+use interprete_topologias::error::{report_config_issues, report_errors};
+use interprete_topologias::parser_ll1::{Grammar, LL1Table};
 use std::fs;
-use std::io::Write;
 
 fn main() {
-    println!("Generating LL(1) parsing table...");
+    let args: Vec<String> = std::env::args().collect();
 
-    // Create the LL(1) table
-    let table = LL1Table::new();
+    // Without arguments the embedded grammar is checked and its table exported
+    let (filename, source) = match args.get(1) {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(content) => (path.clone(), content),
+            Err(e) => {
+                eprintln!("❌ Error reading '{}': {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => ("docs/gramatica.txt".to_string(), DEFAULT_GRAMMAR_SOURCE.to_string()),
+    };
 
-    // Export to human-readable format
-    let output = table.export_table();
-
-    // Write to config/ll1_table.txt
-    let path = "config/ll1_table.txt";
-    match fs::write(path, &output) {
-        Ok(_) => {
-            println!("✅ Successfully generated {}", path);
-            println!("   File size: {} bytes", output.len());
-            println!("   Lines: {}", output.lines().count());
-        }
-        Err(e) => {
-            eprintln!("❌ Error writing file: {}", e);
+    let grammar = match Grammar::parse(&source) {
+        Ok(grammar) => grammar,
+        Err(diagnostic) => {
+            report_errors(&[diagnostic], &source, &filename);
             std::process::exit(1);
         }
+    };
+
+    println!("Checking grammar: {} ({} productions)", filename, grammar.productions().len());
+
+    // The table is only written for a healthy grammar: on problems the
+    // previous config/ll1_table.txt is left untouched
+    let issues = grammar.validate();
+
+    if !issues.is_empty() {
+        report_config_issues(&issues, &source, &filename);
+        println!("❌ Found {} problem(s) in {}", issues.len(), filename);
+        std::process::exit(1);
+    }
+
+    println!("✅ The grammar is LL(1): no conflicts, left recursion or unused parts");
+
+    if args.get(1).is_none() {
+        println!("Generating LL(1) parsing table...");
+
+        // Create the LL(1) table and export it to human-readable format
+        let table = LL1Table::from_grammar(&grammar);
+        let output = table.export_table();
+
+        // Write to config/ll1_table.txt
+        let path = "config/ll1_table.txt";
+        match fs::write(path, &output) {
+            Ok(_) => {
+                println!("✅ Successfully generated {}", path);
+                println!("   File size: {} bytes", output.len());
+                println!("   Lines: {}", output.lines().count());
+            }
+            Err(e) => {
+                eprintln!("❌ Error writing file: {}", e);
+                std::process::exit(1);
+            }
+        }
     }
 }
@@ -0,0 +1,57 @@
+// Utility to statically validate an automaton file (.aut)
+// Reports nondeterminism, unreachable/dead states, error-state coverage
+// and keyword conflicts, each pointing at its line in the file
+
+use interprete_topologias::config::loader::DEFAULT_AUTOMATON_SOURCE;
+use interprete_topologias::error::{Diagnostic, report_config_issues, report_errors};
+use interprete_topologias::lexer_new::Automaton;
+use interprete_topologias::lexer_new::spec::is_spec_path;
+use std::fs;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    // Sin argumentos se valida el autómata incrustado
+    let (filename, source) = match args.get(1) {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(content) => (path.clone(), content),
+            Err(e) => {
+                eprintln!("❌ Error leyendo '{}': {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => (
+            "config/automaton.aut".to_string(),
+            DEFAULT_AUTOMATON_SOURCE.to_string(),
+        ),
+    };
+
+    println!("Validando autómata: {}", filename);
+
+    let parsed = if is_spec_path(&filename) {
+        Automaton::from_spec(&source)
+    } else {
+        Automaton::parse(&source)
+    };
+
+    let automaton = match parsed {
+        Ok(automaton) => automaton,
+        Err(err) => {
+            let diagnostic = Diagnostic::config_error(err.line, err.column, err.length, err.message);
+            report_errors(&[diagnostic], &source, &filename);
+            std::process::exit(1);
+        }
+    };
+
+    let issues = automaton.validate();
+
+    if issues.is_empty() {
+        println!("✅ El autómata no presenta problemas");
+        return;
+    }
+
+    report_config_issues(&issues, &source, &filename);
+
+    println!("❌ Se encontraron {} problema(s) en {}", issues.len(), filename);
+    std::process::exit(1);
+}
@@ -1,37 +1,133 @@
 // src/config/loader.rs
 // Cargador de archivos de configuración con caché
 
-use crate::lexer_new::Automaton;
-use once_cell::sync::Lazy;
+use crate::error::Diagnostic;
+use crate::lexer_new::spec::is_spec_path;
+use crate::lexer_new::{Automaton, AutomatonError};
+use crate::parser_ll1::Grammar;
+use once_cell::sync::{Lazy, OnceCell};
 
-/// Autómata cargado desde archivo (singleton lazy)
+/// Contenido de config/automaton.aut incrustado en el binario al compilar
+pub const DEFAULT_AUTOMATON_SOURCE: &str = include_str!("../../config/automaton.aut");
+
+/// Autómata por defecto, construido desde la copia incrustada (singleton lazy)
 pub static AUTOMATON: Lazy<Automaton> = Lazy::new(|| {
-    Automaton::from_file("config/automaton.aut")
-        .expect("Error fatal: no se pudo cargar el autómata desde config/automaton.aut")
+    Automaton::parse(DEFAULT_AUTOMATON_SOURCE)
+        .expect("Error fatal: el autómata incrustado (config/automaton.aut) es inválido")
 });
 
-/// Carga el autómata (devuelve referencia estática)
+/// Autómata alternativo instalado en tiempo de ejecución (p. ej. con --automaton)
+static OVERRIDE: OnceCell<Automaton> = OnceCell::new();
+
+/// Carga el autómata activo (devuelve referencia estática)
+///
+/// Si se instaló un autómata alternativo con `set_automaton`, se devuelve ese;
+/// de lo contrario se usa el autómata por defecto incrustado en el binario.
 pub fn load_automaton() -> &'static Automaton {
-    &AUTOMATON
+    OVERRIDE.get().unwrap_or(&AUTOMATON)
+}
+
+/// Indica si se instaló un autómata alternativo con `set_automaton`
+///
+/// Sin él, el autómata activo es el incrustado, el mismo del que build.rs
+/// generó `GeneratedDfa`.
+pub fn has_override() -> bool {
+    OVERRIDE.get().is_some()
+}
+
+/// Carga un autómata alternativo desde un archivo .aut o una especificación .spec
+pub fn load_automaton_from(path: &str) -> Result<Automaton, AutomatonError> {
+    if is_spec_path(path) {
+        Automaton::from_spec_file(path)
+    } else {
+        Automaton::from_file(path)
+    }
+}
+
+/// Instala un autómata alternativo para el resto del proceso
+///
+/// Solo puede hacerse una vez; si ya había uno instalado se devuelve el
+/// autómata recibido como error.
+pub fn set_automaton(automaton: Automaton) -> Result<(), Box<Automaton>> {
+    OVERRIDE.set(automaton).map_err(Box::new)
+}
+
+/// Contenido de docs/gramatica.txt incrustado en el binario al compilar
+pub const DEFAULT_GRAMMAR_SOURCE: &str = include_str!("../../docs/gramatica.txt");
+
+/// Gramática por defecto, construida desde la copia incrustada (singleton lazy)
+pub static GRAMMAR: Lazy<Grammar> = Lazy::new(|| {
+    Grammar::parse(DEFAULT_GRAMMAR_SOURCE)
+        .expect("Error fatal: la gramática incrustada (docs/gramatica.txt) es inválida")
+});
+
+/// Gramática alternativa instalada en tiempo de ejecución (p. ej. con --grammar)
+static GRAMMAR_OVERRIDE: OnceCell<Grammar> = OnceCell::new();
+
+/// Carga la gramática activa: la instalada con `set_grammar` o la incrustada
+pub fn load_grammar() -> &'static Grammar {
+    GRAMMAR_OVERRIDE.get().unwrap_or(&GRAMMAR)
+}
+
+/// Carga una gramática alternativa desde un archivo con el formato de docs/gramatica.txt
+pub fn load_grammar_from(path: &str) -> Result<Grammar, Diagnostic> {
+    Grammar::from_file(path)
+}
+
+/// Instala una gramática alternativa para el resto del proceso
+///
+/// Igual que con `set_automaton`, solo puede hacerse una vez.
+pub fn set_grammar(grammar: Grammar) -> Result<(), Box<Grammar>> {
+    GRAMMAR_OVERRIDE.set(grammar).map_err(Box::new)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_load_automaton() {
         let automaton = load_automaton();
         // Verificar que se cargó correctamente
         assert!(automaton.is_final(automaton.initial_state()).is_none());
     }
-    
+
     #[test]
     fn test_automaton_singleton() {
         let a1 = load_automaton();
         let a2 = load_automaton();
-        
+
         // Ambas referencias apuntan al mismo objeto
         assert!(std::ptr::eq(a1, a2));
     }
+
+    #[test]
+    fn test_embedded_automaton_keywords() {
+        let automaton = Automaton::parse(DEFAULT_AUTOMATON_SOURCE).unwrap();
+        assert_eq!(
+            automaton.classify_identifier("programa"),
+            crate::lexer_new::TokenType::Programa
+        );
+    }
+
+    #[test]
+    fn test_embedded_automaton_is_valid() {
+        let issues = Automaton::parse(DEFAULT_AUTOMATON_SOURCE).unwrap().validate();
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_load_automaton_from_missing_file() {
+        let err = load_automaton_from("config/no_existe.aut").err().unwrap();
+        assert_eq!(err.line, 0);
+        assert!(err.message.contains("no_existe.aut"));
+    }
+
+    #[test]
+    fn test_embedded_grammar() {
+        let grammar = Grammar::parse(DEFAULT_GRAMMAR_SOURCE).unwrap();
+        assert_eq!(grammar.productions().len(), 122);
+        assert_eq!(grammar.start(), crate::parser_ll1::NonTerminal::new("Programa"));
+        assert!(std::ptr::eq(load_grammar(), load_grammar()));
+    }
 }
@@ -3,4 +3,6 @@
 
 pub mod loader;
 
-pub use loader::load_automaton;
+pub use loader::{
+    has_override, load_automaton, load_automaton_from, load_grammar, load_grammar_from, set_automaton, set_grammar,
+};
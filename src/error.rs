@@ -10,6 +10,7 @@ pub enum DiagnosticKind {
     SyntaxError,
     SemanticError,
     RuntimeError,
+    ConfigError,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +21,7 @@ pub struct Diagnostic {
     pub length: usize,
     pub message: String,
     pub help: Option<String>,
+    pub file: usize, // Índice en el `SourceMap` (0 = archivo principal)
 }
 
 impl Diagnostic {
@@ -31,6 +33,7 @@ impl Diagnostic {
             length,
             message,
             help: None,
+            file: 0,
         }
     }
 
@@ -42,6 +45,7 @@ impl Diagnostic {
             length,
             message,
             help: None,
+            file: 0,
         }
     }
 
@@ -53,6 +57,7 @@ impl Diagnostic {
             length,
             message,
             help: None,
+            file: 0,
         }
     }
 
@@ -64,6 +69,19 @@ impl Diagnostic {
             length,
             message,
             help: None,
+            file: 0,
+        }
+    }
+
+    pub fn config_error(line: usize, column: usize, length: usize, message: String) -> Self {
+        Diagnostic {
+            kind: DiagnosticKind::ConfigError,
+            line,
+            column,
+            length,
+            message,
+            help: None,
+            file: 0,
         }
     }
 
@@ -71,6 +89,106 @@ impl Diagnostic {
         self.help = Some(help);
         self
     }
+
+    /// Marca el diagnóstico como de otro archivo del `SourceMap`
+    pub fn in_file(mut self, file: usize) -> Self {
+        self.file = file;
+        self
+    }
+}
+
+// ============================================================================
+// ARCHIVOS FUENTE
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub source: String,
+}
+
+/// Archivos de una ejecución: el principal (índice 0) y los que incluye
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra un archivo y devuelve su índice
+    pub fn add(&mut self, name: String, source: String) -> usize {
+        self.files.push(SourceFile { name, source });
+        self.files.len() - 1
+    }
+
+    pub fn get(&self, file: usize) -> Option<&SourceFile> {
+        self.files.get(file)
+    }
+
+    /// Todos los archivos, en el orden en que se registraron
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// Nombre del archivo, o `?` si el índice no existe
+    pub fn name(&self, file: usize) -> &str {
+        self.get(file).map(|f| f.name.as_str()).unwrap_or("?")
+    }
+}
+
+// ============================================================================
+// PROBLEMAS EN ARCHIVOS DE CONFIGURACIÓN
+// ============================================================================
+
+/// Tipo de problema que detecta la validación estática de un archivo de
+/// configuración (autómata o gramática)
+pub trait IssueKind: fmt::Debug + Copy {
+    fn as_str(&self) -> &'static str;
+}
+
+/// Problema encontrado al validar un archivo de configuración
+#[derive(Debug, Clone)]
+pub struct ConfigIssue<K> {
+    pub kind: K,
+
+    /// Línea del archivo (0 si no aplica)
+    pub line: usize,
+
+    pub message: String,
+}
+
+impl<K: IssueKind> ConfigIssue<K> {
+    pub fn new(kind: K, line: usize, message: String) -> Self {
+        Self { kind, line, message }
+    }
+
+    /// Diagnóstico que subraya el contenido de la línea del problema
+    pub fn to_diagnostic(&self, source: &str) -> Diagnostic {
+        let (column, length) = line_span(source, self.line);
+        Diagnostic::config_error(self.line, column, length, self.message.clone())
+            .with_help(self.kind.as_str().to_string())
+    }
+}
+
+impl<K: IssueKind> fmt::Display for ConfigIssue<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "línea {}: {}: {}", self.line, self.kind.as_str(), self.message)
+    }
+}
+
+/// Calcula columna y longitud del contenido (sin sangría) de una línea
+pub fn line_span(source: &str, line: usize) -> (usize, usize) {
+    match line.checked_sub(1).and_then(|idx| source.lines().nth(idx)) {
+        Some(raw) => {
+            let indent = raw.chars().take_while(|c| c.is_whitespace()).count();
+            let length = raw.trim().chars().count().max(1);
+            (indent + 1, length)
+        }
+        None => (1, 1),
+    }
 }
 
 impl fmt::Display for Diagnostic {
@@ -80,6 +198,7 @@ impl fmt::Display for Diagnostic {
             DiagnosticKind::SyntaxError => "Error Sintáctico",
             DiagnosticKind::SemanticError => "Error Semántico",
             DiagnosticKind::RuntimeError => "Error de Ejecución",
+            DiagnosticKind::ConfigError => "Error de Configuración",
         };
 
         write!(
@@ -97,6 +216,7 @@ pub fn report_error(error: &Diagnostic, source: &str, filename: &str) {
         DiagnosticKind::SyntaxError => "error sintáctico",
         DiagnosticKind::SemanticError => "error semántico",
         DiagnosticKind::RuntimeError => "error de ejecución",
+        DiagnosticKind::ConfigError => "error de configuración",
     };
 
     // Header: error: mensaje
@@ -176,7 +296,8 @@ pub fn report_error(error: &Diagnostic, source: &str, filename: &str) {
                  width = line_num_width);
 
         // Indicador de error (^^^)
-        let spaces = " ".repeat(error.column);
+        // Las columnas empiezan en 1: la columna 1 no lleva sangría
+        let spaces = " ".repeat(error.column.saturating_sub(1));
         let carets = "^".repeat(error.length.max(1));
         println!("{:>width$} {} {}{} {}",
                  "".blue().bold(),
@@ -215,8 +336,29 @@ pub fn report_errors(errors: &[Diagnostic], source: &str, filename: &str) {
     for error in errors {
         report_error(error, source, filename);
     }
+    report_error_count(errors.len());
+}
+
+/// Reporta errores de varios archivos: cada uno se muestra con el código y el
+/// nombre del archivo de su campo `file`
+pub fn report_errors_in(errors: &[Diagnostic], sources: &SourceMap) {
+    for error in errors {
+        match sources.get(error.file) {
+            Some(file) => report_error(error, &file.source, &file.name),
+            None => report_error(error, "", "?"),
+        }
+    }
+    report_error_count(errors.len());
+}
+
+/// Reporta los problemas de validación de un archivo de configuración
+pub fn report_config_issues<K: IssueKind>(issues: &[ConfigIssue<K>], source: &str, filename: &str) {
+    for issue in issues {
+        report_error(&issue.to_diagnostic(source), source, filename);
+    }
+}
 
-    let error_count = errors.len();
+fn report_error_count(error_count: usize) {
     println!("{}{} no se pudo compilar debido a {} error{}",
              "error".red().bold(),
              ":".bold(),
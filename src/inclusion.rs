@@ -0,0 +1,337 @@
+// src/inclusion.rs
+// Directiva `incluye "comun.net";`: bibliotecas de topologías compartidas
+//
+// Antes del análisis semántico se cargan los archivos incluidos (con rutas
+// relativas al archivo que los incluye), sus ubicaciones se marcan con el
+// índice del archivo en el `SourceMap` y sus definiciones y módulos se unen
+// al programa principal. Cada archivo se carga una sola vez aunque lo incluyan
+// varios.
+
+use crate::ast::{Definitions, Inclusion, Location, Program};
+use crate::error::{Diagnostic, SourceMap};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Une al programa principal (archivo 0 de `sources`) las bibliotecas que
+/// incluye directa o indirectamente
+///
+/// `analizar` convierte el código de un archivo en su AST (análisis léxico y
+/// sintáctico); sus diagnósticos se marcan con el archivo que los produjo.
+pub fn resolver_inclusiones<F>(
+    programa: &mut Program,
+    sources: &mut SourceMap,
+    mut analizar: F,
+) -> Result<(), Vec<Diagnostic>>
+where
+    F: FnMut(&str) -> Result<Program, Vec<Diagnostic>>,
+{
+    let principal = PathBuf::from(sources.name(0));
+    let canonica = fs::canonicalize(&principal).unwrap_or_else(|_| principal.clone());
+
+    let mut resolver = Resolver {
+        sources,
+        analizar: &mut analizar,
+        pila: vec![(canonica.clone(), 0)],
+        cargados: HashMap::from([(canonica, 0)]),
+        bibliotecas: Vec::new(),
+        errores: Vec::new(),
+    };
+    resolver.visitar(&programa.inclusiones, directorio(&principal));
+
+    let Resolver { sources, bibliotecas, mut errores, .. } = resolver;
+    if errores.is_empty() {
+        errores = unir(programa, bibliotecas, sources);
+    }
+
+    if errores.is_empty() {
+        Ok(())
+    } else {
+        Err(errores)
+    }
+}
+
+struct Resolver<'a> {
+    sources: &'a mut SourceMap,
+    analizar: &'a mut dyn FnMut(&str) -> Result<Program, Vec<Diagnostic>>,
+    /// Archivos que se están cargando, del principal al actual
+    pila: Vec<(PathBuf, usize)>,
+    /// Índice de cada archivo ya cargado, por ruta canónica
+    cargados: HashMap<PathBuf, usize>,
+    /// Bibliotecas en el orden en que se unen (las incluidas antes)
+    bibliotecas: Vec<Program>,
+    errores: Vec<Diagnostic>,
+}
+
+impl Resolver<'_> {
+    fn visitar(&mut self, inclusiones: &[Inclusion], base: &Path) {
+        for inclusion in inclusiones {
+            let ruta = base.join(&inclusion.ruta);
+            let canonica = match fs::canonicalize(&ruta) {
+                Ok(canonica) => canonica,
+                Err(e) => {
+                    self.error(
+                        &inclusion.location,
+                        format!("No se pudo abrir el archivo incluido '{}': {}", ruta.display(), e),
+                    );
+                    continue;
+                }
+            };
+
+            if let Some(inicio) = self.pila.iter().position(|(r, _)| *r == canonica) {
+                let mut cadena: Vec<&str> = self.pila[inicio..].iter().map(|(_, id)| self.sources.name(*id)).collect();
+                cadena.push(self.sources.name(self.pila[inicio].1));
+                let mensaje = format!("Inclusión circular: {}", cadena.join(" → "));
+                self.error(&inclusion.location, mensaje);
+                continue;
+            }
+            if self.cargados.contains_key(&canonica) {
+                continue;
+            }
+
+            let fuente = match fs::read_to_string(&ruta) {
+                Ok(fuente) => fuente,
+                Err(e) => {
+                    self.error(
+                        &inclusion.location,
+                        format!("No se pudo leer el archivo incluido '{}': {}", ruta.display(), e),
+                    );
+                    continue;
+                }
+            };
+            let id = self.sources.add(ruta.display().to_string(), fuente);
+            self.cargados.insert(canonica.clone(), id);
+
+            let fuente = &self.sources.get(id).expect("archivo recién registrado").source;
+            let mut biblioteca = match (self.analizar)(fuente) {
+                Ok(biblioteca) => biblioteca,
+                Err(errores) => {
+                    self.errores.extend(errores.into_iter().map(|e| e.in_file(id)));
+                    continue;
+                }
+            };
+            if !biblioteca.biblioteca {
+                let diagnostico = Diagnostic::semantic_error(
+                    inclusion.location.line,
+                    inclusion.location.column,
+                    inclusion.location.length,
+                    format!("'{}' no es una biblioteca", inclusion.ruta),
+                )
+                .with_help("un archivo incluido solo puede tener incluye, define y modulo (sin programa ni bloque inicio)".to_string())
+                .in_file(inclusion.location.archivo);
+                self.errores.push(diagnostico);
+                continue;
+            }
+            biblioteca.asignar_archivo(id);
+
+            self.pila.push((canonica, id));
+            self.visitar(&biblioteca.inclusiones, directorio(&ruta));
+            self.pila.pop();
+            self.bibliotecas.push(biblioteca);
+        }
+    }
+
+    fn error(&mut self, location: &Location, message: String) {
+        self.errores.push(
+            Diagnostic::semantic_error(location.line, location.column, location.length, message)
+                .in_file(location.archivo),
+        );
+    }
+}
+
+/// Directorio contra el que se resuelven las inclusiones de `ruta`
+fn directorio(ruta: &Path) -> &Path {
+    ruta.parent().unwrap_or_else(|| Path::new(""))
+}
+
+/// Antepone las definiciones y los módulos de las bibliotecas a los del
+/// programa
+///
+/// Un nombre definido en dos archivos distintos es un error que se reporta
+/// aquí, con el archivo de la primera definición; los repetidos dentro de un
+/// mismo archivo los reporta el análisis semántico.
+fn unir(programa: &mut Program, bibliotecas: Vec<Program>, sources: &SourceMap) -> Vec<Diagnostic> {
+    let mut errores = Vec::new();
+    let mut objetos: HashMap<String, Location> = HashMap::new();
+    let mut modulos: HashMap<String, Location> = HashMap::new();
+
+    for parte in bibliotecas.iter().chain(std::iter::once(&*programa)) {
+        let definiciones = &parte.definiciones;
+        let nombres = definiciones.maquinas.iter().map(|d| (&d.nombre, &d.location))
+            .chain(definiciones.concentradores.iter().map(|d| (&d.nombre, &d.location)))
+            .chain(definiciones.coaxiales.iter().map(|d| (&d.nombre, &d.location)))
+            .chain(definiciones.enteros.iter().map(|d| (&d.nombre, &d.location)));
+        for (nombre, location) in nombres {
+            if let Some(error) = registrar(&mut objetos, nombre, location, "El nombre", sources) {
+                errores.push(error);
+            }
+        }
+        for modulo in &parte.modulos {
+            if let Some(error) = registrar(&mut modulos, &modulo.nombre, &modulo.location, "El módulo", sources) {
+                errores.push(error);
+            }
+        }
+    }
+
+    if errores.is_empty() {
+        let mut definiciones = Definitions { location: programa.definiciones.location.clone(), ..Definitions::empty() };
+        let mut modulos = Vec::new();
+        for parte in bibliotecas.into_iter().chain(std::iter::once(programa.clone())) {
+            definiciones.maquinas.extend(parte.definiciones.maquinas);
+            definiciones.concentradores.extend(parte.definiciones.concentradores);
+            definiciones.coaxiales.extend(parte.definiciones.coaxiales);
+            definiciones.enteros.extend(parte.definiciones.enteros);
+            modulos.extend(parte.modulos);
+        }
+        programa.definiciones = definiciones;
+        programa.modulos = modulos;
+    }
+    errores
+}
+
+/// Registra la primera definición de `nombre`; si ya había una en otro
+/// archivo devuelve el error
+fn registrar(
+    definidos: &mut HashMap<String, Location>,
+    nombre: &str,
+    location: &Location,
+    clase: &str,
+    sources: &SourceMap,
+) -> Option<Diagnostic> {
+    match definidos.get(nombre) {
+        Some(previa) if previa.archivo != location.archivo => Some(
+            Diagnostic::semantic_error(
+                location.line,
+                location.column,
+                location.length,
+                format!(
+                    "{} '{}' ya fue definido en {}:{}:{}",
+                    clase,
+                    nombre,
+                    sources.name(previa.archivo),
+                    previa.line,
+                    previa.column
+                ),
+            )
+            .in_file(location.archivo),
+        ),
+        Some(_) => None,
+        None => {
+            definidos.insert(nombre.to_string(), location.clone());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Statement;
+    use crate::lexer_new::tokenize;
+    use crate::parser_ll1::PredictiveParser;
+
+    fn analizar(source: &str) -> Result<Program, Vec<Diagnostic>> {
+        let tokens = tokenize(source).expect("el código de prueba es léxicamente válido");
+        PredictiveParser::new(tokens).parse()
+    }
+
+    /// Escribe los archivos en un directorio temporal propio de la prueba
+    fn archivos(prueba: &str, archivos: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("inclusion_{}_{}", prueba, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (nombre, contenido) in archivos {
+            let ruta = dir.join(nombre);
+            fs::create_dir_all(ruta.parent().unwrap()).unwrap();
+            fs::write(ruta, contenido).unwrap();
+        }
+        dir
+    }
+
+    fn resolver(dir: &Path, principal: &str) -> (Result<Program, Vec<Diagnostic>>, SourceMap) {
+        let ruta = dir.join(principal);
+        let source = fs::read_to_string(&ruta).unwrap();
+        let mut sources = SourceMap::new();
+        sources.add(ruta.display().to_string(), source.clone());
+
+        let mut programa = analizar(&source).unwrap();
+        let resultado = resolver_inclusiones(&mut programa, &mut sources, analizar).map(|_| programa);
+        (resultado, sources)
+    }
+
+    #[test]
+    fn test_merges_libraries_with_file_ids() {
+        let dir = archivos("merge", &[
+            ("red.net", "programa red;\nincluye \"lib/comun.net\";\nincluye \"otra.net\";\ndefine maquinas A;\ninicio\n  conecta(A);\nfin."),
+            ("otra.net", "incluye \"lib/comun.net\";\ndefine maquinas B;\n"),
+            ("lib/comun.net", "define concentradores hub = 4;\nmodulo conecta(maquina m);\ninicio\n  asignaPuerto(m, hub);\nfin\n"),
+        ]);
+
+        let (resultado, sources) = resolver(&dir, "red.net");
+        let programa = resultado.unwrap();
+
+        // comun.net se carga una sola vez aunque otra.net también la incluya
+        let nombres: Vec<&str> = programa.definiciones.maquinas.iter().map(|m| m.nombre.as_str()).collect();
+        assert_eq!(nombres, ["B", "A"]);
+        assert_eq!(programa.definiciones.concentradores.len(), 1);
+        assert_eq!(programa.modulos.len(), 1);
+
+        let hub = &programa.definiciones.concentradores[0].location;
+        assert!(sources.name(hub.archivo).ends_with("comun.net"));
+        assert_eq!((hub.line, hub.column), (1, 23));
+        assert!(matches!(
+            &programa.modulos[0].sentencias[0],
+            Statement::AsignaPuerto { location, .. } if location.archivo == hub.archivo
+        ));
+        assert!(sources.name(programa.definiciones.maquinas[0].location.archivo).ends_with("otra.net"));
+        assert_eq!(programa.definiciones.maquinas[1].location.archivo, 0);
+    }
+
+    #[test]
+    fn test_include_cycle() {
+        let dir = archivos("ciclo", &[
+            ("red.net", "programa red;\nincluye \"a.net\";\ninicio\nfin."),
+            ("a.net", "incluye \"b.net\";\n"),
+            ("b.net", "/// Vuelve a incluir a.net\nincluye \"a.net\";\ndefine maquinas B;\n"),
+        ]);
+
+        let (resultado, sources) = resolver(&dir, "red.net");
+        let errores = resultado.unwrap_err();
+
+        assert_eq!(errores.len(), 1, "{:?}", errores);
+        assert!(errores[0].message.starts_with("Inclusión circular: "));
+        assert!(errores[0].message.contains("a.net → ") && errores[0].message.contains("b.net → "));
+        assert!(sources.name(errores[0].file).ends_with("b.net"));
+        assert_eq!(errores[0].line, 2);
+    }
+
+    #[test]
+    fn test_duplicate_definition_across_files() {
+        let dir = archivos("duplicado", &[
+            ("red.net", "programa red;\nincluye \"comun.net\";\ndefine maquinas A, hub;\ninicio\nfin."),
+            ("comun.net", "define concentradores hub = 4;\n"),
+        ]);
+
+        let (resultado, _) = resolver(&dir, "red.net");
+        let errores = resultado.unwrap_err();
+
+        assert_eq!(errores.len(), 1);
+        assert_eq!(errores[0].file, 0);
+        assert_eq!((errores[0].line, errores[0].column), (3, 20));
+        assert!(errores[0].message.starts_with("El nombre 'hub' ya fue definido en "));
+        assert!(errores[0].message.ends_with("comun.net:1:23"));
+    }
+
+    #[test]
+    fn test_included_file_must_be_library() {
+        let dir = archivos("no_biblioteca", &[
+            ("red.net", "programa red;\nincluye \"otro.net\";\ninicio\nfin."),
+            ("otro.net", "programa otro;\ninicio\nfin."),
+        ]);
+
+        let (resultado, _) = resolver(&dir, "red.net");
+        let errores = resultado.unwrap_err();
+
+        assert_eq!(errores[0].message, "'otro.net' no es una biblioteca");
+        assert_eq!((errores[0].file, errores[0].line), (0, 2));
+    }
+}
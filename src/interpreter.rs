@@ -167,7 +167,8 @@ pub struct Environment {
     pub maquinas: HashMap<String, RuntimeMaquina>,
     pub concentradores: HashMap<String, RuntimeConcentrador>,
     pub coaxiales: HashMap<String, RuntimeCoaxial>,
-    pub modulos: HashMap<String, Vec<Statement>>,
+    pub modulos: HashMap<String, Modulo>,
+    pub variables: HashMap<String, i32>,
     pub output: Vec<String>,
 }
 
@@ -178,6 +179,7 @@ impl Environment {
             concentradores: HashMap::new(),
             coaxiales: HashMap::new(),
             modulos: HashMap::new(),
+            variables: HashMap::new(),
             output: Vec::new(),
         }
     }
@@ -209,6 +211,11 @@ impl Environment {
                 RuntimeCoaxial::new(nombre.clone(), sym.longitud)
             );
         }
+
+        // Las variables enteras empiezan en 0
+        for nombre in symbol_table.variables.keys() {
+            self.variables.insert(nombre.clone(), 0);
+        }
     }
 
     pub fn escribir(&mut self, mensaje: String) {
@@ -224,9 +231,23 @@ impl Environment {
 // INTÉRPRETE
 // ============================================================================
 
+/// Vueltas de ciclos que puede dar una ejecución completa antes de abortar;
+/// los ciclos anidados comparten el presupuesto
+pub const LIMITE_ITERACIONES: u64 = 100_000;
+
+/// Marco de una llamada a módulo: a qué se ligó cada parámetro
+#[derive(Debug, Clone)]
+struct Marco {
+    modulo: String,
+    objetos: HashMap<String, String>, // Parámetro -> nombre del objeto recibido
+    enteros: HashMap<String, i32>,    // Parámetros enteros (copias locales a la llamada)
+}
+
 pub struct Interpreter {
     pub env: Environment,
-    module_call_stack: Vec<String>, // Track module calls to detect cycles
+    module_call_stack: Vec<Marco>, // Un marco por llamada activa; también detecta ciclos
+    limite_iteraciones: u64,        // Para toda la ejecución; evita que un `mientras` se cuelgue
+    iteraciones: u64,               // Vueltas de ciclos dadas hasta ahora
 }
 
 impl Interpreter {
@@ -236,16 +257,25 @@ impl Interpreter {
         Self {
             env,
             module_call_stack: Vec::new(),
+            limite_iteraciones: LIMITE_ITERACIONES,
+            iteraciones: 0,
         }
     }
 
+    /// Cambia el número máximo de iteraciones de la ejecución
+    pub fn with_limite_iteraciones(mut self, limite: u64) -> Self {
+        self.limite_iteraciones = limite;
+        self
+    }
+
     pub fn ejecutar(&mut self, program: &Program) -> Result<(), String> {
         // Registrar módulos
         for modulo in &program.modulos {
-            self.env.modulos.insert(modulo.nombre.clone(), modulo.sentencias.clone());
+            self.env.modulos.insert(modulo.nombre.clone(), modulo.clone());
         }
 
         // Ejecutar sentencias principales
+        self.iteraciones = 0;
         for stmt in &program.sentencias {
             self.exec_statement(stmt)?;
         }
@@ -253,6 +283,76 @@ impl Interpreter {
         Ok(())
     }
 
+    /// Objeto al que se refiere `nombre`: el argumento si es un parámetro del
+    /// módulo en ejecución, o el propio nombre si es un objeto global
+    fn objeto(&self, nombre: &str) -> String {
+        self.module_call_stack.last()
+            .and_then(|marco| marco.objetos.get(nombre))
+            .cloned()
+            .unwrap_or_else(|| nombre.to_string())
+    }
+
+    /// Valor modificable de una variable entera (parámetro local o global)
+    fn variable_mut(&mut self, nombre: &str) -> Option<&mut i32> {
+        if let Some(valor) = self.module_call_stack.last_mut().and_then(|m| m.enteros.get_mut(nombre)) {
+            return Some(valor);
+        }
+        self.env.variables.get_mut(nombre)
+    }
+
+    /// Marco de una llamada: cada parámetro queda ligado a su argumento
+    fn ligar_argumentos(&self, modulo: &Modulo, argumentos: &[Expr]) -> Result<Marco, String> {
+        if argumentos.len() != modulo.parametros.len() {
+            return Err(format!(
+                "El módulo '{}' espera {} argumento(s) pero se le pasaron {}",
+                modulo.nombre, modulo.parametros.len(), argumentos.len()
+            ));
+        }
+
+        let mut marco = Marco {
+            modulo: modulo.nombre.clone(),
+            objetos: HashMap::new(),
+            enteros: HashMap::new(),
+        };
+
+        for (parametro, argumento) in modulo.parametros.iter().zip(argumentos) {
+            match (parametro.tipo, argumento) {
+                (TipoParametro::Entero, _) => {
+                    let valor = self.eval_expression(argumento)?;
+                    let n = valor.as_int().ok_or_else(|| format!(
+                        "El parámetro '{}' de '{}' es entero pero recibió {:?}",
+                        parametro.nombre, modulo.nombre, valor
+                    ))?;
+                    marco.enteros.insert(parametro.nombre.clone(), n);
+                }
+                (_, Expr::Identificador(objeto)) => {
+                    marco.objetos.insert(parametro.nombre.clone(), self.objeto(objeto));
+                }
+                _ => {
+                    return Err(format!(
+                        "El parámetro '{}' de '{}' requiere el nombre de un objeto",
+                        parametro.nombre, modulo.nombre
+                    ));
+                }
+            }
+        }
+
+        Ok(marco)
+    }
+
+    /// Cuenta una vuelta de un ciclo; error si la ejecución supera el límite
+    /// configurado (sumando las vueltas de todos los ciclos)
+    fn contar_iteracion(&mut self, ciclo: &str) -> Result<(), String> {
+        self.iteraciones += 1;
+        if self.iteraciones > self.limite_iteraciones {
+            return Err(format!(
+                "El ciclo '{}' superó el límite de {} iteraciones de la ejecución (¿ciclo infinito?)",
+                ciclo, self.limite_iteraciones
+            ));
+        }
+        Ok(())
+    }
+
     // Evaluar expresiones
     pub fn eval_expression(&self, expr: &Expr) -> Result<Value, String> {
         match expr {
@@ -261,8 +361,12 @@ impl Interpreter {
             Expr::Cadena(s) => Ok(Value::String(s.clone())),
 
             Expr::Identificador(nombre) => {
-                // Los identificadores de objetos no se pueden evaluar directamente
-                Err(format!("No se puede evaluar el identificador '{}' como valor", nombre))
+                // Solo las variables tienen valor; los objetos no se pueden evaluar directamente
+                let parametro = self.module_call_stack.last().and_then(|m| m.enteros.get(nombre));
+                match parametro.or_else(|| self.env.variables.get(nombre)) {
+                    Some(valor) => Ok(Value::Int(*valor)),
+                    None => Err(format!("No se puede evaluar el identificador '{}' como valor", nombre)),
+                }
             }
 
             Expr::AccesoCampo { objeto, campo } => {
@@ -292,10 +396,53 @@ impl Interpreter {
                     None => Err(format!("No se puede aplicar NOT a {:?}", val))
                 }
             }
+
+            Expr::Aritmetico { izq, op, der } => {
+                let val_izq = self.eval_expression(izq)?;
+                let val_der = self.eval_expression(der)?;
+                self.eval_aritmetico(&val_izq, op, &val_der)
+            }
+
+            Expr::Negativo(expr) => {
+                let val = self.eval_expression(expr)?;
+                let n = val.as_int()
+                    .ok_or_else(|| format!("No se puede aplicar '-' a {:?}", val))?;
+                n.checked_neg()
+                    .map(Value::Int)
+                    .ok_or_else(|| format!("Desbordamiento aritmético: -({})", n))
+            }
         }
     }
 
+    /// Operación entera; la división entre cero y el desbordamiento son
+    /// errores de ejecución
+    fn eval_aritmetico(&self, izq: &Value, op: &OpAritmetico, der: &Value) -> Result<Value, String> {
+        let a = izq.as_int()
+            .ok_or_else(|| format!("Operando izquierdo de '{}' no es entero: {:?}", op.simbolo(), izq))?;
+        let b = der.as_int()
+            .ok_or_else(|| format!("Operando derecho de '{}' no es entero: {:?}", op.simbolo(), der))?;
+
+        if b == 0 && matches!(op, OpAritmetico::Division | OpAritmetico::Modulo) {
+            return Err(format!("División entre cero: {} {} {}", a, op.simbolo(), b));
+        }
+
+        let resultado = match op {
+            OpAritmetico::Suma => a.checked_add(b),
+            OpAritmetico::Resta => a.checked_sub(b),
+            OpAritmetico::Multiplicacion => a.checked_mul(b),
+            OpAritmetico::Division => a.checked_div(b),
+            OpAritmetico::Modulo => a.checked_rem(b),
+        };
+
+        resultado
+            .map(Value::Int)
+            .ok_or_else(|| format!("Desbordamiento aritmético: {} {} {}", a, op.simbolo(), b))
+    }
+
     fn eval_campo_acceso(&self, objeto: &str, campo: &str) -> Result<Value, String> {
+        let objeto = self.objeto(objeto);
+        let objeto = objeto.as_str();
+
         // Verificar si es un concentrador
         if let Some(conc) = self.env.concentradores.get(objeto) {
             return match campo {
@@ -336,7 +483,8 @@ impl Interpreter {
         if objeto.contains('.') {
             let parts: Vec<&str> = objeto.split('.').collect();
             if parts.len() == 2 && parts[1] == "p" {
-                let conc_nombre = parts[0];
+                let conc_nombre = self.objeto(parts[0]);
+                let conc_nombre = conc_nombre.as_str();
                 if let Some(conc) = self.env.concentradores.get(conc_nombre) {
                     if idx > 0 && idx <= conc.puertos {
                         let ocupado = conc.puertos_ocupados[(idx - 1) as usize];
@@ -390,6 +538,9 @@ impl Interpreter {
     fn exec_statement(&mut self, stmt: &Statement) -> Result<(), String> {
         match stmt {
             Statement::Coloca { objeto, x, y, .. } => {
+                // Los parámetros de módulo se sustituyen por el objeto que recibieron
+                let objeto = &self.objeto(objeto);
+
                 let x_val = self.eval_expression(x)?.as_int()
                     .ok_or("La coordenada X debe ser un entero")?;
                 let y_val = self.eval_expression(y)?.as_int()
@@ -415,6 +566,8 @@ impl Interpreter {
             }
 
             Statement::ColocaCoaxial { coaxial, x, y, direccion, .. } => {
+                let coaxial = &self.objeto(coaxial);
+
                 let x_val = self.eval_expression(x)?.as_int()
                     .ok_or("La coordenada X debe ser un entero")?;
                 let y_val = self.eval_expression(y)?.as_int()
@@ -432,6 +585,9 @@ impl Interpreter {
             }
 
             Statement::ColocaCoaxialConcentrador { coaxial, concentrador, .. } => {
+                let coaxial = &self.objeto(coaxial);
+                let concentrador = &self.objeto(concentrador);
+
                 // Verificar que el concentrador tenga salida coaxial
                 if let Some(conc) = self.env.concentradores.get_mut(concentrador) {
                     if !conc.tiene_coaxial {
@@ -451,6 +607,9 @@ impl Interpreter {
             }
 
             Statement::UneMaquinaPuerto { maquina, concentrador, puerto, .. } => {
+                let maquina = &self.objeto(maquina);
+                let concentrador = &self.objeto(concentrador);
+
                 let puerto_num = self.eval_expression(puerto)?.as_int()
                     .ok_or("El puerto debe ser un entero")?;
 
@@ -479,6 +638,9 @@ impl Interpreter {
             }
 
             Statement::AsignaPuerto { maquina, concentrador, .. } => {
+                let maquina = &self.objeto(maquina);
+                let concentrador = &self.objeto(concentrador);
+
                 // Verificar que la máquina existe
                 if !self.env.maquinas.contains_key(maquina) {
                     return Err(format!("Máquina '{}' no encontrada", maquina));
@@ -505,6 +667,9 @@ impl Interpreter {
             }
 
             Statement::MaquinaCoaxial { maquina, coaxial, posicion, .. } => {
+                let maquina = &self.objeto(maquina);
+                let coaxial = &self.objeto(coaxial);
+
                 let pos_val = self.eval_expression(posicion)?.as_int()
                     .ok_or("La posición debe ser un entero")?;
 
@@ -530,6 +695,9 @@ impl Interpreter {
             }
 
             Statement::AsignaMaquinaCoaxial { maquina, coaxial, .. } => {
+                let maquina = &self.objeto(maquina);
+                let coaxial = &self.objeto(coaxial);
+
                 // Verificar que la máquina existe
                 if !self.env.maquinas.contains_key(maquina) {
                     return Err(format!("Máquina '{}' no encontrada", maquina));
@@ -576,29 +744,89 @@ impl Interpreter {
                 Ok(())
             }
 
-            Statement::Si { condicion, entonces, sino, .. } => {
-                let cond_val = self.eval_expression(condicion)?;
-                let es_verdadero = cond_val.as_bool()
-                    .ok_or("La condición debe ser booleana")?;
+            Statement::Si { condicion, entonces, sino_si, sino, .. } => {
+                // Se ejecuta la primera rama cuya condición se cumpla
+                let ramas = std::iter::once((condicion, entonces))
+                    .chain(sino_si.iter().map(|rama| (&rama.condicion, &rama.sentencias)));
+                for (condicion, sentencias) in ramas {
+                    let cond_val = self.eval_expression(condicion)?;
+                    let es_verdadero = cond_val.as_bool()
+                        .ok_or("La condición debe ser booleana")?;
+
+                    if es_verdadero {
+                        for stmt in sentencias {
+                            self.exec_statement(stmt)?;
+                        }
+                        return Ok(());
+                    }
+                }
 
-                if es_verdadero {
-                    for stmt in entonces {
+                if let Some(sino_stmts) = sino {
+                    for stmt in sino_stmts {
                         self.exec_statement(stmt)?;
                     }
-                } else if let Some(sino_stmts) = sino {
-                    for stmt in sino_stmts {
+                }
+                Ok(())
+            }
+
+            Statement::Mientras { condicion, cuerpo, .. } => {
+                loop {
+                    let cond_val = self.eval_expression(condicion)?;
+                    let es_verdadero = cond_val.as_bool()
+                        .ok_or("La condición debe ser booleana")?;
+                    if !es_verdadero {
+                        return Ok(());
+                    }
+
+                    self.contar_iteracion("mientras")?;
+                    for stmt in cuerpo {
+                        self.exec_statement(stmt)?;
+                    }
+                }
+            }
+
+            Statement::Para { variable, desde, hasta, cuerpo, .. } => {
+                // Los límites se evalúan una sola vez, antes de la primera vuelta
+                let desde_val = self.eval_expression(desde)?;
+                let inicio = desde_val.as_int()
+                    .ok_or_else(|| format!("El límite inicial del ciclo 'para' no es entero: {:?}", desde_val))?;
+                let hasta_val = self.eval_expression(hasta)?;
+                let fin = hasta_val.as_int()
+                    .ok_or_else(|| format!("El límite final del ciclo 'para' no es entero: {:?}", hasta_val))?;
+
+                for valor in inicio..=fin {
+                    self.contar_iteracion("para")?;
+                    match self.variable_mut(variable) {
+                        Some(actual) => *actual = valor,
+                        None => return Err(format!("Variable '{}' no encontrada", variable)),
+                    }
+                    for stmt in cuerpo {
                         self.exec_statement(stmt)?;
                     }
                 }
                 Ok(())
             }
 
-            Statement::LlamadaModulo { nombre, .. } => {
+            Statement::Asignacion { variable, valor, .. } => {
+                let val = self.eval_expression(valor)?;
+                let n = val.as_int()
+                    .ok_or_else(|| format!("No se puede asignar {:?} a la variable entera '{}'", val, variable))?;
+
+                match self.variable_mut(variable) {
+                    Some(actual) => {
+                        *actual = n;
+                        Ok(())
+                    }
+                    None => Err(format!("Variable '{}' no encontrada", variable)),
+                }
+            }
+
+            Statement::LlamadaModulo { nombre, argumentos, .. } => {
                 // Verificar si el módulo ya está en la pila de llamadas (ciclo)
-                if self.module_call_stack.contains(nombre) {
+                if self.module_call_stack.iter().any(|marco| &marco.modulo == nombre) {
                     // Construir la cadena de llamadas para el error
-                    let mut call_chain = self.module_call_stack.clone();
-                    call_chain.push(nombre.clone());
+                    let mut call_chain: Vec<&str> = self.module_call_stack.iter().map(|m| m.modulo.as_str()).collect();
+                    call_chain.push(nombre);
                     let chain_str = call_chain.join(" -> ");
                     return Err(format!(
                         "Llamada recursiva detectada: {}\nLos módulos no pueden llamarse a sí mismos directa o indirectamente",
@@ -607,13 +835,14 @@ impl Interpreter {
                 }
 
                 // Obtener las sentencias del módulo
-                if let Some(stmts) = self.env.modulos.get(nombre).cloned() {
-                    // Agregar módulo a la pila de llamadas
-                    self.module_call_stack.push(nombre.clone());
+                if let Some(modulo) = self.env.modulos.get(nombre).cloned() {
+                    // Los argumentos se evalúan en el marco de quien llama
+                    let marco = self.ligar_argumentos(&modulo, argumentos)?;
+                    self.module_call_stack.push(marco);
 
                     // Ejecutar sentencias del módulo
                     let result = (|| {
-                        for stmt in &stmts {
+                        for stmt in &modulo.sentencias {
                             self.exec_statement(stmt)?;
                         }
                         Ok(())
@@ -630,3 +859,202 @@ impl Interpreter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer_new::tokenize;
+    use crate::parser_ll1::PredictiveParser;
+    use crate::semantic::SemanticAnalyzer;
+
+    /// Intérprete listo para ejecutar `source` (que debe pasar el análisis semántico)
+    fn preparar(source: &str) -> (Interpreter, Program) {
+        let program = PredictiveParser::new(tokenize(source).unwrap()).parse().unwrap();
+        let mut analyzer = SemanticAnalyzer::new();
+        if let Err(errors) = analyzer.analyze(&program) {
+            panic!("errores semánticos: {:?}", errors);
+        }
+        (Interpreter::new(&analyzer.symbol_table), program)
+    }
+
+    /// Salida de `escribe` de un programa que termina sin errores
+    fn salida(source: &str) -> String {
+        let (mut interpreter, program) = preparar(source);
+        interpreter.ejecutar(&program).unwrap();
+        interpreter.env.obtener_output()
+    }
+
+    /// Error de ejecución de un programa que debe fallar
+    fn error(source: &str) -> String {
+        let (mut interpreter, program) = preparar(source);
+        interpreter.ejecutar(&program).expect_err("el programa debía fallar")
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let source = "programa p;\ninicio\n\
+                      escribe(2 + 3 * 4);\n\
+                      escribe((2 + 3) * 4);\n\
+                      escribe(7 / 2);\n\
+                      escribe(-7 % 3);\n\
+                      escribe(-(2 - 5));\n\
+                      escribe(10 - 4 - 3);\nfin.";
+        assert_eq!(salida(source), "14\n20\n3\n-1\n3\n3");
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        assert_eq!(
+            error("programa p;\ninicio\n  escribe(10 / 0);\nfin."),
+            "División entre cero: 10 / 0"
+        );
+        assert_eq!(
+            error("programa p;\ndefine enteros x;\ninicio\n  escribe(10 % x);\nfin."),
+            "División entre cero: 10 % 0"
+        );
+    }
+
+    #[test]
+    fn test_overflow_is_reported() {
+        assert_eq!(
+            error("programa p;\ninicio\n  escribe(2147483647 + 1);\nfin."),
+            "Desbordamiento aritmético: 2147483647 + 1"
+        );
+        assert_eq!(
+            error("programa p;\ninicio\n  escribe(65536 * 65536);\nfin."),
+            "Desbordamiento aritmético: 65536 * 65536"
+        );
+
+        // i32::MIN se obtiene restando; su negación y su división entre -1 desbordan
+        let min = "programa p;\ndefine enteros x;\ninicio\n  x = -2147483647 - 1;\n  escribe(x);\n";
+        assert_eq!(salida(&format!("{}fin.", min)), "-2147483648");
+        assert_eq!(error(&format!("{}  escribe(-x);\nfin.", min)), "Desbordamiento aritmético: -(-2147483648)");
+        assert_eq!(
+            error(&format!("{}  escribe(x / -1);\nfin.", min)),
+            "Desbordamiento aritmético: -2147483648 / -1"
+        );
+    }
+
+    #[test]
+    fn test_variables_store_values() {
+        let source = "programa p;\ndefine enteros x, y, sinAsignar;\ninicio\n\
+                      x = 5;\n\
+                      y = x * 2;\n\
+                      x = x + y;\n\
+                      escribe(x);\nfin.";
+        let (mut interpreter, program) = preparar(source);
+        interpreter.ejecutar(&program).unwrap();
+
+        // Las variables empiezan en 0 y guardan el último valor asignado
+        let variables = &interpreter.env.variables;
+        assert_eq!(variables["x"], 15);
+        assert_eq!(variables["y"], 10);
+        assert_eq!(variables["sinAsignar"], 0);
+        assert_eq!(interpreter.env.obtener_output(), "15");
+    }
+
+    #[test]
+    fn test_loops_terminate() {
+        let source = "programa p;\ndefine enteros i, n;\ninicio\n\
+                      para i = 1 hasta 3 inicio escribe(i); fin\n\
+                      escribe(i);\n\
+                      n = 2;\n\
+                      para i = 1 hasta n inicio n = n + 1; fin\n\
+                      escribe(n);\n\
+                      para i = 5 hasta 1 inicio escribe(i); fin\n\
+                      i = 0;\n\
+                      mientras (i < 4) inicio i = i + 1; fin\n\
+                      escribe(i);\nfin.";
+
+        // `hasta` es inclusivo, los límites se evalúan una vez, un rango vacío
+        // no ejecuta el cuerpo y la variable conserva el último valor
+        assert_eq!(salida(source), "1\n2\n3\n3\n4\n4");
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        assert_eq!(
+            error("programa p;\ninicio\n  mientras (1 = 1) inicio fin\nfin."),
+            format!("El ciclo 'mientras' superó el límite de {} iteraciones de la ejecución (¿ciclo infinito?)", LIMITE_ITERACIONES)
+        );
+        assert_eq!(
+            error("programa p;\ndefine enteros i;\ninicio\n  para i = 0 hasta 100000 inicio fin\nfin."),
+            "El ciclo 'para' superó el límite de 100000 iteraciones de la ejecución (¿ciclo infinito?)"
+        );
+    }
+
+    /// Ejecuta `source` con un límite de `limite` iteraciones
+    fn con_limite(source: &str, limite: u64) -> Result<String, String> {
+        let (interpreter, program) = preparar(source);
+        let mut interpreter = interpreter.with_limite_iteraciones(limite);
+        interpreter.ejecutar(&program).map(|_| interpreter.env.obtener_output())
+    }
+
+    #[test]
+    fn test_custom_iteration_limit() {
+        // El límite es de toda la ejecución: dos ciclos suman sus vueltas
+        let cinco = "programa p;\ndefine enteros i;\ninicio\n\
+                     para i = 1 hasta 2 inicio fin\n\
+                     i = 0;\n\
+                     mientras (i < 3) inicio i = i + 1; fin\n\
+                     escribe(i);\nfin.";
+        assert_eq!(con_limite(cinco, 5).unwrap(), "3");
+        assert_eq!(
+            con_limite(cinco, 4).unwrap_err(),
+            "El ciclo 'mientras' superó el límite de 4 iteraciones de la ejecución (¿ciclo infinito?)"
+        );
+
+        assert_eq!(
+            con_limite("programa p;\ndefine enteros i;\ninicio\n  para i = 1 hasta 6 inicio fin\nfin.", 5).unwrap_err(),
+            "El ciclo 'para' superó el límite de 5 iteraciones de la ejecución (¿ciclo infinito?)"
+        );
+    }
+
+    #[test]
+    fn test_nested_loops_share_the_iteration_limit() {
+        // 3 vueltas del ciclo externo y 3 × 3 del interno: 12 en total
+        let anidados = "programa p;\ndefine enteros i, j, n;\ninicio\n\
+                        para i = 1 hasta 3 inicio\n\
+                          j = 0;\n\
+                          mientras (j < 3) inicio j = j + 1; n = n + 1; fin\n\
+                        fin\n\
+                        escribe(n);\nfin.";
+        assert_eq!(con_limite(anidados, 12).unwrap(), "9");
+        assert_eq!(
+            con_limite(anidados, 11).unwrap_err(),
+            "El ciclo 'mientras' superó el límite de 11 iteraciones de la ejecución (¿ciclo infinito?)"
+        );
+    }
+
+    #[test]
+    fn test_module_frames() {
+        let source = "programa p;\ndefine maquinas A, B;\ndefine concentradores hub = 4;\ndefine enteros n;\n\
+                      modulo ubica(maquina hub, entero n);\ninicio\n\
+                        n = n * 10;\n\
+                        coloca(hub, n, n + 1);\n\
+                        escribe(n);\nfin\n\
+                      inicio\n\
+                        n = 7;\n\
+                        ubica(A, 2);\n\
+                        ubica(B, n);\n\
+                        escribe(n);\n\
+                        coloca(hub, 100, 200);\nfin.";
+        let (mut interpreter, program) = preparar(source);
+        interpreter.ejecutar(&program).unwrap();
+        let env = &interpreter.env;
+
+        // El parámetro entero es una copia: el global `n` no cambia
+        assert_eq!(env.obtener_output(), "20\n70\n7");
+        assert_eq!(env.variables["n"], 7);
+
+        // Dentro del módulo `hub` es la máquina recibida, no el concentrador
+        assert_eq!((env.maquinas["A"].x, env.maquinas["A"].y), (20, 21));
+        assert_eq!((env.maquinas["B"].x, env.maquinas["B"].y), (70, 71));
+        assert!(env.maquinas["B"].colocada);
+
+        // Después de la llamada `hub` vuelve a ser el concentrador global
+        let hub = &env.concentradores["hub"];
+        assert!(hub.colocado);
+        assert_eq!((hub.x, hub.y), (100, 200));
+    }
+}
@@ -1,7 +1,11 @@
 // src/lexer_new/automaton.rs
 // Motor del autómata finito determinista (DFA)
 
-use super::token::TokenType;
+use super::byte_scanner::DenseTable;
+use super::error::AutomatonError;
+use super::spec::parse_bracket_class;
+use super::token::{TokenType, is_combining_mark};
+use once_cell::sync::{Lazy, OnceCell};
 use std::collections::HashMap;
 use std::fs;
 
@@ -36,8 +40,40 @@ pub enum CharClass {
 
     /// Cualquier carácter excepto newline (para comentarios)
     AnyExceptNewline,
+
+    /// Cualquier letra Unicode (á, ñ, ü, ...), incluidas las marcas
+    /// combinantes para admitir texto descompuesto (`o` + tilde)
+    UnicodeLetter,
+
+    /// Cualquier dígito Unicode
+    UnicodeDigit,
+}
+
+/// Letra Unicode o marca combinante
+pub(super) fn is_unicode_letter(ch: char) -> bool {
+    ch.is_alphabetic() || is_combining_mark(ch)
 }
 
+/// Rangos de caracteres que cumplen un predicado (calculados una sola vez)
+fn ranges_where(pred: fn(char) -> bool) -> Vec<(char, char)> {
+    let mut ranges: Vec<(char, char)> = Vec::new();
+    for ch in (0..=char::MAX as u32).filter_map(char::from_u32).filter(|c| pred(*c)) {
+        match ranges.last_mut() {
+            Some((_, end)) if *end as u32 + 1 == ch as u32 => *end = ch,
+            _ => ranges.push((ch, ch)),
+        }
+    }
+    ranges
+}
+
+/// Rangos de letras Unicode
+pub(super) static UNICODE_LETTER_RANGES: Lazy<Vec<(char, char)>> =
+    Lazy::new(|| ranges_where(is_unicode_letter));
+
+/// Rangos de dígitos Unicode
+pub(super) static UNICODE_DIGIT_RANGES: Lazy<Vec<(char, char)>> =
+    Lazy::new(|| ranges_where(char::is_numeric));
+
 impl CharClass {
     /// Verifica si un carácter pertenece a esta clase
     pub fn matches(&self, ch: char) -> bool {
@@ -47,11 +83,40 @@ impl CharClass {
             CharClass::Multi(classes) => classes.iter().any(|c| c.matches(ch)),
             CharClass::Any => true,
             CharClass::AnyExceptNewline => ch != '\n',
+            CharClass::UnicodeLetter => is_unicode_letter(ch),
+            CharClass::UnicodeDigit => ch.is_numeric(),
         }
     }
+
+    /// Rangos de caracteres (inclusivos) que cubre esta clase
+    pub fn ranges(&self) -> Vec<(char, char)> {
+        match self {
+            CharClass::Exact(c) => vec![(*c, *c)],
+            CharClass::Range(start, end) if start <= end => vec![(*start, *end)],
+            CharClass::Range(_, _) => Vec::new(),
+            CharClass::Multi(classes) => classes.iter().flat_map(|c| c.ranges()).collect(),
+            CharClass::Any => vec![('\0', char::MAX)],
+            CharClass::AnyExceptNewline => vec![('\0', '\t'), ('\u{b}', char::MAX)],
+            CharClass::UnicodeLetter => UNICODE_LETTER_RANGES.clone(),
+            CharClass::UnicodeDigit => UNICODE_DIGIT_RANGES.clone(),
+        }
+    }
+
+    /// Devuelve un carácter que pertenece a ambas clases, si existe
+    pub fn overlap(&self, other: &CharClass) -> Option<char> {
+        for (a_start, a_end) in self.ranges() {
+            for (b_start, b_end) in other.ranges() {
+                let start = a_start.max(b_start);
+                if start <= a_end.min(b_end) {
+                    return Some(start);
+                }
+            }
+        }
+        None
+    }
     
     /// Parsea una clase de caracteres desde string
-    /// Ejemplos: "a", "[a-z]", "[A-Z]", "ANY", "[a-zA-Z0-9_]"
+    /// Ejemplos: "a", "[a-z]", "[A-Z]", "ANY", "[a-zA-Z0-9_]", "[^/\\n]"
     pub fn parse(s: &str) -> Result<Self, String> {
         let s = s.trim();
 
@@ -63,62 +128,32 @@ impl CharClass {
             return Ok(CharClass::AnyExceptNewline);
         }
 
+        if s == "UNICODE_LETTER" {
+            return Ok(CharClass::UnicodeLetter);
+        }
+
+        if s == "UNICODE_DIGIT" {
+            return Ok(CharClass::UnicodeDigit);
+        }
+
         // Rango: [a-z] o múltiples como [a-zA-Z_]
         if s.starts_with('[') && s.ends_with(']') {
             let inner = &s[1..s.len() - 1];
 
-            // Ignorar negación por ahora (simplificación)
-            let is_negated = inner.starts_with('^');
-            let inner = inner.trim_start_matches('^');
-
             // Un solo carácter: [a]
-            if inner.len() == 1 {
+            if inner.chars().count() == 1 {
                 return Ok(CharClass::Exact(inner.chars().next().unwrap()));
             }
 
             // Rango simple: a-z
-            if inner.len() == 3 && inner.chars().nth(1) == Some('-') {
-                let start = inner.chars().nth(0).unwrap();
-                let end = inner.chars().nth(2).unwrap();
-                return Ok(CharClass::Range(start, end));
+            let chars: Vec<char> = inner.chars().collect();
+            if chars.len() == 3 && chars[1] == '-' {
+                return Ok(CharClass::Range(chars[0], chars[2]));
             }
 
-            // Para múltiples rangos o caracteres mixtos como [a-zA-Z0-9_]
-            // Por simplicidad, convertiremos esto en un Range que cubra todo
-            // NOTA: Esta es una simplificación. Una implementación completa
-            // necesitaría soportar múltiples rangos simultáneos.
-
-            // Por ahora, manejar los casos más comunes:
-            if inner == "a-zA-Z_" || inner == "a-zA-Z" {
-                // Aceptar letras minúsculas, mayúsculas y guion bajo
-                return Ok(CharClass::Multi(vec![
-                    CharClass::Range('a', 'z'),
-                    CharClass::Range('A', 'Z'),
-                    CharClass::Exact('_'),
-                ]));
-            }
-            if inner == "a-zA-Z0-9_" || inner == "a-zA-Z0-9" {
-                // Aceptar alfanuméricos y guion bajo
-                return Ok(CharClass::Multi(vec![
-                    CharClass::Range('a', 'z'),
-                    CharClass::Range('A', 'Z'),
-                    CharClass::Range('0', '9'),
-                    CharClass::Exact('_'),
-                ]));
-            }
-            if inner == "0-9" {
-                return Ok(CharClass::Range('0', '9'));
-            }
-            if inner == "^\"\\\"" || inner == "^\"\\\\" {
-                // Cualquier cosa excepto comillas y backslash - usar ANY por ahora
-                return Ok(CharClass::Any);
-            }
-            if inner == "^\\n" || inner == "^\n" {
-                // Cualquier cosa excepto newline - usar ANY por ahora
-                return Ok(CharClass::Any);
-            }
-
-            return Err(format!("Clase de caracteres inválida: {}", s));
+            // Múltiples rangos, escapes y negación: [a-zA-Z0-9_], [^"\\], [^/\n]
+            return parse_bracket_class(s)
+                .map_err(|e| format!("Clase de caracteres inválida: {} ({})", s, e));
         }
 
         // Carácter escapado
@@ -158,55 +193,206 @@ impl CharClass {
     }
 }
 
+/// Normaliza un lexema para buscar palabras reservadas: minúsculas y vocales
+/// sin tilde ni diéresis (`Módulo` → `modulo`). La ñ se conserva.
+pub fn fold_keyword(lexeme: &str) -> String {
+    lexeme
+        .to_lowercase()
+        .chars()
+        .filter(|c| !is_combining_mark(*c))
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' | 'ã' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' | 'õ' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            other => other,
+        })
+        .collect()
+}
+
+/// Clave en la tabla de palabras reservadas: las escritas en minúsculas no
+/// distinguen mayúsculas ni acentos; las camelCase se buscan tal cual
+pub(super) fn keyword_key(lexeme: &str) -> String {
+    if lexeme.chars().any(char::is_uppercase) {
+        lexeme.to_string()
+    } else {
+        fold_keyword(lexeme)
+    }
+}
+
 /// Transición del autómata
 #[derive(Debug, Clone)]
 pub struct Transition {
     pub from: StateId,
     pub char_class: CharClass,
     pub to: StateId,
+
+    /// Línea del archivo .aut donde se definió
+    pub line: usize,
+}
+
+/// Entrada de la tabla de palabras reservadas, tal como aparece en el .aut
+#[derive(Debug, Clone)]
+pub struct KeywordEntry {
+    pub lexeme: String,
+    pub token_type: TokenType,
+    pub line: usize,
+}
+
+/// Nombre del dialecto de una sección `KEYWORDS` sin nombre
+pub const DEFAULT_DIALECT: &str = "espanol";
+
+/// Palabras reservadas de un dialecto (`KEYWORDS english` ... `END_KEYWORDS`)
+///
+/// Todos los dialectos producen los mismos tipos de token; solo cambia cómo
+/// se escriben, así que el parser no distingue entre ellos.
+#[derive(Debug, Clone, Default)]
+pub struct Dialect {
+    pub(super) name: String,
+
+    /// Palabras reservadas: lexema → tipo de token
+    pub(super) keywords: HashMap<String, TokenType>,
+
+    /// Palabras reservadas en el orden del archivo (incluye duplicados)
+    pub(super) entries: Vec<KeywordEntry>,
+}
+
+impl Dialect {
+    pub(super) fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Agrega una palabra reservada al dialecto
+    pub(super) fn add(&mut self, entry: KeywordEntry) {
+        self.keywords.insert(keyword_key(&entry.lexeme), entry.token_type.clone());
+        self.entries.push(entry);
+    }
+
+    /// Nombre con el que se declaró el dialecto
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Palabras reservadas en el orden del archivo
+    pub fn entries(&self) -> &[KeywordEntry] {
+        &self.entries
+    }
+
+    /// Clasifica un identificador (keyword o identifier)
+    pub fn classify_identifier(&self, lexeme: &str) -> TokenType {
+        // Buscar en keywords (sin distinguir mayúsculas ni acentos: módulo = MODULO).
+        // Un lexema ASCII en minúsculas ya está normalizado: no hace falta copiarlo
+        let folded = if lexeme.bytes().all(|b| b.is_ascii() && !b.is_ascii_uppercase()) {
+            self.keywords.get(lexeme)
+        } else {
+            self.keywords.get(&fold_keyword(lexeme))
+        };
+        if let Some(token_type) = folded {
+            return token_type.clone();
+        }
+        
+        // Buscar case-sensitive para funciones camelCase
+        if let Some(token_type) = self.keywords.get(lexeme) {
+            return token_type.clone();
+        }
+        
+        TokenType::Identifier
+    }
+
+    /// Primera palabra del dialecto para un tipo de token, si es palabra reservada
+    pub fn spelling(&self, token_type: &TokenType) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| &entry.token_type == token_type)
+            .map(|entry| entry.lexeme.as_str())
+    }
+
+    /// Texto de un tipo de token para los diagnósticos: la palabra reservada
+    /// tal como se escribe en este dialecto, o el nombre genérico del token
+    pub fn display(&self, token_type: &TokenType) -> String {
+        match self.spelling(token_type) {
+            Some(word) => word.to_string(),
+            None => token_type.to_string(),
+        }
+    }
+}
+
+/// Delimitadores de comentario de bloque (METADATA `block_comment: /* */`)
+///
+/// Un DFA no puede contar niveles de anidamiento, así que el Scanner reconoce
+/// estos comentarios directamente antes de ejecutar el autómata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockComment {
+    pub open: String,
+    pub close: String,
+
+    /// Si `/* /* */ */` se considera un solo comentario (`nested_comments: true`)
+    pub nested: bool,
 }
 
 /// Autómata finito determinista
 pub struct Automaton {
     /// Estado inicial
-    initial_state: StateId,
+    pub(super) initial_state: StateId,
     
     /// Lista de transiciones
-    transitions: Vec<Transition>,
+    pub(super) transitions: Vec<Transition>,
     
     /// Estados finales: estado → tipo de token
-    final_states: HashMap<StateId, TokenType>,
+    pub(super) final_states: HashMap<StateId, TokenType>,
     
-    /// Palabras reservadas: lexema → tipo de token
-    keywords: HashMap<String, TokenType>,
+    /// Dialectos de palabras reservadas; el primero es el predeterminado
+    pub(super) dialects: Vec<Dialect>,
     
     /// Mapa de nombres de estado a IDs
-    state_map: HashMap<String, StateId>,
+    pub(super) state_map: HashMap<String, StateId>,
+
+    /// Línea del .aut donde aparece cada estado por primera vez
+    pub(super) state_lines: HashMap<StateId, usize>,
+
+    /// Estados finales marcados como FINAL:ERROR
+    pub(super) error_states: Vec<StateId>,
+
+    /// Comentarios de bloque, si el autómata los declara
+    pub(super) block_comment: Option<BlockComment>,
+
+    /// Tabla densa para `ByteScanner`, calculada al primer uso
+    pub(super) dense: OnceCell<DenseTable>,
 }
 
 impl Automaton {
     /// Carga el autómata desde un archivo .aut
-    pub fn from_file(path: &str) -> Result<Self, String> {
+    pub fn from_file(path: &str) -> Result<Self, AutomatonError> {
         let content = fs::read_to_string(path)
-            .map_err(|e| format!("Error leyendo archivo {}: {}", path, e))?;
+            .map_err(|e| AutomatonError::new(format!("Error leyendo archivo {}: {}", path, e)))?;
         
         Self::parse(&content)
     }
     
     /// Parsea el contenido del archivo .aut
-    pub fn parse(content: &str) -> Result<Self, String> {
+    pub fn parse(content: &str) -> Result<Self, AutomatonError> {
         let mut initial_state = StateId::new(0);
         let mut transitions = Vec::new();
         let mut final_states = HashMap::new();
-        let mut keywords = HashMap::new();
+        let mut dialects = vec![Dialect::new(DEFAULT_DIALECT)];
+        let mut current_dialect = 0;
         let mut state_map: HashMap<String, StateId> = HashMap::new();
+        let mut state_lines: HashMap<StateId, usize> = HashMap::new();
+        let mut error_states = Vec::new();
+        let mut block_comment = None;
+        let mut nested_comments = false;
         let mut next_state_id = 0;
         
         let mut current_section = "";
         let mut initial_state_name: Option<String> = None;
         
-        for (line_num, line) in content.lines().enumerate() {
-            let line = line.trim();
+        for (line_num, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim();
+            let at_line = |message: String| AutomatonError::at_line(message, line_num + 1, raw_line);
             
             // Ignorar comentarios y líneas vacías
             if line.is_empty() || line.starts_with('#') {
@@ -214,10 +400,17 @@ impl Automaton {
             }
             
             // Detectar secciones
-            if line == "METADATA" || line == "STATES" || line == "TRANSITIONS" || line == "KEYWORDS" {
+            if line == "METADATA" || line == "STATES" || line == "TRANSITIONS" {
                 current_section = line;
                 continue;
             }
+
+            // `KEYWORDS` o `KEYWORDS <dialecto>`
+            if let Some(name) = keywords_header(line) {
+                current_dialect = dialect_slot(&mut dialects, name).map_err(at_line)?;
+                current_section = "KEYWORDS";
+                continue;
+            }
             
             if line.starts_with("END_") {
                 current_section = "";
@@ -232,6 +425,10 @@ impl Automaton {
                         if parts.len() == 2 {
                             initial_state_name = Some(parts[1].trim().to_string());
                         }
+                    } else if let Some(value) = line.strip_prefix("block_comment:") {
+                        block_comment = Some(Self::parse_block_comment(value).map_err(at_line)?);
+                    } else if let Some(value) = line.strip_prefix("nested_comments:") {
+                        nested_comments = Self::parse_flag(value).map_err(at_line)?;
                     }
                 }
                 
@@ -254,11 +451,16 @@ impl Automaton {
                         id
                     });
                     
+                    state_lines.entry(state_id).or_insert(line_num + 1);
+                    
                     // Verificar si es estado final
                     for part in &parts[1..] {
-                        if part.starts_with("FINAL:") {
-                            let token_type_str = &part[6..];
-                            let token_type = Self::parse_token_type(token_type_str)?;
+                        if let Some(token_type_str) = part.strip_prefix("FINAL:") {
+                            let token_type = Self::parse_token_type(token_type_str)
+                                .map_err(at_line)?;
+                            if token_type_str == "ERROR" {
+                                error_states.push(state_id);
+                            }
                             final_states.insert(state_id, token_type);
                         }
                     }
@@ -278,7 +480,10 @@ impl Automaton {
                     } else if parts.len() == 3 {
                         (parts[0], parts[1], parts[2])
                     } else {
-                        continue;
+                        return Err(at_line(format!(
+                            "Transición mal formada (se esperaba 'origen, clase, destino'): {}",
+                            content
+                        )));
                     };
                     
                     // Obtener o crear estados
@@ -294,14 +499,17 @@ impl Automaton {
                         id
                     });
                     
+                    state_lines.entry(from).or_insert(line_num + 1);
+                    state_lines.entry(to).or_insert(line_num + 1);
+                    
                     // Parsear clase de caracteres
-                    let char_class = CharClass::parse(char_spec)
-                        .map_err(|e| format!("Línea {}: {}", line_num + 1, e))?;
+                    let char_class = CharClass::parse(char_spec).map_err(at_line)?;
                     
                     transitions.push(Transition {
                         from,
                         char_class,
                         to,
+                        line: line_num + 1,
                     });
                 }
                 
@@ -310,12 +518,12 @@ impl Automaton {
                     let parts: Vec<&str> = line.split('#').collect();
                     let content = parts[0].trim();
                     
-                    let parts: Vec<&str> = content.split(',').map(|s| s.trim()).collect();
-                    if parts.len() == 2 {
-                        let keyword = parts[0].to_string();
-                        let token_type = Self::parse_token_type(parts[1])?;
-                        keywords.insert(keyword, token_type);
-                    }
+                    let (keyword, token_type) = Self::parse_keyword(content).map_err(at_line)?;
+                    dialects[current_dialect].add(KeywordEntry {
+                        lexeme: keyword,
+                        token_type,
+                        line: line_num + 1,
+                    });
                 }
                 
                 _ => {}
@@ -325,20 +533,61 @@ impl Automaton {
         // Establecer estado inicial
         if let Some(name) = initial_state_name {
             initial_state = *state_map.get(&name)
-                .ok_or_else(|| format!("Estado inicial '{}' no encontrado", name))?;
+                .ok_or_else(|| AutomatonError::new(format!("Estado inicial '{}' no encontrado", name)))?;
         }
         
         Ok(Automaton {
             initial_state,
             transitions,
             final_states,
-            keywords,
+            dialects,
             state_map,
+            state_lines,
+            error_states,
+            block_comment: block_comment.map(|bc| BlockComment { nested: nested_comments, ..bc }),
+            dense: OnceCell::new(),
         })
     }
+
+    /// Parsea los delimitadores de `block_comment: /* */`
+    pub(super) fn parse_block_comment(value: &str) -> Result<BlockComment, String> {
+        match value.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [open, close] => Ok(BlockComment {
+                open: open.to_string(),
+                close: close.to_string(),
+                nested: false,
+            }),
+            _ => Err(format!(
+                "Comentario de bloque mal formado (se esperaba 'apertura cierre'): {}",
+                value.trim()
+            )),
+        }
+    }
+
+    /// Parsea un valor booleano de METADATA (`true`/`false`)
+    pub(super) fn parse_flag(value: &str) -> Result<bool, String> {
+        match value.trim() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            other => Err(format!("Valor inválido '{}' (se esperaba true o false)", other)),
+        }
+    }
     
+    /// Parsea una entrada de palabra reservada: `palabra, TIPO`
+    pub(super) fn parse_keyword(content: &str) -> Result<(String, TokenType), String> {
+        let parts: Vec<&str> = content.split(',').map(|s| s.trim()).collect();
+        if parts.len() != 2 {
+            return Err(format!(
+                "Palabra reservada mal formada (se esperaba 'palabra, TIPO'): {}",
+                content
+            ));
+        }
+        let token_type = Self::parse_token_type(parts[1])?;
+        Ok((parts[0].to_string(), token_type))
+    }
+
     /// Parsea un tipo de token desde string
-    fn parse_token_type(s: &str) -> Result<TokenType, String> {
+    pub(super) fn parse_token_type(s: &str) -> Result<TokenType, String> {
         match s {
             "PROGRAMA" => Ok(TokenType::Programa),
             "DEFINE" => Ok(TokenType::Define),
@@ -346,11 +595,19 @@ impl Automaton {
             "CONCENTRADORES" => Ok(TokenType::Concentradores),
             "COAXIAL" => Ok(TokenType::Coaxial),
             "SEGMENTO" => Ok(TokenType::Segmento),
+            "ENTEROS" => Ok(TokenType::Enteros),
             "MODULO" => Ok(TokenType::Modulo),
             "INICIO" => Ok(TokenType::Inicio),
             "FIN" => Ok(TokenType::Fin),
             "SI" => Ok(TokenType::Si),
             "SINO" => Ok(TokenType::Sino),
+            "MIENTRAS" => Ok(TokenType::Mientras),
+            "PARA" => Ok(TokenType::Para),
+            "HASTA" => Ok(TokenType::Hasta),
+            "MAQUINA" => Ok(TokenType::Maquina),
+            "CONCENTRADOR" => Ok(TokenType::Concentrador),
+            "ENTERO" => Ok(TokenType::Entero),
+            "INCLUYE" => Ok(TokenType::Incluye),
             
             "COLOCA" => Ok(TokenType::Coloca),
             "COLOCA_COAXIAL" => Ok(TokenType::ColocaCoaxial),
@@ -377,6 +634,12 @@ impl Automaton {
             "OR" => Ok(TokenType::Or),
             "NOT" => Ok(TokenType::Not),
             
+            "PLUS" => Ok(TokenType::Plus),
+            "MINUS" => Ok(TokenType::Minus),
+            "STAR" => Ok(TokenType::Star),
+            "SLASH" => Ok(TokenType::Slash),
+            "PERCENT" => Ok(TokenType::Percent),
+            
             "COMMA" => Ok(TokenType::Comma),
             "SEMICOLON" => Ok(TokenType::Semicolon),
             "DOT" => Ok(TokenType::Dot),
@@ -391,6 +654,7 @@ impl Automaton {
             
             "WHITESPACE" => Ok(TokenType::Whitespace),
             "COMMENT" => Ok(TokenType::Comment),
+            "DOC_COMMENT" => Ok(TokenType::DocComment),
             "ERROR" => Ok(TokenType::Identifier), // ERROR tokens treated as identifiers for now
 
             _ => Err(format!("Tipo de token desconocido: {}", s)),
@@ -417,23 +681,91 @@ impl Automaton {
         self.final_states.get(&state)
     }
     
-    /// Clasifica un identificador (keyword o identifier)
+    /// Delimitadores de comentario de bloque, si existen
+    pub fn block_comment(&self) -> Option<&BlockComment> {
+        self.block_comment.as_ref()
+    }
+
+    /// Número de estados (los IDs van de 0 a `state_count() - 1`)
+    pub fn state_count(&self) -> usize {
+        self.state_map.values().map(|id| id.as_usize() + 1).max().unwrap_or(0)
+    }
+
+    /// Tabla de transiciones densa (estado × clase de byte), calculada una vez
+    pub fn dense_table(&self) -> &DenseTable {
+        self.dense.get_or_init(|| DenseTable::new(self))
+    }
+
+    /// Obtiene el nombre con el que se declaró un estado en el .aut
+    pub fn state_name(&self, state: StateId) -> Option<&str> {
+        self.state_map
+            .iter()
+            .find(|(_, id)| **id == state)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Dialectos de palabras reservadas en el orden del archivo
+    pub fn dialects(&self) -> &[Dialect] {
+        &self.dialects
+    }
+
+    /// Dialecto de las secciones `KEYWORDS` sin nombre
+    pub fn default_dialect(&self) -> &Dialect {
+        &self.dialects[0]
+    }
+
+    /// Busca un dialecto por nombre (sin distinguir mayúsculas ni acentos)
+    pub fn dialect(&self, name: &str) -> Result<&Dialect, String> {
+        self.dialect_index(name).map(|index| &self.dialects[index])
+    }
+
+    /// Posición de un dialecto en `dialects()`
+    pub(super) fn dialect_index(&self, name: &str) -> Result<usize, String> {
+        let key = fold_keyword(name);
+        self.dialects
+            .iter()
+            .position(|dialect| fold_keyword(&dialect.name) == key)
+            .ok_or_else(|| {
+                let names: Vec<&str> = self.dialects.iter().map(Dialect::name).collect();
+                format!("Dialecto desconocido '{}' (disponibles: {})", name, names.join(", "))
+            })
+    }
+
+    /// Clasifica un identificador con el dialecto predeterminado
     pub fn classify_identifier(&self, lexeme: &str) -> TokenType {
-        // Buscar en keywords (case-insensitive para palabras reservadas)
-        let lower = lexeme.to_lowercase();
-        if let Some(token_type) = self.keywords.get(&lower) {
-            return token_type.clone();
-        }
-        
-        // Buscar case-sensitive para funciones camelCase
-        if let Some(token_type) = self.keywords.get(lexeme) {
-            return token_type.clone();
-        }
-        
-        TokenType::Identifier
+        self.default_dialect().classify_identifier(lexeme)
     }
 }
 
+/// Nombre de dialecto de un encabezado `KEYWORDS [dialecto]`, o `None` si la
+/// línea no abre una sección de palabras reservadas
+pub(super) fn keywords_header(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("KEYWORDS")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    match rest.split('#').next().unwrap_or("").trim() {
+        "" => Some(DEFAULT_DIALECT),
+        name => Some(name),
+    }
+}
+
+/// Índice del dialecto `name`, creándolo si es la primera sección que lo usa
+pub(super) fn dialect_slot(dialects: &mut Vec<Dialect>, name: &str) -> Result<usize, String> {
+    if name.contains(char::is_whitespace) {
+        return Err(format!("Nombre de dialecto inválido: '{}'", name));
+    }
+
+    let key = fold_keyword(name);
+    if let Some(index) = dialects.iter().position(|d| fold_keyword(&d.name) == key) {
+        return Ok(index);
+    }
+
+    dialects.push(Dialect::new(name));
+    Ok(dialects.len() - 1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -532,6 +864,64 @@ mod tests {
         assert!(matches!(digit, CharClass::Range('0', '9')));
     }
 
+    #[test]
+    fn test_char_class_unicode() {
+        let letter = CharClass::parse("UNICODE_LETTER").unwrap();
+        assert!(letter.matches('ñ'));
+        assert!(letter.matches('É'));
+        assert!(!letter.matches('5'));
+
+        let digit = CharClass::parse("UNICODE_DIGIT").unwrap();
+        assert!(digit.matches('7'));
+        assert!(!digit.matches('a'));
+
+        let class = CharClass::parse("[a-z\\p{L}]").unwrap();
+        assert!(class.matches('ó'));
+    }
+
+    #[test]
+    fn test_keyword_accent_insensitive() {
+        let content = r#"
+METADATA
+initial_state: q0
+END_METADATA
+
+STATES
+q0
+q_id FINAL:IDENTIFIER
+END_STATES
+
+TRANSITIONS
+q0, UNICODE_LETTER, q_id
+q_id, UNICODE_LETTER, q_id
+END_TRANSITIONS
+
+KEYWORDS
+modulo, MODULO
+maquinaCoaxial, MAQUINA_COAXIAL
+END_KEYWORDS
+"#;
+
+        let automaton = Automaton::parse(content).unwrap();
+
+        assert_eq!(fold_keyword("Módulo"), "modulo");
+        assert_eq!(fold_keyword("año"), "año");
+        assert_eq!(automaton.classify_identifier("módulo"), TokenType::Modulo);
+        assert_eq!(automaton.classify_identifier("MÓDULO"), TokenType::Modulo);
+        // Forma descompuesta: o + tilde combinante
+        assert_eq!(automaton.classify_identifier("mo\u{301}dulo"), TokenType::Modulo);
+
+        // Las funciones camelCase siguen siendo exactas
+        assert_eq!(
+            automaton.classify_identifier("maquinaCoaxial"),
+            TokenType::MaquinaCoaxial
+        );
+        assert_eq!(
+            automaton.classify_identifier("máquinaCoaxial"),
+            TokenType::Identifier
+        );
+    }
+
     #[test]
     fn test_automaton_parse_simple() {
         let content = r#"
@@ -602,6 +992,53 @@ END_KEYWORDS
         assert!(automaton.next_state(q0, '@').is_none());
     }
 
+    #[test]
+    fn test_keyword_dialects() {
+        let content = r#"
+METADATA
+initial_state: q0
+END_METADATA
+
+STATES
+q0
+q_id FINAL:IDENTIFIER
+END_STATES
+
+TRANSITIONS
+q0, [a-zA-Z], q_id
+q_id, [a-zA-Z], q_id
+END_TRANSITIONS
+
+KEYWORDS
+coloca, COLOCA
+uneMaquinaPuerto, UNE_MAQUINA_PUERTO
+END_KEYWORDS
+
+KEYWORDS english   # mismo TokenType, otra palabra
+place, COLOCA
+connectPort, UNE_MAQUINA_PUERTO
+END_KEYWORDS
+"#;
+
+        let automaton = Automaton::parse(content).unwrap();
+        let names: Vec<&str> = automaton.dialects().iter().map(Dialect::name).collect();
+        assert_eq!(names, vec![DEFAULT_DIALECT, "english"]);
+
+        let english = automaton.dialect("ENGLISH").unwrap();
+        assert_eq!(english.classify_identifier("PLACE"), TokenType::Coloca);
+        assert_eq!(english.classify_identifier("connectPort"), TokenType::UneMaquinaPuerto);
+        assert_eq!(english.classify_identifier("coloca"), TokenType::Identifier);
+        assert_eq!(automaton.classify_identifier("place"), TokenType::Identifier);
+
+        // Los diagnósticos usan la palabra del dialecto
+        assert_eq!(english.display(&TokenType::UneMaquinaPuerto), "connectPort");
+        assert_eq!(english.display(&TokenType::Semicolon), ";");
+        assert_eq!(automaton.default_dialect().display(&TokenType::Coloca), "coloca");
+
+        assert!(automaton.dialect("klingon").is_err());
+        assert!(Automaton::parse("KEYWORDS en glish\nEND_KEYWORDS").is_err());
+    }
+
     #[test]
     fn test_keyword_classification() {
         let content = r#"
@@ -0,0 +1,515 @@
+// src/lexer_new/byte_scanner.rs
+// Scanner sobre los bytes UTF-8 del código fuente, con lexemas prestados
+
+use super::automaton::{Automaton, BlockComment, Dialect, StateId};
+use super::error::LexicalError;
+use super::token::{Span, Token, TokenType, is_combining_mark};
+
+/// Destino "sin transición" en la tabla densa
+const DEAD: u32 = u32::MAX;
+
+/// Tabla de transiciones densa: estado × clase de byte
+///
+/// Los 128 bytes ASCII se agrupan en clases: dos bytes están en la misma clase
+/// si todos los estados los tratan igual. Así una transición es un acceso a
+/// `next[estado * clases + clase]` en lugar de recorrer la lista de
+/// transiciones del autómata. Los caracteres no ASCII son raros en un `.net`
+/// y siguen consultando el autómata.
+#[derive(Debug, Clone)]
+pub struct DenseTable {
+    /// Clase de cada byte ASCII
+    classes: [u8; 128],
+
+    /// Número de clases (columnas de la tabla)
+    class_count: usize,
+
+    /// Estado destino por estado y clase, o `DEAD`
+    next: Vec<u32>,
+
+    /// Tipo de token de cada estado final
+    finals: Vec<Option<TokenType>>,
+
+    initial: u32,
+}
+
+impl DenseTable {
+    /// Precalcula la tabla a partir del autómata
+    pub fn new(automaton: &Automaton) -> Self {
+        let state_count = automaton.state_count();
+        let column = |byte: u8| -> Vec<u32> {
+            (0..state_count)
+                .map(|state| {
+                    automaton
+                        .next_state(StateId::new(state), byte as char)
+                        .map_or(DEAD, |next| next.as_usize() as u32)
+                })
+                .collect()
+        };
+
+        // Agrupar bytes con la misma columna de destinos
+        let mut classes = [0u8; 128];
+        let mut columns: Vec<Vec<u32>> = Vec::new();
+        for byte in 0..128u8 {
+            let targets = column(byte);
+            let class = match columns.iter().position(|c| *c == targets) {
+                Some(class) => class,
+                None => {
+                    columns.push(targets);
+                    columns.len() - 1
+                }
+            };
+            classes[byte as usize] = class as u8;
+        }
+
+        let class_count = columns.len();
+        let mut next = vec![DEAD; state_count * class_count];
+        for (class, targets) in columns.iter().enumerate() {
+            for (state, target) in targets.iter().enumerate() {
+                next[state * class_count + class] = *target;
+            }
+        }
+
+        let finals = (0..state_count)
+            .map(|state| automaton.is_final(StateId::new(state)).cloned())
+            .collect();
+
+        Self {
+            classes,
+            class_count,
+            next,
+            finals,
+            initial: automaton.initial_state().as_usize() as u32,
+        }
+    }
+
+    /// Número de clases de bytes ASCII
+    pub fn class_count(&self) -> usize {
+        self.class_count
+    }
+
+    /// Transición por un byte ASCII
+    fn step(&self, state: u32, byte: u8) -> u32 {
+        let class = self.classes[byte as usize] as usize;
+        self.next[state as usize * self.class_count + class]
+    }
+}
+
+/// Token cuyo lexema es un trozo del código fuente (sin copiarlo)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenRef<'src> {
+    pub token_type: TokenType,
+    pub lexeme: &'src str,
+    pub span: Span,
+}
+
+impl TokenRef<'_> {
+    /// Copia el token a un `Token` con lexema propio
+    pub fn to_token(&self) -> Token {
+        Token::new(
+            self.token_type.clone(),
+            self.lexeme.to_string(),
+            self.span.line,
+            self.span.column,
+        )
+        .at_offset(self.span.start)
+    }
+
+    /// Verifica si el token es trivia (espacios o comentarios)
+    pub fn is_trivia(&self) -> bool {
+        self.token_type.should_ignore()
+    }
+}
+
+/// DFA que recorre `ByteScanner`
+///
+/// Lo implementan la tabla densa calculada desde un `Automaton` (`DenseDfa`) y
+/// el DFA generado en compilación desde `config/automaton.aut`
+/// (`generated::GeneratedDfa`), de modo que ambos comparten el mismo scanner.
+pub trait Transitions {
+    /// Estado inicial
+    fn initial_state(&self) -> u32;
+
+    /// Siguiente estado por un byte ASCII
+    fn step_ascii(&self, state: u32, byte: u8) -> Option<u32>;
+
+    /// Siguiente estado por un carácter no ASCII
+    fn step_char(&self, state: u32, ch: char) -> Option<u32>;
+
+    /// Tipo de token si el estado es final
+    fn final_token(&self, state: u32) -> Option<&TokenType>;
+
+    /// Palabra reservada del dialecto activo o identificador
+    fn classify_identifier(&self, lexeme: &str) -> TokenType;
+
+    /// Delimitadores de comentario de bloque, si existen
+    fn block_comment(&self) -> Option<&BlockComment>;
+
+    /// Cambia el dialecto de palabras reservadas
+    fn set_dialect(&mut self, name: &str) -> Result<(), String>;
+}
+
+/// Tabla densa de un `Automaton` cargado en tiempo de ejecución
+pub struct DenseDfa<'a> {
+    automaton: &'a Automaton,
+    table: &'a DenseTable,
+    dialect: &'a Dialect,
+}
+
+impl<'a> DenseDfa<'a> {
+    /// La tabla densa del autómata se calcula la primera vez
+    pub fn new(automaton: &'a Automaton) -> Self {
+        Self {
+            automaton,
+            table: automaton.dense_table(),
+            dialect: automaton.default_dialect(),
+        }
+    }
+}
+
+impl Transitions for DenseDfa<'_> {
+    fn initial_state(&self) -> u32 {
+        self.table.initial
+    }
+
+    fn step_ascii(&self, state: u32, byte: u8) -> Option<u32> {
+        Some(self.table.step(state, byte)).filter(|next| *next != DEAD)
+    }
+
+    fn step_char(&self, state: u32, ch: char) -> Option<u32> {
+        self.automaton
+            .next_state(StateId::new(state as usize), ch)
+            .map(|next| next.as_usize() as u32)
+    }
+
+    fn final_token(&self, state: u32) -> Option<&TokenType> {
+        self.table.finals[state as usize].as_ref()
+    }
+
+    fn classify_identifier(&self, lexeme: &str) -> TokenType {
+        self.dialect.classify_identifier(lexeme)
+    }
+
+    fn block_comment(&self) -> Option<&BlockComment> {
+        self.automaton.block_comment()
+    }
+
+    fn set_dialect(&mut self, name: &str) -> Result<(), String> {
+        self.dialect = self.automaton.dialect(name)?;
+        Ok(())
+    }
+}
+
+/// Scanner que recorre los bytes UTF-8 del código fuente
+///
+/// Produce los mismos tokens que `Scanner`, pero sin convertir el archivo a
+/// `Vec<char>` ni crear un `String` por lexema: cada `TokenRef` apunta al
+/// código fuente. Pensado para archivos `.net` generados muy grandes.
+pub struct ByteScanner<'src, T: Transitions> {
+    source: &'src str,
+    dfa: T,
+
+    /// Desplazamiento en bytes de la posición actual
+    pos: usize,
+
+    /// Línea actual (1-indexed)
+    line: usize,
+
+    /// Columna actual (1-indexed)
+    column: usize,
+}
+
+impl<'src, 'a> ByteScanner<'src, DenseDfa<'a>> {
+    /// Crea un scanner guiado por la tabla densa de `automaton`
+    pub fn new(source: &'src str, automaton: &'a Automaton) -> Self {
+        Self::with_transitions(source, DenseDfa::new(automaton))
+    }
+}
+
+impl<'src, T: Transitions> ByteScanner<'src, T> {
+    /// Crea un scanner guiado por cualquier DFA
+    pub fn with_transitions(source: &'src str, dfa: T) -> Self {
+        Self {
+            source,
+            dfa,
+            pos: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Clasifica las palabras reservadas con otro dialecto
+    pub fn set_dialect(&mut self, name: &str) -> Result<(), String> {
+        self.dfa.set_dialect(name)
+    }
+
+    /// Escanea todos los tokens, sin trivia y terminados en EOF
+    pub fn scan_all(&mut self) -> Result<Vec<TokenRef<'src>>, LexicalError> {
+        let mut tokens = Vec::new();
+
+        while let Some(token) = self.scan_token()? {
+            if !token.is_trivia() {
+                tokens.push(token);
+            }
+        }
+
+        tokens.push(self.eof_token());
+        Ok(tokens)
+    }
+
+    /// Escanea todos los tokens sin detenerse en errores léxicos
+    /// (ver `Scanner::scan_all_recovering`)
+    pub fn scan_all_recovering(&mut self) -> (Vec<TokenRef<'src>>, Vec<LexicalError>) {
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+
+        loop {
+            match self.scan_token() {
+                Ok(Some(token)) => {
+                    if !token.is_trivia() {
+                        tokens.push(token);
+                    }
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    errors.push(error);
+                    // Saltar el carácter problemático y continuar
+                    self.advance_char();
+                }
+            }
+        }
+
+        tokens.push(self.eof_token());
+
+        (tokens, errors)
+    }
+
+    /// Escanea el siguiente token
+    pub fn scan_token(&mut self) -> Result<Option<TokenRef<'src>>, LexicalError> {
+        if self.pos >= self.source.len() {
+            return Ok(None);
+        }
+
+        if let Some(block) = self.dfa.block_comment()
+            && self.source.as_bytes()[self.pos..].starts_with(block.open.as_bytes())
+        {
+            // Copia de los delimitadores: el scanner avanza mientras los usa
+            let block = block.clone();
+            return self.scan_block_comment(&block).map(Some);
+        }
+
+        let start = self.mark();
+        let bytes = self.source.as_bytes();
+        let mut state = self.dfa.initial_state();
+        let mut last_final: Option<(u32, (usize, usize, usize))> = None;
+
+        while self.pos < bytes.len() {
+            let byte = bytes[self.pos];
+            let next = if byte.is_ascii() {
+                self.dfa.step_ascii(state, byte)
+            } else {
+                let ch = self.source[self.pos..].chars().next().unwrap_or('\0');
+                self.dfa.step_char(state, ch)
+            };
+
+            let Some(next) = next else {
+                break;
+            };
+
+            self.advance_char();
+            state = next;
+            if self.dfa.final_token(state).is_some() {
+                last_final = Some((state, self.mark()));
+            }
+        }
+
+        let Some((final_state, end)) = last_final else {
+            // El error apunta al carácter que no pudo comenzar un token
+            self.reset_to(start);
+            let ch = self.source[self.pos..].chars().next().unwrap_or('\0');
+            return Err(LexicalError::invalid_char(ch, start.1, start.2));
+        };
+
+        self.reset_to(end);
+        let lexeme = &self.source[start.0..self.pos];
+        let token_type = match self.dfa.final_token(final_state) {
+            Some(TokenType::Identifier) => self.dfa.classify_identifier(lexeme),
+            Some(token_type) => token_type.clone(),
+            None => unreachable!("last_final solo guarda estados finales"),
+        };
+
+        Ok(Some(self.token_from(token_type, start)))
+    }
+
+    /// Escanea un comentario de bloque (ver `Scanner::scan_block_comment`)
+    fn scan_block_comment(&mut self, block: &BlockComment) -> Result<TokenRef<'src>, LexicalError> {
+        let start = self.mark();
+        let bytes = self.source.as_bytes();
+        let open = block.open.as_bytes();
+        let close = block.close.as_bytes();
+
+        self.advance_bytes(open.len());
+        let mut depth = 1;
+
+        while self.pos < bytes.len() {
+            if bytes[self.pos..].starts_with(close) {
+                self.advance_bytes(close.len());
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(self.token_from(TokenType::Comment, start));
+                }
+            } else if block.nested && bytes[self.pos..].starts_with(open) {
+                self.advance_bytes(open.len());
+                depth += 1;
+            } else {
+                self.advance_char();
+            }
+        }
+
+        Err(LexicalError::unterminated_block_comment(
+            &block.open,
+            &block.close,
+            start.1,
+            start.2,
+        ))
+    }
+
+    /// Token desde `start` hasta la posición actual
+    fn token_from(
+        &self,
+        token_type: TokenType,
+        (start, line, column): (usize, usize, usize),
+    ) -> TokenRef<'src> {
+        TokenRef {
+            token_type,
+            lexeme: &self.source[start..self.pos],
+            span: Span {
+                start,
+                end: self.pos,
+                line,
+                column,
+                end_line: self.line,
+                end_column: self.column,
+            },
+        }
+    }
+
+    /// Avanza un carácter completo, actualizando línea y columna (nada al final)
+    fn advance_char(&mut self) {
+        let Some(&byte) = self.source.as_bytes().get(self.pos) else {
+            return;
+        };
+        if byte.is_ascii() {
+            self.pos += 1;
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        } else if let Some(ch) = self.source[self.pos..].chars().next() {
+            self.pos += ch.len_utf8();
+            // Las marcas combinantes no ocupan columna propia
+            if !is_combining_mark(ch) {
+                self.column += 1;
+            }
+        }
+    }
+
+    /// Avanza `n` bytes de un delimitador (se asume texto sin saltos de línea)
+    fn advance_bytes(&mut self, n: usize) {
+        let end = self.pos + n;
+        while self.pos < end {
+            self.advance_char();
+        }
+    }
+
+    /// Posición actual: (byte, línea, columna)
+    fn mark(&self) -> (usize, usize, usize) {
+        (self.pos, self.line, self.column)
+    }
+
+    /// Vuelve a una posición guardada con `mark`
+    fn reset_to(&mut self, (pos, line, column): (usize, usize, usize)) {
+        self.pos = pos;
+        self.line = line;
+        self.column = column;
+    }
+
+    /// Token EOF ubicado al final del código fuente
+    fn eof_token(&self) -> TokenRef<'src> {
+        self.token_from(TokenType::Eof, self.mark())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_automaton;
+    use crate::lexer_new::Scanner;
+
+    /// Tokens de ambos scanners como (tipo, lexema, span)
+    fn both(source: &str) -> (Vec<(TokenType, String, Span)>, Vec<(TokenType, String, Span)>) {
+        let chars = Scanner::with_default_automaton(source)
+            .scan_all()
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.token_type, t.lexeme, t.span))
+            .collect();
+        let bytes = ByteScanner::new(source, load_automaton())
+            .scan_all()
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.token_type, t.lexeme.to_string(), t.span))
+            .collect();
+        (chars, bytes)
+    }
+
+    #[test]
+    fn test_dense_table_groups_bytes() {
+        let table = DenseTable::new(load_automaton());
+        assert!(table.class_count() < 128);
+        assert_eq!(table.classes[b'a' as usize], table.classes[b'q' as usize]);
+        assert_ne!(table.classes[b'a' as usize], table.classes[b';' as usize]);
+    }
+
+    #[test]
+    fn test_same_tokens_as_scanner() {
+        let (chars, bytes) = both(include_str!("../../ejemplo1.net"));
+        assert_eq!(chars, bytes);
+
+        let source = "programa Señal; /* a /* b */ c */ máquinaRecepción mo\u{301}dulo\nñandú 12";
+        let (chars, bytes) = both(source);
+        assert_eq!(chars, bytes);
+    }
+
+    #[test]
+    fn test_lexemes_borrow_source() {
+        let source = String::from("programa red;");
+        let tokens = ByteScanner::new(&source, load_automaton()).scan_all().unwrap();
+
+        assert_eq!(tokens[1].lexeme, "red");
+        assert!(std::ptr::eq(tokens[1].lexeme.as_ptr(), source[9..].as_ptr()));
+        assert_eq!(tokens[1].to_token().lexeme, "red");
+    }
+
+    #[test]
+    fn test_errors_match_scanner() {
+        let source = "programa ñ\n  @";
+        let expected = Scanner::with_default_automaton(source).scan_all().unwrap_err();
+        let err = ByteScanner::new(source, load_automaton()).scan_all().unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message),
+            (expected.line, expected.column, expected.message)
+        );
+
+        let err = ByteScanner::new("/* sin cerrar", load_automaton()).scan_all().unwrap_err();
+        assert_eq!((err.line, err.column, err.length), (1, 1, 2));
+    }
+
+    #[test]
+    fn test_dialect() {
+        let mut scanner = ByteScanner::new("begin", load_automaton());
+        scanner.set_dialect("english").unwrap();
+        assert_eq!(scanner.scan_all().unwrap()[0].token_type, TokenType::Inicio);
+    }
+}
@@ -0,0 +1,166 @@
+// src/lexer_new/codegen.rs
+// Generación de código Rust para el DFA (la usa build.rs)
+
+use super::automaton::{Automaton, CharClass, StateId};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Patrón de `match` que cubre la clase, si puede escribirse sin guarda
+fn pattern(class: &CharClass) -> Option<String> {
+    match class {
+        CharClass::Exact(c) => Some(format!("{:?}", c)),
+        CharClass::Range(start, end) => Some(format!("{:?}..={:?}", start, end)),
+        CharClass::Multi(classes) => {
+            let parts: Option<Vec<String>> = classes.iter().map(pattern).collect();
+            parts.map(|parts| parts.join(" | "))
+        }
+        CharClass::Any => Some("_".to_string()),
+        CharClass::AnyExceptNewline | CharClass::UnicodeLetter | CharClass::UnicodeDigit => None,
+    }
+}
+
+/// Condición equivalente a `class.matches(ch)` para usar como guarda
+fn condition(class: &CharClass) -> String {
+    match class {
+        CharClass::Exact(c) => format!("ch == {:?}", c),
+        CharClass::Range(start, end) => format!("({:?}..={:?}).contains(&ch)", start, end),
+        CharClass::Multi(classes) => {
+            let parts: Vec<String> = classes.iter().map(condition).collect();
+            format!("({})", parts.join(" || "))
+        }
+        CharClass::Any => "true".to_string(),
+        CharClass::AnyExceptNewline => "ch != '\\n'".to_string(),
+        CharClass::UnicodeLetter => "is_unicode_letter(ch)".to_string(),
+        CharClass::UnicodeDigit => "ch.is_numeric()".to_string(),
+    }
+}
+
+/// Rango vacío (`[z-a]`): nunca coincide y no genera brazo
+fn is_empty(class: &CharClass) -> bool {
+    matches!(class, CharClass::Range(start, end) if start > end)
+}
+
+impl Automaton {
+    /// Genera el DFA como código Rust: un `match` por estado en lugar de
+    /// recorrer la lista de transiciones
+    ///
+    /// Los brazos conservan el orden del .aut, así que con transiciones
+    /// solapadas gana la primera, igual que en `next_state`. El código usa
+    /// `TokenType`, `BlockComment`, `Lazy` e `is_unicode_letter`, que deben
+    /// estar en alcance donde se incluya.
+    pub fn to_rust(&self) -> String {
+        let mut out = String::new();
+        out.push_str("// Generado por build.rs a partir del autómata. No editar.\n\n");
+
+        writeln!(out, "/// Estado inicial").unwrap();
+        writeln!(out, "pub const INITIAL_STATE: u32 = {};\n", self.initial_state().as_usize()).unwrap();
+
+        // Transiciones agrupadas por estado de origen, en orden de archivo
+        let mut by_state: BTreeMap<usize, Vec<(&CharClass, usize)>> = BTreeMap::new();
+        for transition in &self.transitions {
+            if !is_empty(&transition.char_class) {
+                by_state
+                    .entry(transition.from.as_usize())
+                    .or_default()
+                    .push((&transition.char_class, transition.to.as_usize()));
+            }
+        }
+
+        writeln!(out, "/// Siguiente estado desde `state` con el carácter `ch`").unwrap();
+        writeln!(out, "pub fn next_state(state: u32, ch: char) -> Option<u32> {{").unwrap();
+        writeln!(out, "    match state {{").unwrap();
+        for (state, arms) in &by_state {
+            let name = self.state_name(StateId::new(*state)).unwrap_or("?");
+            writeln!(out, "        // {}", name).unwrap();
+            writeln!(out, "        {} => match ch {{", state).unwrap();
+            for (class, to) in arms {
+                match pattern(class) {
+                    Some(pattern) => writeln!(out, "            {} => Some({}),", pattern, to),
+                    None => writeln!(out, "            ch if {} => Some({}),", condition(class), to),
+                }
+                .unwrap();
+            }
+            writeln!(out, "            _ => None,").unwrap();
+            writeln!(out, "        }},").unwrap();
+        }
+        writeln!(out, "        _ => None,").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}\n").unwrap();
+
+        let finals: BTreeMap<usize, _> = self
+            .final_states
+            .iter()
+            .map(|(state, token_type)| (state.as_usize(), token_type))
+            .collect();
+
+        writeln!(out, "/// Tipo de token de cada estado final").unwrap();
+        writeln!(out, "pub fn final_token(state: u32) -> Option<&'static TokenType> {{").unwrap();
+        writeln!(out, "    match state {{").unwrap();
+        for (state, token_type) in &finals {
+            writeln!(out, "        {} => Some(&TokenType::{:?}),", state, token_type).unwrap();
+        }
+        writeln!(out, "        _ => None,").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}\n").unwrap();
+
+        let names: Vec<String> = self.dialects().iter().map(|d| format!("{:?}", d.name())).collect();
+        writeln!(out, "/// Dialectos de palabras reservadas; el primero es el predeterminado").unwrap();
+        writeln!(out, "pub const DIALECTS: &[&str] = &[{}];\n", names.join(", ")).unwrap();
+
+        // Mismas claves que la tabla del dialecto (ver `keyword_key`)
+        writeln!(out, "/// Palabra reservada con la clave `key` en el dialecto `dialect`").unwrap();
+        writeln!(out, "pub fn keyword(dialect: usize, key: &str) -> Option<TokenType> {{").unwrap();
+        writeln!(out, "    match (dialect, key) {{").unwrap();
+        for (index, dialect) in self.dialects().iter().enumerate() {
+            let keywords: BTreeMap<_, _> = dialect.keywords.iter().collect();
+            for (key, token_type) in keywords {
+                writeln!(out, "        ({}, {:?}) => Some(TokenType::{:?}),", index, key, token_type).unwrap();
+            }
+        }
+        writeln!(out, "        _ => None,").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}\n").unwrap();
+
+        writeln!(out, "/// Delimitadores de comentario de bloque").unwrap();
+        writeln!(out, "pub static BLOCK_COMMENT: Lazy<Option<BlockComment>> = Lazy::new(|| {{").unwrap();
+        match self.block_comment() {
+            Some(block) => writeln!(
+                out,
+                "    Some(BlockComment {{ open: {:?}.to_string(), close: {:?}.to_string(), nested: {} }})",
+                block.open, block.close, block.nested,
+            ),
+            None => writeln!(out, "    None"),
+        }
+        .unwrap();
+        writeln!(out, "}});").unwrap();
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arms_keep_file_order() {
+        let automaton = Automaton::parse(
+            "METADATA\ninitial_state: q0\nEND_METADATA\n\
+             STATES\nq0\nq_a FINAL:IDENTIFIER\nq_x FINAL:ERROR\nEND_STATES\n\
+             TRANSITIONS\nq0, [a-c], q_a\nq0, UNICODE_LETTER, q_a\nq0, ANY, q_x\nEND_TRANSITIONS\n\
+             KEYWORDS\nsi, SI\nEND_KEYWORDS\n",
+        )
+        .unwrap();
+
+        let code = automaton.to_rust();
+        let range = code.find("'a'..='c' => Some(1)").unwrap();
+        let letter = code.find("ch if is_unicode_letter(ch) => Some(1)").unwrap();
+        let any = code.find("_ => Some(2)").unwrap();
+        assert!(range < letter && letter < any);
+
+        assert!(code.contains("1 => Some(&TokenType::Identifier)"));
+        assert!(code.contains("(0, \"si\") => Some(TokenType::Si)"));
+        assert!(code.contains("pub const DIALECTS: &[&str] = &[\"espanol\"];"));
+        assert!(code.contains("Lazy::new(|| {\n    None\n})"));
+    }
+}
@@ -0,0 +1,259 @@
+// src/lexer_new/dot.rs
+// Exportación del autómata a Graphviz (DOT)
+
+use super::automaton::{
+    Automaton, CharClass, StateId, UNICODE_DIGIT_RANGES, UNICODE_LETTER_RANGES,
+};
+use super::spec::{Ranges, complement, normalize};
+use std::collections::HashMap;
+
+/// Escapa un carácter para mostrarlo dentro de una clase compacta
+fn display_char(ch: char) -> String {
+    match ch {
+        ' ' => "\\s".to_string(),
+        '\n' => "\\n".to_string(),
+        '\t' => "\\t".to_string(),
+        '\r' => "\\r".to_string(),
+        '\\' | ']' | '[' | '-' | '^' => format!("\\{}", ch),
+        c if c.is_control() => c.escape_unicode().to_string(),
+        c => c.to_string(),
+    }
+}
+
+/// Extrae de `ranges` los conjuntos Unicode que contiene por completo y
+/// devuelve el resto junto con sus nombres (`\p{L}`, `\p{N}`)
+fn extract_named_sets(ranges: &[(u32, u32)]) -> (Ranges, String) {
+    let named: [(&str, &[(char, char)]); 2] = [
+        ("\\p{L}", &UNICODE_LETTER_RANGES),
+        ("\\p{N}", &UNICODE_DIGIT_RANGES),
+    ];
+
+    let mut rest = ranges.to_vec();
+    let mut names = String::new();
+    for (name, set) in named {
+        let set: Ranges = set.iter().map(|&(s, e)| (s as u32, e as u32)).collect();
+        let mut union = ranges.to_vec();
+        union.extend(set.iter().copied());
+        if normalize(union) != ranges {
+            continue;
+        }
+        // rest \ set = ¬(¬rest ∪ set)
+        let mut without = complement(&rest);
+        without.extend(set);
+        rest = complement(&normalize(without));
+        names.push_str(name);
+    }
+    (rest, names)
+}
+
+/// Describe rangos normalizados: `a`, `[a-z]`, `[a-zA-Z_]`, `[^\n]`, `ANY`,
+/// `[_\p{L}]`
+fn describe_ranges(ranges: &[(u32, u32)]) -> String {
+    let body = |ranges: &[(u32, u32)]| -> String {
+        ranges
+            .iter()
+            .filter_map(|&(start, end)| {
+                // Un extremo dentro de los sustitutos se acota al carácter válido vecino
+                let start = char::from_u32(start).unwrap_or('\u{E000}');
+                let end = char::from_u32(end).unwrap_or('\u{D7FF}');
+                if start > end {
+                    return None;
+                }
+                Some(match end as u32 - start as u32 {
+                    0 => display_char(start),
+                    1 => format!("{}{}", display_char(start), display_char(end)),
+                    _ => format!("{}-{}", display_char(start), display_char(end)),
+                })
+            })
+            .collect()
+    };
+
+    let complemented = complement(ranges);
+    match (ranges, complemented.as_slice()) {
+        (_, []) => "ANY".to_string(),
+        // Un solo carácter fuera de corchetes no necesita escapar [ ] - ^
+        ([(start, end)], _) if start == end => match char::from_u32(*start) {
+            Some(c @ ('[' | ']' | '-' | '^')) => c.to_string(),
+            Some(c) => display_char(c),
+            None => String::new(),
+        },
+        // Entre la forma directa, la negada ("todo excepto...") y la que nombra
+        // letras y dígitos Unicode se muestra la más corta
+        _ => {
+            let (rest, names) = extract_named_sets(ranges);
+            let mut candidates = vec![
+                format!("[{}]", body(ranges)),
+                format!("[^{}]", body(&complemented)),
+            ];
+            if !names.is_empty() {
+                candidates.push(format!("[{}{}]", body(&rest), names));
+            }
+            candidates.into_iter().min_by_key(|c| c.chars().count()).unwrap()
+        }
+    }
+}
+
+/// Rangos normalizados (sin sustitutos UTF-16 de por medio) de una clase
+fn code_ranges(class: &CharClass) -> Ranges {
+    let ranges = class
+        .ranges()
+        .into_iter()
+        .map(|(start, end)| (start as u32, end as u32))
+        .collect();
+    // Los sustitutos no son caracteres: rellenarlos permite fusionar ANY
+    let mut ranges = normalize(ranges);
+    for range in ranges.iter_mut() {
+        if range.1 == 0xD7FF {
+            range.1 = 0xDFFF;
+        }
+    }
+    normalize(ranges)
+}
+
+impl CharClass {
+    /// Descripción compacta de la clase, p. ej. `[a-zA-Z0-9_]` o `[^\n]`
+    pub fn label(&self) -> String {
+        describe_ranges(&code_ranges(self))
+    }
+}
+
+/// Escapa texto para una etiqueta DOT entre comillas
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Automaton {
+    /// Exporta el autómata en formato Graphviz DOT
+    ///
+    /// Los estados finales se dibujan con doble círculo y su TokenType; las
+    /// transiciones paralelas (mismo origen y destino) se fusionan en una sola
+    /// arista etiquetada con la unión de sus clases de caracteres.
+    pub fn to_dot(&self) -> String {
+        let mut states: Vec<(&str, StateId)> = self
+            .state_map
+            .iter()
+            .map(|(name, id)| (name.as_str(), *id))
+            .collect();
+        states.sort_by_key(|(_, id)| id.as_usize());
+        let names: HashMap<StateId, &str> = states.iter().map(|(name, id)| (*id, *name)).collect();
+
+        let mut out = String::new();
+        out.push_str("digraph automaton {\n");
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [shape=circle, fontname=\"monospace\"];\n");
+        out.push_str("    edge [fontname=\"monospace\"];\n");
+        out.push_str("    __start [shape=point];\n");
+        out.push_str(&format!(
+            "    __start -> \"{}\";\n",
+            dot_escape(names.get(&self.initial_state).copied().unwrap_or("q0"))
+        ));
+        out.push('\n');
+
+        // Estados
+        for (name, id) in &states {
+            let name = dot_escape(name);
+            if self.error_states.contains(id) {
+                out.push_str(&format!(
+                    "    \"{}\" [shape=doublecircle, color=red, label=\"{}\\nERROR\"];\n",
+                    name, name
+                ));
+            } else if let Some(token_type) = self.final_states.get(id) {
+                out.push_str(&format!(
+                    "    \"{}\" [shape=doublecircle, label=\"{}\\n{}\"];\n",
+                    name,
+                    name,
+                    dot_escape(&format!("{:?}", token_type))
+                ));
+            } else {
+                out.push_str(&format!("    \"{}\";\n", name));
+            }
+        }
+        out.push('\n');
+
+        // Transiciones paralelas fusionadas, en orden de aparición
+        let mut edges: Vec<((StateId, StateId), Ranges)> = Vec::new();
+        for trans in &self.transitions {
+            let key = (trans.from, trans.to);
+            let ranges = code_ranges(&trans.char_class);
+            match edges.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, existing)) => existing.extend(ranges),
+                None => edges.push((key, ranges)),
+            }
+        }
+
+        for ((from, to), ranges) in edges {
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                dot_escape(names.get(&from).copied().unwrap_or("?")),
+                dot_escape(names.get(&to).copied().unwrap_or("?")),
+                dot_escape(&describe_ranges(&normalize(ranges)))
+            ));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_class_labels() {
+        assert_eq!(CharClass::Exact('a').label(), "a");
+        assert_eq!(CharClass::Exact(' ').label(), "\\s");
+        assert_eq!(CharClass::Exact('[').label(), "[");
+        assert_eq!(CharClass::Range('0', '9').label(), "[0-9]");
+        assert_eq!(CharClass::Any.label(), "ANY");
+        assert_eq!(CharClass::AnyExceptNewline.label(), "[^\\n]");
+        assert_eq!(
+            CharClass::parse("[a-zA-Z0-9_]").unwrap().label(),
+            "[0-9A-Z_a-z]"
+        );
+        assert_eq!(CharClass::UnicodeLetter.label(), "[\\p{L}]");
+        assert_eq!(
+            CharClass::parse("[a-zA-Z0-9_\\p{L}\\p{N}]").unwrap().label(),
+            "[_\\p{L}\\p{N}]"
+        );
+    }
+
+    #[test]
+    fn test_dot_export() {
+        let automaton = Automaton::parse(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+STATES
+q0
+q_ws FINAL:WHITESPACE
+q_str
+q_str_end FINAL:STRING
+q_error FINAL:ERROR
+END_STATES
+TRANSITIONS
+q0, \s, q_ws
+q0, \t, q_ws
+q0, ", q_str
+q_str, [a-z], q_str
+q_str, ", q_str_end
+END_TRANSITIONS
+"#,
+        )
+        .unwrap();
+
+        let dot = automaton.to_dot();
+        assert!(dot.starts_with("digraph automaton {"));
+        assert!(dot.contains("__start -> \"q0\";"));
+        assert!(dot.contains("\"q_ws\" [shape=doublecircle, label=\"q_ws\\nWhitespace\"];"));
+        assert!(dot.contains("\"q_error\" [shape=doublecircle, color=red"));
+
+        // \s y \t se fusionan en una sola arista
+        assert_eq!(dot.matches("\"q0\" -> \"q_ws\"").count(), 1);
+        assert!(dot.contains("\"q0\" -> \"q_ws\" [label=\"[\\\\t\\\\s]\"];"));
+
+        // Las comillas de la etiqueta se escapan
+        assert!(dot.contains("\"q_str\" -> \"q_str_end\" [label=\"\\\"\"];"));
+    }
+}
@@ -50,6 +50,16 @@ impl LexicalError {
         )
     }
     
+    /// Error por comentario de bloque sin cerrar (apunta al delimitador de apertura)
+    pub fn unterminated_block_comment(open: &str, close: &str, line: usize, column: usize) -> Self {
+        Self::new(
+            format!("Comentario de bloque sin cerrar: falta '{}' para el '{}' abierto aquí", close, open),
+            line,
+            column,
+            open.chars().count(),
+        )
+    }
+
     /// Error por número inválido
     pub fn invalid_number(lexeme: String, line: usize, column: usize) -> Self {
         Self::new(
@@ -73,6 +83,57 @@ impl fmt::Display for LexicalError {
 
 impl std::error::Error for LexicalError {}
 
+/// Error al cargar o parsear un archivo de autómata (.aut)
+#[derive(Debug, Clone)]
+pub struct AutomatonError {
+    /// Mensaje de error
+    pub message: String,
+
+    /// Línea del archivo .aut (0 si el error no corresponde a una línea)
+    pub line: usize,
+
+    /// Columna donde inicia el texto problemático
+    pub column: usize,
+
+    /// Longitud del texto problemático
+    pub length: usize,
+}
+
+impl AutomatonError {
+    /// Crea un error que no apunta a ninguna línea concreta
+    pub fn new(message: String) -> Self {
+        Self {
+            message,
+            line: 0,
+            column: 0,
+            length: 0,
+        }
+    }
+
+    /// Crea un error que abarca el contenido de una línea del archivo .aut
+    pub fn at_line(message: String, line: usize, raw_line: &str) -> Self {
+        let indent = raw_line.chars().take_while(|c| c.is_whitespace()).count();
+        Self {
+            message,
+            line,
+            column: indent + 1,
+            length: raw_line.trim().chars().count(),
+        }
+    }
+}
+
+impl fmt::Display for AutomatonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "Error en autómata, línea {}: {}", self.line, self.message)
+        } else {
+            write!(f, "Error en autómata: {}", self.message)
+        }
+    }
+}
+
+impl std::error::Error for AutomatonError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,4 +166,13 @@ mod tests {
         assert!(display.contains("1:5"));
         assert!(display.contains("Test error"));
     }
+
+    #[test]
+    fn test_automaton_error_at_line() {
+        let err = AutomatonError::at_line("Test".to_string(), 4, "  q0, a, q1");
+        assert_eq!(err.line, 4);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.length, 9);
+        assert!(format!("{}", err).contains("línea 4"));
+    }
 }
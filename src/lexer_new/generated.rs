@@ -0,0 +1,157 @@
+// src/lexer_new/generated.rs
+// DFA de config/automaton.aut generado como código Rust por build.rs
+
+use super::automaton::{BlockComment, fold_keyword};
+use super::byte_scanner::{ByteScanner, Transitions};
+use super::token::TokenType;
+
+/// Código generado: `next_state`, `final_token`, `keyword`, `DIALECTS`, ...
+#[allow(clippy::all, unreachable_patterns)]
+mod dfa {
+    use super::super::automaton::{BlockComment, is_unicode_letter};
+    use super::super::token::TokenType;
+    use once_cell::sync::Lazy;
+
+    include!(concat!(env!("OUT_DIR"), "/automaton_dfa.rs"));
+}
+
+/// DFA incrustado en el binario: ni lee archivos ni interpreta tablas
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeneratedDfa {
+    /// Dialecto activo (índice en `dialects()`)
+    dialect: usize,
+}
+
+impl GeneratedDfa {
+    /// Nombres de los dialectos de palabras reservadas
+    pub fn dialects() -> &'static [&'static str] {
+        dfa::DIALECTS
+    }
+}
+
+impl Transitions for GeneratedDfa {
+    fn initial_state(&self) -> u32 {
+        dfa::INITIAL_STATE
+    }
+
+    fn step_ascii(&self, state: u32, byte: u8) -> Option<u32> {
+        dfa::next_state(state, byte as char)
+    }
+
+    fn step_char(&self, state: u32, ch: char) -> Option<u32> {
+        dfa::next_state(state, ch)
+    }
+
+    fn final_token(&self, state: u32) -> Option<&TokenType> {
+        dfa::final_token(state)
+    }
+
+    fn classify_identifier(&self, lexeme: &str) -> TokenType {
+        // Igual que `Dialect::classify_identifier`: normalizado y luego exacto
+        dfa::keyword(self.dialect, &fold_keyword(lexeme))
+            .or_else(|| dfa::keyword(self.dialect, lexeme))
+            .unwrap_or(TokenType::Identifier)
+    }
+
+    fn block_comment(&self) -> Option<&BlockComment> {
+        dfa::BLOCK_COMMENT.as_ref()
+    }
+
+    fn set_dialect(&mut self, name: &str) -> Result<(), String> {
+        let key = fold_keyword(name);
+        self.dialect = dfa::DIALECTS
+            .iter()
+            .position(|dialect| fold_keyword(dialect) == key)
+            .ok_or_else(|| {
+                format!("Dialecto desconocido '{}' (disponibles: {})", name, dfa::DIALECTS.join(", "))
+            })?;
+        Ok(())
+    }
+}
+
+impl<'src> ByteScanner<'src, GeneratedDfa> {
+    /// Crea un scanner con el DFA generado desde `config/automaton.aut`
+    pub fn generated(source: &'src str) -> Self {
+        Self::with_transitions(source, GeneratedDfa::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::loader::{AUTOMATON, DEFAULT_AUTOMATON_SOURCE};
+    use crate::lexer_new::{Automaton, Scanner, Span};
+
+    type Stream = Vec<(TokenType, String, Span)>;
+
+    /// Tokens del autómata interpretado y del generado, en un dialecto
+    fn both(source: &str, dialect: &str) -> (Stream, Stream) {
+        let mut scanner = Scanner::new(source, &*AUTOMATON);
+        scanner.set_dialect(dialect).unwrap();
+        let interpreted = scanner
+            .scan_all()
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.token_type, t.lexeme, t.span))
+            .collect();
+
+        let mut scanner = ByteScanner::generated(source);
+        scanner.set_dialect(dialect).unwrap();
+        let generated = scanner
+            .scan_all()
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.token_type, t.lexeme.to_string(), t.span))
+            .collect();
+
+        (interpreted, generated)
+    }
+
+    #[test]
+    fn test_generated_matches_example_programs() {
+        for source in [include_str!("../../ejemplo1.net"), include_str!("../../ejemplo2.net")] {
+            let (interpreted, generated) = both(source, "espanol");
+            assert_eq!(interpreted, generated);
+        }
+
+        let (interpreted, generated) = both(include_str!("../../ejemplo_en.net"), "english");
+        assert_eq!(interpreted, generated);
+    }
+
+    #[test]
+    fn test_generated_matches_scanner_inputs() {
+        let inputs = [
+            "programa PROGRAMA Programa inicio fin",
+            "a, b; 12345 abc\ndef",
+            "x <= 1 && y <> 2 || !z >= 3",
+            "a /* x /* y */ z */ b // fin\n/// doc\nc",
+            "máquinaRecepción año2 mo\u{301}dulo ñandú MÓDULO",
+            "uno.p[1] = 0 colocaCoaxial ColocaCoaxial",
+        ];
+        for source in inputs {
+            let (interpreted, generated) = both(source, "espanol");
+            assert_eq!(interpreted, generated, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_generated_errors_match() {
+        for source in ["programa @", "/* abierto", "a\n  $ b"] {
+            let expected = Scanner::new(source, &*AUTOMATON).scan_all().unwrap_err();
+            let err = ByteScanner::generated(source).scan_all().unwrap_err();
+            assert_eq!(
+                (err.line, err.column, err.length, err.message),
+                (expected.line, expected.column, expected.length, expected.message),
+            );
+        }
+    }
+
+    #[test]
+    fn test_generated_code_is_current() {
+        // El código incluido corresponde al autómata incrustado actual
+        let automaton = Automaton::parse(DEFAULT_AUTOMATON_SOURCE).unwrap();
+        let code = include_str!(concat!(env!("OUT_DIR"), "/automaton_dfa.rs"));
+        assert_eq!(automaton.to_rust(), code);
+        assert_eq!(GeneratedDfa::dialects(), ["espanol", "english"]);
+    }
+}
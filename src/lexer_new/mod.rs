@@ -3,10 +3,24 @@
 pub mod automaton;
 pub mod token;
 pub mod scanner;
+pub mod byte_scanner;
+pub mod codegen;
+pub mod generated;
 pub mod error;
+pub mod validate;
+pub mod spec;
+pub mod dot;
+pub mod tokenize;
 
 // Re-export commonly used types
-pub use automaton::Automaton;
+pub use automaton::{Automaton, Dialect};
 pub use scanner::Scanner;
-pub use token::{Token, TokenType};
-pub use error::LexicalError;
+pub use byte_scanner::{ByteScanner, DenseDfa, DenseTable, TokenRef, Transitions};
+pub use generated::GeneratedDfa;
+pub use token::{Span, Token, TokenType};
+pub use error::{AutomatonError, LexicalError};
+pub use validate::{ValidationIssue, ValidationKind};
+pub use tokenize::{
+    lexical_diagnostics, report_lexical_errors, select_dialect, tokenize, tokenize_with_automaton,
+    tokenize_with_dialect,
+};
@@ -1,39 +1,70 @@
 // src/lexer_new/scanner.rs
 // Scanner principal que usa el autómata para tokenizar
 
-use super::automaton::Automaton;
-use super::token::{Token, TokenType};
+use super::automaton::{Automaton, BlockComment};
+use super::token::{Token, TokenType, is_combining_mark};
 use super::error::LexicalError;
+use crate::config::load_automaton;
+use std::borrow::Borrow;
 
 /// Scanner de código fuente
-pub struct Scanner {
+///
+/// El autómata puede ser prestado (`&Automaton`), compartido (`Arc<Automaton>`)
+/// o propio (`Automaton`); por defecto es el autómata activo de `config`.
+pub struct Scanner<A: Borrow<Automaton> = &'static Automaton> {
     /// Código fuente
     source: Vec<char>,
     
     /// Posición actual
     pos: usize,
     
+    /// Desplazamiento en bytes de la posición actual
+    offset: usize,
+    
     /// Línea actual (1-indexed)
     line: usize,
     
     /// Columna actual (1-indexed)
     column: usize,
     
-    /// Referencia al autómata
-    automaton: &'static Automaton,
+    /// Autómata que guía el escaneo
+    automaton: A,
+
+    /// Dialecto de palabras reservadas (índice en `Automaton::dialects`)
+    dialect: usize,
 }
 
 impl Scanner {
+    /// Crea un scanner con el autómata activo (`config::load_automaton`)
+    pub fn with_default_automaton(source: &str) -> Self {
+        Self::new(source, load_automaton())
+    }
+}
+
+impl<A: Borrow<Automaton>> Scanner<A> {
     /// Crea un nuevo scanner
-    pub fn new(source: &str, automaton: &'static Automaton) -> Self {
+    pub fn new(source: &str, automaton: A) -> Self {
         Self {
             source: source.chars().collect(),
             pos: 0,
+            offset: 0,
             line: 1,
             column: 1,
             automaton,
+            dialect: 0,
         }
     }
+
+    /// Clasifica las palabras reservadas con otro dialecto del autómata
+    pub fn set_dialect(&mut self, name: &str) -> Result<(), String> {
+        self.dialect = self.automaton().dialect_index(name)?;
+        Ok(())
+    }
+
+    /// Autómata que usa el scanner
+    pub fn automaton(&self) -> &Automaton {
+        self.automaton.borrow()
+    }
     
     /// Escanea todos los tokens del código fuente
     pub fn scan_all(&mut self) -> Result<Vec<Token>, LexicalError> {
@@ -52,11 +83,60 @@ impl Scanner {
         }
         
         // Agregar token EOF
-        tokens.push(Token::eof(self.line, self.column));
+        tokens.push(self.eof_token());
         
         Ok(tokens)
     }
+
+    /// Escanea todos los tokens sin descartar nada (modo sin pérdida)
+    ///
+    /// A diferencia de `scan_all`, conserva espacios y comentarios (trivia),
+    /// así que concatenar los lexemas reproduce el archivo exacto. Cada token
+    /// lleva su `span` en bytes para que formateadores, herramientas de
+    /// refactorización o un editor puedan reescribir el código sin perder
+    /// comentarios.
+    pub fn scan_lossless(&mut self) -> Result<Vec<Token>, LexicalError> {
+        let mut tokens = Vec::new();
+
+        while let Some(token) = self.scan_token()? {
+            tokens.push(token);
+        }
+
+        tokens.push(self.eof_token());
+
+        Ok(tokens)
+    }
     
+    /// Escanea todos los tokens sin detenerse en errores léxicos
+    ///
+    /// Cada carácter que no inicia ningún token se reporta y se salta, de modo
+    /// que un solo recorrido devuelve todos los errores del archivo junto con
+    /// los tokens válidos (terminados en EOF).
+    pub fn scan_all_recovering(&mut self) -> (Vec<Token>, Vec<LexicalError>) {
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+
+        loop {
+            match self.scan_token() {
+                Ok(Some(token)) => {
+                    if !token.token_type.should_ignore() {
+                        tokens.push(token);
+                    }
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    errors.push(error);
+                    // Saltar el carácter problemático y continuar
+                    self.advance();
+                }
+            }
+        }
+
+        tokens.push(self.eof_token());
+
+        (tokens, errors)
+    }
+
     /// Escanea el siguiente token
     pub fn scan_token(&mut self) -> Result<Option<Token>, LexicalError> {
         // Fin del archivo
@@ -64,27 +144,39 @@ impl Scanner {
             return Ok(None);
         }
         
+        // Los comentarios de bloque (posiblemente anidados) no caben en un DFA
+        let opens_block = self
+            .automaton()
+            .block_comment()
+            .is_some_and(|block| self.matches_at(self.pos, &block.open));
+        if opens_block {
+            // Copia de los delimitadores: el scanner avanza mientras los usa
+            let block = self.automaton().block_comment().cloned().expect("comprobado arriba");
+            return self.scan_block_comment(&block).map(Some);
+        }
+
         let start_pos = self.pos;
+        let start_offset = self.offset;
         let start_line = self.line;
         let start_column = self.column;
         
         // Ejecutar el autómata
-        let mut current_state = self.automaton.initial_state();
+        let mut current_state = self.automaton().initial_state();
         let mut last_final_state = None;
-        let mut last_final_pos = start_pos;
+        let mut last_final_at = self.mark();
         
         while !self.is_at_end() {
             let ch = self.current_char();
             
             // Intentar transición
-            if let Some(next_state) = self.automaton.next_state(current_state, ch) {
+            if let Some(next_state) = self.automaton().next_state(current_state, ch) {
                 self.advance();
                 current_state = next_state;
                 
                 // Si llegamos a un estado final, recordarlo
-                if let Some(token_type) = self.automaton.is_final(current_state) {
+                if let Some(token_type) = self.automaton().is_final(current_state) {
                     last_final_state = Some(token_type.clone());
-                    last_final_pos = self.pos;
+                    last_final_at = self.mark();
                 }
             } else {
                 break;
@@ -94,14 +186,13 @@ impl Scanner {
         // Verificar si terminamos en un estado final
         if let Some(token_type) = last_final_state {
             // Retroceder a la última posición válida
-            self.pos = last_final_pos;
-            self.update_position_from(start_pos);
+            self.reset_to(last_final_at);
             
-            let lexeme: String = self.source[start_pos..last_final_pos].iter().collect();
+            let lexeme: String = self.source[start_pos..self.pos].iter().collect();
             
             // Clasificar identificadores (keywords vs identifiers)
             let final_token_type = if token_type == TokenType::Identifier {
-                self.automaton.classify_identifier(&lexeme)
+                self.automaton().dialects()[self.dialect].classify_identifier(&lexeme)
             } else {
                 token_type
             };
@@ -111,16 +202,73 @@ impl Scanner {
                 lexeme,
                 start_line,
                 start_column,
-            );
+            )
+            .at_offset(start_offset);
             
             return Ok(Some(token));
         }
         
-        // No se reconoció ningún token válido
+        // No se reconoció ningún token válido: volver al inicio para que el
+        // error apunte al carácter que no pudo comenzar un token
+        self.reset_to((start_pos, start_offset, start_line, start_column));
+
         let ch = self.current_char();
         Err(LexicalError::invalid_char(ch, start_line, start_column))
     }
     
+    /// Escanea un comentario de bloque que empieza en la posición actual
+    ///
+    /// Con `nested` cada apertura interna requiere su propio cierre. Si el
+    /// archivo termina antes, el error apunta al delimitador de apertura y el
+    /// resto del archivo queda consumido.
+    fn scan_block_comment(&mut self, block: &BlockComment) -> Result<Token, LexicalError> {
+        let start_pos = self.pos;
+        let start_offset = self.offset;
+        let start_line = self.line;
+        let start_column = self.column;
+
+        self.advance_by(block.open.chars().count());
+        let mut depth = 1;
+
+        while !self.is_at_end() {
+            if self.matches_at(self.pos, &block.close) {
+                self.advance_by(block.close.chars().count());
+                depth -= 1;
+                if depth == 0 {
+                    let lexeme: String = self.source[start_pos..self.pos].iter().collect();
+                    let token = Token::new(TokenType::Comment, lexeme, start_line, start_column);
+                    return Ok(token.at_offset(start_offset));
+                }
+            } else if block.nested && self.matches_at(self.pos, &block.open) {
+                self.advance_by(block.open.chars().count());
+                depth += 1;
+            } else {
+                self.advance();
+            }
+        }
+
+        Err(LexicalError::unterminated_block_comment(
+            &block.open,
+            &block.close,
+            start_line,
+            start_column,
+        ))
+    }
+
+    /// Verifica si el texto `s` aparece en la posición `pos`
+    fn matches_at(&self, pos: usize, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, ch)| self.source.get(pos + i) == Some(&ch))
+    }
+
+    /// Avanza `n` posiciones
+    fn advance_by(&mut self, n: usize) {
+        for _ in 0..n {
+            self.advance();
+        }
+    }
+
     /// Obtiene el carácter actual sin avanzar
     fn current_char(&self) -> char {
         if self.is_at_end() {
@@ -135,11 +283,13 @@ impl Scanner {
         if !self.is_at_end() {
             let ch = self.source[self.pos];
             self.pos += 1;
+            self.offset += ch.len_utf8();
             
             if ch == '\n' {
                 self.line += 1;
                 self.column = 1;
-            } else {
+            } else if !is_combining_mark(ch) {
+                // Las marcas combinantes no ocupan columna propia
                 self.column += 1;
             }
         }
@@ -150,23 +300,22 @@ impl Scanner {
         self.pos >= self.source.len()
     }
     
-    /// Actualiza line y column basándose en el contenido desde start_pos
-    fn update_position_from(&mut self, start_pos: usize) {
-        self.line = 1;
-        self.column = 1;
-        
-        for i in 0..self.pos {
-            if i >= self.source.len() {
-                break;
-            }
-            
-            if self.source[i] == '\n' {
-                self.line += 1;
-                self.column = 1;
-            } else {
-                self.column += 1;
-            }
-        }
+    /// Posición actual: (índice, byte, línea, columna)
+    fn mark(&self) -> (usize, usize, usize, usize) {
+        (self.pos, self.offset, self.line, self.column)
+    }
+
+    /// Vuelve a una posición guardada con `mark`
+    fn reset_to(&mut self, (pos, offset, line, column): (usize, usize, usize, usize)) {
+        self.pos = pos;
+        self.offset = offset;
+        self.line = line;
+        self.column = column;
+    }
+
+    /// Token EOF ubicado al final del código fuente
+    fn eof_token(&self) -> Token {
+        Token::eof(self.line, self.column).at_offset(self.offset)
     }
 }
 
@@ -212,6 +361,12 @@ programa, PROGRAMA
 inicio, INICIO
 fin, FIN
 END_KEYWORDS
+
+KEYWORDS english
+program, PROGRAMA
+begin, INICIO
+end, FIN
+END_KEYWORDS
 "#;
         Automaton::parse(content).expect("Failed to parse test automaton")
     }
@@ -222,7 +377,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scanner_creation() {
-        let scanner = Scanner::new("test", &TEST_AUTOMATON);
+        let scanner = Scanner::new("test", &*TEST_AUTOMATON);
         assert_eq!(scanner.pos, 0);
         assert_eq!(scanner.line, 1);
         assert_eq!(scanner.column, 1);
@@ -230,10 +385,10 @@ END_KEYWORDS
 
     #[test]
     fn test_is_at_end() {
-        let mut scanner = Scanner::new("", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("", &*TEST_AUTOMATON);
         assert!(scanner.is_at_end());
 
-        let mut scanner = Scanner::new("a", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("a", &*TEST_AUTOMATON);
         assert!(!scanner.is_at_end());
         scanner.advance();
         assert!(scanner.is_at_end());
@@ -241,7 +396,7 @@ END_KEYWORDS
 
     #[test]
     fn test_advance() {
-        let mut scanner = Scanner::new("abc\ndef", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("abc\ndef", &*TEST_AUTOMATON);
 
         assert_eq!(scanner.line, 1);
         assert_eq!(scanner.column, 1);
@@ -258,7 +413,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_identifier() {
-        let mut scanner = Scanner::new("test", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("test", &*TEST_AUTOMATON);
         let token = scanner.scan_token().unwrap().unwrap();
 
         assert_eq!(token.token_type, TokenType::Identifier);
@@ -269,7 +424,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_number() {
-        let mut scanner = Scanner::new("12345", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("12345", &*TEST_AUTOMATON);
         let token = scanner.scan_token().unwrap().unwrap();
 
         assert_eq!(token.token_type, TokenType::Number);
@@ -278,7 +433,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_keyword() {
-        let mut scanner = Scanner::new("programa", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("programa", &*TEST_AUTOMATON);
         let token = scanner.scan_token().unwrap().unwrap();
 
         assert_eq!(token.token_type, TokenType::Programa);
@@ -287,16 +442,64 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_keyword_case_insensitive() {
-        let mut scanner = Scanner::new("PROGRAMA", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("PROGRAMA", &*TEST_AUTOMATON);
         let token = scanner.scan_token().unwrap().unwrap();
 
         assert_eq!(token.token_type, TokenType::Programa);
         assert_eq!(token.lexeme, "PROGRAMA");
     }
 
+    #[test]
+    fn test_scan_keyword_in_dialect() {
+        let kinds = |scanner: &mut Scanner| -> Vec<TokenType> {
+            scanner.scan_all().unwrap().into_iter().map(|t| t.token_type).collect()
+        };
+
+        let mut scanner = Scanner::new("begin inicio", &*TEST_AUTOMATON);
+        assert_eq!(
+            kinds(&mut scanner),
+            vec![TokenType::Identifier, TokenType::Inicio, TokenType::Eof]
+        );
+
+        // En inglés las palabras en español vuelven a ser identificadores
+        let mut scanner = Scanner::new("BEGIN inicio", &*TEST_AUTOMATON);
+        scanner.set_dialect("English").unwrap();
+        assert_eq!(
+            kinds(&mut scanner),
+            vec![TokenType::Inicio, TokenType::Identifier, TokenType::Eof]
+        );
+
+        let mut scanner = Scanner::new("", &*TEST_AUTOMATON);
+        let err = scanner.set_dialect("klingon").unwrap_err();
+        assert!(err.contains("espanol, english"), "{}", err);
+    }
+
+    #[test]
+    fn test_owned_and_shared_automata() {
+        use std::sync::Arc;
+
+        fn first<A: Borrow<Automaton>>(scanner: &mut Scanner<A>) -> TokenType {
+            scanner.scan_all().unwrap()[0].token_type.clone()
+        }
+
+        // Dos autómatas distintos en el mismo proceso, sin pasar por config
+        let mut owned = Scanner::new("programa", create_test_automaton());
+        assert_eq!(first(&mut owned), TokenType::Programa);
+
+        let shared = Arc::new(create_test_automaton());
+        let mut a = Scanner::new("fin", Arc::clone(&shared));
+        let mut b = Scanner::new("inicio", shared);
+        assert_eq!(first(&mut a), TokenType::Fin);
+        assert_eq!(first(&mut b), TokenType::Inicio);
+
+        let mut default = Scanner::with_default_automaton("programa");
+        assert_eq!(first(&mut default), TokenType::Programa);
+        assert!(std::ptr::eq(default.automaton(), load_automaton()));
+    }
+
     #[test]
     fn test_scan_delimiter() {
-        let mut scanner = Scanner::new(",", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new(",", &*TEST_AUTOMATON);
         let token = scanner.scan_token().unwrap().unwrap();
 
         assert_eq!(token.token_type, TokenType::Comma);
@@ -305,7 +508,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_multiple_tokens() {
-        let mut scanner = Scanner::new("var1,var2", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("var1,var2", &*TEST_AUTOMATON);
 
         let token1 = scanner.scan_token().unwrap().unwrap();
         assert_eq!(token1.token_type, TokenType::Identifier);
@@ -321,7 +524,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_all_tokens() {
-        let mut scanner = Scanner::new("programa inicio fin", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("programa inicio fin", &*TEST_AUTOMATON);
         let tokens = scanner.scan_all().unwrap();
 
         // Should have 3 keywords + EOF (whitespace ignored)
@@ -334,7 +537,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_with_whitespace() {
-        let mut scanner = Scanner::new("  test  ", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("  test  ", &*TEST_AUTOMATON);
         let tokens = scanner.scan_all().unwrap();
 
         // Whitespace should be ignored
@@ -345,7 +548,7 @@ END_KEYWORDS
 
     #[test]
     fn test_scan_multiline() {
-        let mut scanner = Scanner::new("var1\nvar2", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("var1\nvar2", &*TEST_AUTOMATON);
         let tokens = scanner.scan_all().unwrap();
 
         assert_eq!(tokens.len(), 3); // 2 identifiers + EOF
@@ -355,15 +558,158 @@ END_KEYWORDS
 
     #[test]
     fn test_invalid_character() {
-        let mut scanner = Scanner::new("@", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("@", &*TEST_AUTOMATON);
         let result = scanner.scan_token();
 
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_invalid_character_after_partial_match() {
+        // "1@" avanza sobre el número; "@" falla sin consumir nada
+        let mut scanner = Scanner::new("1@", &*TEST_AUTOMATON);
+        scanner.scan_token().unwrap();
+        let error = scanner.scan_token().unwrap_err();
+
+        assert_eq!(error.column, 2);
+        assert!(error.message.contains('@'));
+        assert_eq!(scanner.pos, 1);
+    }
+
+    #[test]
+    fn test_scan_all_recovering() {
+        let mut scanner = Scanner::new("a @ b\n@@ c", &*TEST_AUTOMATON);
+        let (tokens, errors) = scanner.scan_all_recovering();
+
+        let lexemes: Vec<&str> = tokens.iter().map(|t| t.lexeme.as_str()).collect();
+        assert_eq!(lexemes, vec!["a", "b", "c", ""]);
+        assert_eq!(tokens.last().unwrap().token_type, TokenType::Eof);
+
+        let positions: Vec<(usize, usize)> = errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(positions, vec![(1, 3), (2, 1), (2, 2)]);
+    }
+
+    fn block_comment_automaton(nested: bool) -> Automaton {
+        let content = format!(
+            "METADATA\ninitial_state: q0\nblock_comment: /* */\nnested_comments: {}\nEND_METADATA\n\
+             STATES\nq0\nq_id FINAL:IDENTIFIER\nq_ws FINAL:WHITESPACE\nq_star FINAL:IDENTIFIER\nEND_STATES\n\
+             TRANSITIONS\nq0, [a-z], q_id\nq0, \\s, q_ws\nq0, *, q_star\nq0, /, q_star\nEND_TRANSITIONS\n",
+            nested
+        );
+        Automaton::parse(&content).unwrap()
+    }
+
+    #[test]
+    fn test_nested_block_comment() {
+        let mut scanner = Scanner::new("a /* x /* y */ z */ b", block_comment_automaton(true));
+        let (tokens, errors) = scanner.scan_all_recovering();
+
+        assert!(errors.is_empty());
+        let lexemes: Vec<&str> = tokens.iter().map(|t| t.lexeme.as_str()).collect();
+        assert_eq!(lexemes, vec!["a", "b", ""]);
+    }
+
+    #[test]
+    fn test_flat_block_comment() {
+        // Sin anidamiento el primer */ cierra el comentario
+        let mut scanner = Scanner::new("/* x /* y */ z */", block_comment_automaton(false));
+        let (tokens, errors) = scanner.scan_all_recovering();
+
+        assert!(errors.is_empty());
+        let lexemes: Vec<&str> = tokens.iter().map(|t| t.lexeme.as_str()).collect();
+        assert_eq!(lexemes, vec!["z", "*", "/", ""]);
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        let mut scanner = Scanner::new("a  /* x /* */", block_comment_automaton(true));
+        let (tokens, errors) = scanner.scan_all_recovering();
+
+        assert_eq!(tokens.len(), 2); // a + EOF
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column, errors[0].length), (1, 4, 2));
+        assert!(errors[0].message.contains("*/"));
+    }
+
+    #[test]
+    fn test_unicode_identifiers() {
+        let source = "máquinaRecepción año2 módulo";
+        let mut scanner = Scanner::with_default_automaton(source);
+        let tokens = scanner.scan_all().unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::Identifier);
+        assert_eq!(tokens[0].lexeme, "máquinaRecepción");
+        assert_eq!((tokens[0].column, tokens[0].length), (1, 16));
+        assert_eq!(tokens[1].lexeme, "año2");
+        assert_eq!(tokens[1].column, 18);
+        assert_eq!(tokens[2].token_type, TokenType::Modulo);
+        assert_eq!(tokens[2].column, 23);
+    }
+
+    #[test]
+    fn test_combining_marks_share_column() {
+        // "mo" + tilde combinante + "dulo x": la tilde no ocupa columna
+        let source = "mo\u{301}dulo x";
+        let mut scanner = Scanner::with_default_automaton(source);
+        let tokens = scanner.scan_all().unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::Modulo);
+        assert_eq!(tokens[0].length, 6);
+        assert_eq!(tokens[1].lexeme, "x");
+        assert_eq!(tokens[1].column, 8);
+    }
+
+    #[test]
+    fn test_scan_lossless_round_trip() {
+        let source = format!(
+            "// Topología de ejemplo\n/* bloque */\n{}",
+            include_str!("../../ejemplo1.net")
+        );
+        let source = source.as_str();
+        let mut scanner = Scanner::with_default_automaton(source);
+        let tokens = scanner.scan_lossless().unwrap();
+
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Whitespace));
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment));
+
+        // Concatenar los lexemas reproduce el archivo exacto
+        let rebuilt: String = tokens.iter().map(|t| t.lexeme.as_str()).collect();
+        assert_eq!(rebuilt, source);
+
+        // Cada span apunta a su lexema y continúa donde terminó el anterior
+        let mut expected_start = 0;
+        for token in &tokens {
+            assert_eq!(token.span.start, expected_start);
+            assert_eq!(token.span.text(source), token.lexeme);
+            expected_start = token.span.end;
+        }
+        assert_eq!(tokens.last().unwrap().span.start, source.len());
+    }
+
+    #[test]
+    fn test_lossless_spans() {
+        let source = "año /* a\n b */ x";
+        let mut scanner = Scanner::with_default_automaton(source);
+        let tokens = scanner.scan_lossless().unwrap();
+
+        // "año" ocupa 4 bytes pero 3 columnas
+        assert_eq!((tokens[0].span.start, tokens[0].span.end), (0, 4));
+        assert_eq!(tokens[0].span.end_column, 4);
+
+        let comment = &tokens[2];
+        assert_eq!(comment.token_type, TokenType::Comment);
+        assert_eq!((comment.span.start, comment.span.end), (5, 15));
+        assert_eq!((comment.span.line, comment.span.column), (1, 5));
+        assert_eq!((comment.span.end_line, comment.span.end_column), (2, 6));
+
+        let x = &tokens[4];
+        assert_eq!(x.span.text(source), "x");
+        assert_eq!((x.line, x.column), (2, 7));
+    }
+
     #[test]
     fn test_empty_input() {
-        let mut scanner = Scanner::new("", &TEST_AUTOMATON);
+        let mut scanner = Scanner::new("", &*TEST_AUTOMATON);
         let tokens = scanner.scan_all().unwrap();
 
         // Should only have EOF
@@ -0,0 +1,828 @@
+// src/lexer_new/spec.rs
+// Especificación de tokens con expresiones regulares (.spec)
+//
+// Compila reglas `NOMBRE = regex` a un Automaton:
+//   regex → NFA (Thompson) → DFA (construcción de subconjuntos) → DFA mínimo
+// El resultado es un Automaton normal, por lo que el Scanner no cambia.
+
+use super::automaton::{
+    Automaton, BlockComment, CharClass, DEFAULT_DIALECT, Dialect, KeywordEntry, StateId,
+    Transition, UNICODE_DIGIT_RANGES, UNICODE_LETTER_RANGES, dialect_slot, keywords_header,
+};
+use super::error::AutomatonError;
+use super::token::TokenType;
+use once_cell::sync::OnceCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+
+/// Último punto de código válido
+const MAX_CODE: u32 = char::MAX as u32;
+
+/// Rangos de puntos de código (inclusivos), ordenados y sin solapamiento
+pub(super) type Ranges = Vec<(u32, u32)>;
+
+/// Ordena y fusiona rangos solapados o contiguos
+pub(super) fn normalize(mut ranges: Ranges) -> Ranges {
+    ranges.sort_unstable();
+    let mut merged: Ranges = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                *last_end = (*last_end).max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Complemento de un conjunto de rangos normalizado
+pub(super) fn complement(ranges: &[(u32, u32)]) -> Ranges {
+    let mut result = Vec::new();
+    let mut next = 0u32;
+    for &(start, end) in ranges {
+        if start > next {
+            result.push((next, start - 1));
+        }
+        next = end.saturating_add(1);
+    }
+    if next <= MAX_CODE {
+        result.push((next, MAX_CODE));
+    }
+    result
+}
+
+/// Convierte rangos de puntos de código a una CharClass (omitiendo sustitutos UTF-16)
+fn to_char_class(ranges: &Ranges) -> CharClass {
+    let mut classes: Vec<CharClass> = ranges
+        .iter()
+        .filter_map(|&(start, end)| {
+            let start = if (0xD800..=0xDFFF).contains(&start) { 0xE000 } else { start };
+            let end = if (0xD800..=0xDFFF).contains(&end) { 0xD7FF } else { end };
+            let (start, end) = (char::from_u32(start)?, char::from_u32(end)?);
+            match (start, end) {
+                (s, e) if s > e => None,
+                (s, e) if s == e => Some(CharClass::Exact(s)),
+                (s, e) => Some(CharClass::Range(s, e)),
+            }
+        })
+        .collect();
+
+    if classes.len() == 1 {
+        classes.pop().unwrap()
+    } else {
+        CharClass::Multi(classes)
+    }
+}
+
+// ============================================================================
+// EXPRESIONES REGULARES
+// ============================================================================
+
+/// Árbol de una expresión regular
+#[derive(Debug, Clone)]
+enum Regex {
+    /// Conjunto de caracteres
+    Set(Ranges),
+    Concat(Vec<Regex>),
+    Alt(Vec<Regex>),
+    Star(Box<Regex>),
+    Plus(Box<Regex>),
+    Optional(Box<Regex>),
+}
+
+/// Parser descendente recursivo de expresiones regulares
+///
+/// Sintaxis: literales, `.` (cualquier carácter excepto newline), `[a-z_]`,
+/// `[^"\\]`, `( )`, `|`, `*`, `+`, `?`. Escapes como en .aut: `\n`, `\t`,
+/// `\r`, `\s` (espacio) y `\x` para cualquier metacarácter `x`. `\p{L}` y
+/// `\p{N}` son las letras y los dígitos Unicode, también dentro de clases.
+struct RegexParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl RegexParser {
+    fn parse(pattern: &str) -> Result<Regex, String> {
+        let mut parser = RegexParser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+
+        if parser.chars.is_empty() {
+            return Err("Expresión regular vacía".to_string());
+        }
+
+        let regex = parser.parse_alt()?;
+        match parser.peek() {
+            None => Ok(regex),
+            Some(ch) => Err(parser.error(format!("'{}' inesperado", ch))),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek();
+        self.pos += 1;
+        ch
+    }
+
+    fn error(&self, message: String) -> String {
+        format!("{} en la posición {} de la expresión regular", message, self.pos + 1)
+    }
+
+    /// alt := concat ('|' concat)*
+    fn parse_alt(&mut self) -> Result<Regex, String> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.peek() == Some('|') {
+            self.next();
+            branches.push(self.parse_concat()?);
+        }
+        Ok(if branches.len() == 1 { branches.pop().unwrap() } else { Regex::Alt(branches) })
+    }
+
+    /// concat := repeat+
+    fn parse_concat(&mut self) -> Result<Regex, String> {
+        let mut items = Vec::new();
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            items.push(self.parse_repeat()?);
+        }
+
+        match items.len() {
+            0 => Err(self.error("Alternativa vacía".to_string())),
+            1 => Ok(items.pop().unwrap()),
+            _ => Ok(Regex::Concat(items)),
+        }
+    }
+
+    /// repeat := atom ('*' | '+' | '?')*
+    fn parse_repeat(&mut self) -> Result<Regex, String> {
+        let mut atom = self.parse_atom()?;
+        while let Some(op) = self.peek() {
+            atom = match op {
+                '*' => Regex::Star(Box::new(atom)),
+                '+' => Regex::Plus(Box::new(atom)),
+                '?' => Regex::Optional(Box::new(atom)),
+                _ => break,
+            };
+            self.next();
+        }
+        Ok(atom)
+    }
+
+    fn parse_atom(&mut self) -> Result<Regex, String> {
+        match self.next() {
+            Some('(') => {
+                let inner = self.parse_alt()?;
+                if self.next() != Some(')') {
+                    return Err(self.error("Se esperaba ')'".to_string()));
+                }
+                Ok(inner)
+            }
+            Some('[') => self.parse_class(),
+            Some('.') => Ok(Regex::Set(complement(&[('\n' as u32, '\n' as u32)]))),
+            Some('\\') if self.peek() == Some('p') => Ok(Regex::Set(self.parse_property()?)),
+            Some('\\') => {
+                let ch = self.parse_escape()?;
+                Ok(Regex::Set(vec![(ch as u32, ch as u32)]))
+            }
+            Some(ch @ ('*' | '+' | '?')) => {
+                self.pos -= 1;
+                Err(self.error(format!("'{}' sin expresión que repetir", ch)))
+            }
+            Some(ch) => Ok(Regex::Set(vec![(ch as u32, ch as u32)])),
+            None => Err(self.error("Expresión incompleta".to_string())),
+        }
+    }
+
+    /// Carácter tras `\`
+    fn parse_escape(&mut self) -> Result<char, String> {
+        match self.next() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('s') => Ok(' '),
+            Some(ch) => Ok(ch),
+            None => Err(self.error("Escape incompleto".to_string())),
+        }
+    }
+
+    /// Propiedad Unicode tras `\`: `p{L}` (letras) o `p{N}` (dígitos)
+    fn parse_property(&mut self) -> Result<Ranges, String> {
+        self.next();
+        let ranges: &[(char, char)] = match (self.next(), self.next(), self.next()) {
+            (Some('{'), Some('L'), Some('}')) => &UNICODE_LETTER_RANGES,
+            (Some('{'), Some('N'), Some('}')) => &UNICODE_DIGIT_RANGES,
+            _ => return Err(self.error("Propiedad Unicode desconocida (use \\p{L} o \\p{N})".to_string())),
+        };
+        Ok(ranges.iter().map(|&(s, e)| (s as u32, e as u32)).collect())
+    }
+
+    /// class := '[' '^'? (char ('-' char)? | '\p{L}' | '\p{N}')+ ']'
+    fn parse_class(&mut self) -> Result<Regex, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.next();
+        }
+
+        let mut ranges = Vec::new();
+        loop {
+            let start = match self.next() {
+                Some(']') if !ranges.is_empty() => break,
+                Some(']') => return Err(self.error("Clase de caracteres vacía".to_string())),
+                Some('\\') if self.peek() == Some('p') => {
+                    ranges.extend(self.parse_property()?);
+                    continue;
+                }
+                Some('\\') => self.parse_escape()?,
+                Some(ch) => ch,
+                None => return Err(self.error("Falta ']'".to_string())),
+            };
+
+            // Rango a-z (un '-' antes de ']' es literal)
+            let end = if self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']') {
+                self.next();
+                match self.next() {
+                    Some('\\') => self.parse_escape()?,
+                    Some(ch) => ch,
+                    None => return Err(self.error("Falta ']'".to_string())),
+                }
+            } else {
+                start
+            };
+
+            if start > end {
+                return Err(self.error(format!("Rango invertido '{}-{}'", start, end)));
+            }
+            ranges.push((start as u32, end as u32));
+        }
+
+        let ranges = normalize(ranges);
+        Ok(Regex::Set(if negated { complement(&ranges) } else { ranges }))
+    }
+}
+
+/// Parsea una clase entre corchetes (`[a-z_]`, `[^"\\]`) con la sintaxis de las
+/// expresiones regulares; la usa también `CharClass::parse` para los .aut
+pub(super) fn parse_bracket_class(class: &str) -> Result<CharClass, String> {
+    let mut parser = RegexParser {
+        chars: class.chars().collect(),
+        pos: 0,
+    };
+
+    if parser.next() != Some('[') {
+        return Err(parser.error("Se esperaba '['".to_string()));
+    }
+
+    match parser.parse_class()? {
+        Regex::Set(ranges) if parser.peek().is_none() => Ok(to_char_class(&ranges)),
+        _ => Err(parser.error("Texto después de ']'".to_string())),
+    }
+}
+
+// ============================================================================
+// NFA (CONSTRUCCIÓN DE THOMPSON)
+// ============================================================================
+
+#[derive(Default)]
+struct NfaState {
+    epsilon: Vec<usize>,
+    edges: Vec<(Ranges, usize)>,
+}
+
+#[derive(Default)]
+struct Nfa {
+    states: Vec<NfaState>,
+
+    /// Estado de aceptación → índice de la regla (menor índice = mayor prioridad)
+    accepting: HashMap<usize, usize>,
+}
+
+impl Nfa {
+    fn add_state(&mut self) -> usize {
+        self.states.push(NfaState::default());
+        self.states.len() - 1
+    }
+
+    /// Construye el fragmento de una expresión y devuelve (inicio, fin)
+    fn build(&mut self, regex: &Regex) -> (usize, usize) {
+        match regex {
+            Regex::Set(ranges) => {
+                let start = self.add_state();
+                let end = self.add_state();
+                self.states[start].edges.push((ranges.clone(), end));
+                (start, end)
+            }
+            Regex::Concat(items) => {
+                let (start, mut end) = self.build(&items[0]);
+                for item in &items[1..] {
+                    let (s, e) = self.build(item);
+                    self.states[end].epsilon.push(s);
+                    end = e;
+                }
+                (start, end)
+            }
+            Regex::Alt(branches) => {
+                let start = self.add_state();
+                let end = self.add_state();
+                for branch in branches {
+                    let (s, e) = self.build(branch);
+                    self.states[start].epsilon.push(s);
+                    self.states[e].epsilon.push(end);
+                }
+                (start, end)
+            }
+            Regex::Star(inner) | Regex::Plus(inner) | Regex::Optional(inner) => {
+                let start = self.add_state();
+                let end = self.add_state();
+                let (s, e) = self.build(inner);
+                self.states[start].epsilon.push(s);
+                self.states[e].epsilon.push(end);
+                if !matches!(regex, Regex::Plus(_)) {
+                    self.states[start].epsilon.push(end);
+                }
+                if !matches!(regex, Regex::Optional(_)) {
+                    self.states[e].epsilon.push(s);
+                }
+                (start, end)
+            }
+        }
+    }
+
+    /// Cerradura-ε de un conjunto de estados
+    fn closure(&self, states: impl IntoIterator<Item = usize>) -> BTreeSet<usize> {
+        let mut result = BTreeSet::new();
+        let mut stack: Vec<usize> = states.into_iter().collect();
+        while let Some(state) = stack.pop() {
+            if result.insert(state) {
+                stack.extend(&self.states[state].epsilon);
+            }
+        }
+        result
+    }
+
+    /// Intervalos elementales: partición del alfabeto en la que cada arista
+    /// cubre cada intervalo completo o no lo toca
+    fn alphabet(&self) -> Ranges {
+        let mut bounds = BTreeSet::new();
+        for state in &self.states {
+            for (ranges, _) in &state.edges {
+                for &(start, end) in ranges {
+                    bounds.insert(start);
+                    bounds.insert(end + 1);
+                }
+            }
+        }
+
+        let bounds: Vec<u32> = bounds.into_iter().collect();
+        bounds.windows(2).map(|w| (w[0], w[1] - 1)).collect()
+    }
+}
+
+// ============================================================================
+// DFA (SUBCONJUNTOS + MINIMIZACIÓN)
+// ============================================================================
+
+struct Dfa {
+    /// Transiciones por intervalo elemental del alfabeto
+    transitions: Vec<Vec<Option<usize>>>,
+
+    /// Regla aceptada por cada estado (la de mayor prioridad)
+    accepting: Vec<Option<usize>>,
+}
+
+impl Dfa {
+    /// Construcción de subconjuntos; el estado 0 es el inicial
+    fn from_nfa(nfa: &Nfa, start: usize, alphabet: &Ranges) -> Self {
+        let mut sets = vec![nfa.closure([start])];
+        let mut index: HashMap<BTreeSet<usize>, usize> = HashMap::from([(sets[0].clone(), 0)]);
+        let mut transitions = Vec::new();
+
+        let mut current = 0;
+        while current < sets.len() {
+            let mut row = Vec::with_capacity(alphabet.len());
+            for &(symbol, _) in alphabet {
+                let moved = sets[current].iter().flat_map(|&state| {
+                    nfa.states[state].edges.iter().filter_map(move |(ranges, to)| {
+                        // Los rangos están ordenados: búsqueda binaria
+                        let idx = ranges.partition_point(|&(_, e)| e < symbol);
+                        ranges.get(idx).is_some_and(|&(s, _)| s <= symbol).then_some(*to)
+                    })
+                });
+                let target = nfa.closure(moved);
+
+                if target.is_empty() {
+                    row.push(None);
+                    continue;
+                }
+
+                let id = *index.entry(target.clone()).or_insert_with(|| {
+                    sets.push(target);
+                    sets.len() - 1
+                });
+                row.push(Some(id));
+            }
+            transitions.push(row);
+            current += 1;
+        }
+
+        let accepting = sets
+            .iter()
+            .map(|set| set.iter().filter_map(|s| nfa.accepting.get(s).copied()).min())
+            .collect();
+
+        Dfa { transitions, accepting }
+    }
+
+    /// Minimización por refinamiento de particiones (Moore)
+    ///
+    /// Los estados parten agrupados por tipo de token aceptado; el estado 0
+    /// sigue siendo el inicial.
+    fn minimize(&self, token_types: &[TokenType]) -> Self {
+        let mut block: Vec<usize> = {
+            let mut ids: HashMap<Option<&TokenType>, usize> = HashMap::new();
+            self.accepting
+                .iter()
+                .map(|rule| {
+                    let next = ids.len();
+                    *ids.entry(rule.map(|r| &token_types[r])).or_insert(next)
+                })
+                .collect()
+        };
+        let mut count = block.iter().max().map_or(0, |b| b + 1);
+
+        loop {
+            let mut ids: HashMap<(usize, Vec<Option<usize>>), usize> = HashMap::new();
+            let refined: Vec<usize> = (0..self.transitions.len())
+                .map(|state| {
+                    let signature = self.transitions[state]
+                        .iter()
+                        .map(|t| t.map(|target| block[target]))
+                        .collect();
+                    let next = ids.len();
+                    *ids.entry((block[state], signature)).or_insert(next)
+                })
+                .collect();
+
+            let refined_count = ids.len();
+            block = refined;
+            if refined_count == count {
+                break;
+            }
+            count = refined_count;
+        }
+
+        let mut transitions = vec![Vec::new(); count];
+        let mut accepting: Vec<Option<usize>> = vec![None; count];
+        for (state, &b) in block.iter().enumerate() {
+            if transitions[b].is_empty() {
+                transitions[b] = self.transitions[state]
+                    .iter()
+                    .map(|t| t.map(|target| block[target]))
+                    .collect();
+            }
+            accepting[b] = match (accepting[b], self.accepting[state]) {
+                (Some(a), Some(r)) => Some(a.min(r)),
+                (a, r) => a.or(r),
+            };
+        }
+
+        Dfa { transitions, accepting }
+    }
+}
+
+// ============================================================================
+// ARCHIVO .spec
+// ============================================================================
+
+/// Regla de token: `NOMBRE = regex`
+struct TokenRule {
+    token_type: TokenType,
+    regex: Regex,
+    line: usize,
+}
+
+/// Indica si una ruta corresponde a una especificación de tokens (.spec)
+pub fn is_spec_path(path: &str) -> bool {
+    path.ends_with(".spec")
+}
+
+impl Automaton {
+    /// Carga un autómata desde un archivo de especificación de tokens
+    pub fn from_spec_file(path: &str) -> Result<Self, AutomatonError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| AutomatonError::new(format!("Error leyendo archivo {}: {}", path, e)))?;
+
+        Self::from_spec(&content)
+    }
+
+    /// Compila una especificación de tokens (secciones METADATA, TOKENS y KEYWORDS)
+    ///
+    /// Cuando dos reglas reconocen el mismo lexema gana la que aparece primero.
+    pub fn from_spec(content: &str) -> Result<Self, AutomatonError> {
+        let mut rules = Vec::new();
+        let mut dialects = vec![Dialect::new(DEFAULT_DIALECT)];
+        let mut current_dialect = 0;
+        let mut block_comment = None;
+        let mut nested_comments = false;
+        let mut current_section = "";
+
+        for (line_num, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim();
+            let at_line = |message: String| AutomatonError::at_line(message, line_num + 1, raw_line);
+
+            // Ignorar comentarios y líneas vacías
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line == "METADATA" || line == "TOKENS" {
+                current_section = line;
+                continue;
+            }
+
+            // `KEYWORDS` o `KEYWORDS <dialecto>`, igual que en .aut
+            if let Some(name) = keywords_header(line) {
+                current_dialect = dialect_slot(&mut dialects, name).map_err(at_line)?;
+                current_section = "KEYWORDS";
+                continue;
+            }
+
+            if line.starts_with("END_") {
+                current_section = "";
+                continue;
+            }
+
+            match current_section {
+                "METADATA" => {
+                    // Mismas claves de comentarios de bloque que en .aut
+                    if let Some(value) = line.strip_prefix("block_comment:") {
+                        block_comment = Some(Self::parse_block_comment(value).map_err(at_line)?);
+                    } else if let Some(value) = line.strip_prefix("nested_comments:") {
+                        nested_comments = Self::parse_flag(value).map_err(at_line)?;
+                    }
+                }
+
+                "TOKENS" => {
+                    // Formato: NOMBRE = regex
+                    let (name, pattern) = line.split_once('=').ok_or_else(|| {
+                        at_line(format!("Regla mal formada (se esperaba 'NOMBRE = regex'): {}", line))
+                    })?;
+                    let token_type = Self::parse_token_type(name.trim()).map_err(at_line)?;
+                    let regex = RegexParser::parse(pattern.trim()).map_err(at_line)?;
+                    rules.push(TokenRule {
+                        token_type,
+                        regex,
+                        line: line_num + 1,
+                    });
+                }
+
+                "KEYWORDS" => {
+                    let content = line.split('#').next().unwrap_or("").trim();
+                    let (lexeme, token_type) = Self::parse_keyword(content).map_err(at_line)?;
+                    dialects[current_dialect].add(KeywordEntry {
+                        lexeme,
+                        token_type,
+                        line: line_num + 1,
+                    });
+                }
+
+                _ => {
+                    return Err(at_line(format!("Línea fuera de una sección: {}", line)));
+                }
+            }
+        }
+
+        if rules.is_empty() {
+            return Err(AutomatonError::new("La especificación no define ningún token".to_string()));
+        }
+
+        // Un NFA con un inicio común que enlaza todas las reglas
+        let mut nfa = Nfa::default();
+        let start = nfa.add_state();
+        for (index, rule) in rules.iter().enumerate() {
+            let (s, e) = nfa.build(&rule.regex);
+            nfa.states[start].epsilon.push(s);
+            nfa.accepting.insert(e, index);
+        }
+
+        let alphabet = nfa.alphabet();
+        let token_types: Vec<TokenType> = rules.iter().map(|r| r.token_type.clone()).collect();
+        let dfa = Dfa::from_nfa(&nfa, start, &alphabet).minimize(&token_types);
+
+        if let Some(rule) = dfa.accepting[0] {
+            let line = rules[rule].line;
+            let raw_line = content.lines().nth(line - 1).unwrap_or("");
+            return Err(AutomatonError::at_line(
+                "La regla reconoce la cadena vacía".to_string(),
+                line,
+                raw_line,
+            ));
+        }
+
+        let mut automaton = Self::from_dfa(&dfa, &alphabet, &rules, dialects);
+        automaton.block_comment = block_comment.map(|bc| BlockComment { nested: nested_comments, ..bc });
+        Ok(automaton)
+    }
+
+    /// Convierte el DFA mínimo en un Automaton con estados `d0`, `d1`, ...
+    fn from_dfa(
+        dfa: &Dfa,
+        alphabet: &Ranges,
+        rules: &[TokenRule],
+        dialects: Vec<Dialect>,
+    ) -> Self {
+        let mut transitions = Vec::new();
+        let mut final_states = HashMap::new();
+        let mut state_map = HashMap::new();
+        let mut state_lines = HashMap::new();
+
+        for (state, row) in dfa.transitions.iter().enumerate() {
+            let from = StateId::new(state);
+            state_map.insert(format!("d{}", state), from);
+
+            if let Some(rule) = dfa.accepting[state] {
+                final_states.insert(from, rules[rule].token_type.clone());
+                state_lines.insert(from, rules[rule].line);
+            }
+
+            // Una transición por destino, con los intervalos fusionados
+            let mut by_target: BTreeMap<usize, Ranges> = BTreeMap::new();
+            for (interval, target) in alphabet.iter().zip(row) {
+                if let Some(target) = target {
+                    by_target.entry(*target).or_default().push(*interval);
+                }
+            }
+
+            for (target, ranges) in by_target {
+                let to = StateId::new(target);
+                transitions.push(Transition {
+                    from,
+                    char_class: to_char_class(&normalize(ranges)),
+                    to,
+                    line: state_lines.get(&to).copied().unwrap_or(0),
+                });
+            }
+        }
+
+        Automaton {
+            initial_state: StateId::new(0),
+            transitions,
+            final_states,
+            dialects,
+            state_map,
+            state_lines,
+            error_states: Vec::new(),
+            block_comment: None,
+            dense: OnceCell::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer_new::Scanner;
+
+    fn tokens(automaton: &Automaton, source: &str) -> Vec<(TokenType, String)> {
+        Scanner::new(source, automaton)
+            .scan_all()
+            .unwrap()
+            .into_iter()
+            .filter(|t| t.token_type != TokenType::Eof)
+            .map(|t| (t.token_type, t.lexeme))
+            .collect()
+    }
+
+    fn compile(spec: &str) -> Automaton {
+        Automaton::from_spec(spec).unwrap()
+    }
+
+    #[test]
+    fn test_regex_parser() {
+        assert!(RegexParser::parse("[a-zA-Z_][a-zA-Z0-9_]*").is_ok());
+        assert!(RegexParser::parse("\"([^\"\\\\]|\\\\.)*\"").is_ok());
+        assert!(RegexParser::parse("(ab").is_err());
+        assert!(RegexParser::parse("*a").is_err());
+        assert!(RegexParser::parse("[z-a]").is_err());
+        assert!(RegexParser::parse("a||b").is_err());
+    }
+
+    #[test]
+    fn test_class_ranges() {
+        let Regex::Set(ranges) = RegexParser::parse("[^a-c]").unwrap() else {
+            panic!("se esperaba un conjunto");
+        };
+        assert_eq!(ranges, vec![(0, 'a' as u32 - 1), ('d' as u32, MAX_CODE)]);
+    }
+
+    #[test]
+    fn test_priority_and_longest_match() {
+        let automaton = compile(
+            r#"
+TOKENS
+LESS_EQUAL = <=
+NOT_EQUAL = <>
+LESS = <
+IDENTIFIER = [a-z]+
+NUMBER = [0-9]+
+WHITESPACE = [ ]+
+END_TOKENS
+"#,
+        );
+
+        assert_eq!(
+            tokens(&automaton, "a <= 12 <> <"),
+            vec![
+                (TokenType::Identifier, "a".to_string()),
+                (TokenType::LessEqual, "<=".to_string()),
+                (TokenType::Number, "12".to_string()),
+                (TokenType::NotEqual, "<>".to_string()),
+                (TokenType::Less, "<".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_minimization() {
+        // (a|b)*abb tiene un DFA mínimo de 4 estados
+        let automaton = compile("TOKENS\nIDENTIFIER = (a|b)*abb\nEND_TOKENS\n");
+        assert_eq!(automaton.state_map.len(), 4);
+    }
+
+    #[test]
+    fn test_strings_and_comments() {
+        let automaton = compile(
+            r#"
+TOKENS
+STRING = "([^"\\]|\\.)*"
+COMMENT = //.*
+WHITESPACE = [ \t\r\n]+
+END_TOKENS
+"#,
+        );
+
+        assert_eq!(
+            tokens(&automaton, "\"a\\\"b\" // fin\n\"c\""),
+            vec![
+                (TokenType::String, "\"a\\\"b\"".to_string()),
+                (TokenType::String, "\"c\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keywords_section() {
+        let automaton = compile(
+            "TOKENS\nIDENTIFIER = [a-z]+\nEND_TOKENS\nKEYWORDS\nsi, SI\nEND_KEYWORDS\n",
+        );
+        assert_eq!(automaton.classify_identifier("si"), TokenType::Si);
+        assert_eq!(tokens(&automaton, "si")[0].0, TokenType::Si);
+    }
+
+    #[test]
+    fn test_spec_errors_report_line() {
+        let err = Automaton::from_spec("TOKENS\nNUMBER = [0-9]*\nEND_TOKENS\n").err().unwrap();
+        assert_eq!(err.line, 2);
+
+        let err = Automaton::from_spec("TOKENS\nBOGUS = a\nEND_TOKENS\n").err().unwrap();
+        assert_eq!(err.line, 2);
+
+        let err = Automaton::from_spec("TOKENS\nNUMBER = (0\nEND_TOKENS\n").err().unwrap();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_default_spec_matches_automaton() {
+        let from_spec = compile(include_str!("../../config/tokens.spec"));
+        let from_aut = Automaton::parse(include_str!("../../config/automaton.aut")).unwrap();
+
+        let source = include_str!("../../ejemplo1.net");
+        assert_eq!(tokens(&from_spec, source), tokens(&from_aut, source));
+        assert!(from_spec.validate().is_empty());
+
+        let unicode = "máquinaRecepción año2 módulo ñandú";
+        assert_eq!(tokens(&from_spec, unicode), tokens(&from_aut, unicode));
+    }
+
+    #[test]
+    fn test_unicode_properties() {
+        let automaton = compile(
+            "TOKENS\nIDENTIFIER = \\p{L}[\\p{L}\\p{N}]*\nWHITESPACE = [ ]+\nEND_TOKENS\n",
+        );
+        assert_eq!(
+            tokens(&automaton, "año2 Ñu"),
+            vec![
+                (TokenType::Identifier, "año2".to_string()),
+                (TokenType::Identifier, "Ñu".to_string()),
+            ]
+        );
+        assert!(RegexParser::parse("\\p{X}").is_err());
+    }
+}
@@ -13,11 +13,19 @@ pub enum TokenType {
     Concentradores,
     Coaxial,
     Segmento,
+    Enteros,
     Modulo,
     Inicio,
     Fin,
     Si,
     Sino,
+    Mientras,
+    Para,
+    Hasta,
+    Maquina,
+    Concentrador,
+    Entero,
+    Incluye,
     
     // ============ Funciones del Lenguaje ============
     Coloca,
@@ -48,6 +56,13 @@ pub enum TokenType {
     Or,             // ||
     Not,            // !
     
+    // ============ Operadores Aritméticos ============
+    Plus,           // +
+    Minus,          // -
+    Star,           // *
+    Slash,          // /
+    Percent,        // %
+    
     // ============ Delimitadores ============
     Comma,          // ,
     Semicolon,      // ;
@@ -64,7 +79,8 @@ pub enum TokenType {
     
     // ============ Especiales ============
     Whitespace,     // Espacios, tabs, newlines (ignorado)
-    Comment,        // // ... (ignorado)
+    Comment,        // // ... y /* ... */ (ignorado)
+    DocComment,     // /// ... (se adjunta a la siguiente declaración)
     Eof,            // Fin de archivo
 }
 
@@ -78,11 +94,19 @@ impl TokenType {
             TokenType::Concentradores => "concentradores",
             TokenType::Coaxial => "coaxial",
             TokenType::Segmento => "segmento",
+            TokenType::Enteros => "enteros",
             TokenType::Modulo => "modulo",
             TokenType::Inicio => "inicio",
             TokenType::Fin => "fin",
             TokenType::Si => "si",
             TokenType::Sino => "sino",
+            TokenType::Mientras => "mientras",
+            TokenType::Para => "para",
+            TokenType::Hasta => "hasta",
+            TokenType::Maquina => "maquina",
+            TokenType::Concentrador => "concentrador",
+            TokenType::Entero => "entero",
+            TokenType::Incluye => "incluye",
             
             TokenType::Coloca => "coloca",
             TokenType::ColocaCoaxial => "colocaCoaxial",
@@ -109,6 +133,12 @@ impl TokenType {
             TokenType::Or => "||",
             TokenType::Not => "!",
             
+            TokenType::Plus => "+",
+            TokenType::Minus => "-",
+            TokenType::Star => "*",
+            TokenType::Slash => "/",
+            TokenType::Percent => "%",
+            
             TokenType::Comma => ",",
             TokenType::Semicolon => ";",
             TokenType::Dot => ".",
@@ -123,6 +153,7 @@ impl TokenType {
             
             TokenType::Whitespace => "espacio",
             TokenType::Comment => "comentario",
+            TokenType::DocComment => "comentario de documentación",
             TokenType::Eof => "fin de archivo",
         }
     }
@@ -139,6 +170,67 @@ impl fmt::Display for TokenType {
     }
 }
 
+/// Ubicación de un token en el código fuente
+///
+/// `start..end` es el rango en bytes, de modo que `&source[span.start..span.end]`
+/// devuelve el lexema; línea y columna (1-indexed) marcan inicio y fin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    /// Desplazamiento en bytes del primer carácter
+    pub start: usize,
+
+    /// Desplazamiento en bytes tras el último carácter
+    pub end: usize,
+
+    /// Línea y columna del inicio
+    pub line: usize,
+    pub column: usize,
+
+    /// Línea y columna justo después del final
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// Calcula el span de `text` empezando en la posición dada
+    pub fn of(text: &str, start: usize, line: usize, column: usize) -> Self {
+        let mut end_line = line;
+        let mut end_column = column;
+        for ch in text.chars() {
+            if ch == '\n' {
+                end_line += 1;
+                end_column = 1;
+            } else if !is_combining_mark(ch) {
+                end_column += 1;
+            }
+        }
+
+        Self {
+            start,
+            end: start + text.len(),
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// Longitud en bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Verifica si el span no cubre ningún carácter
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Texto del código fuente cubierto por el span
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start..self.end]
+    }
+}
+
 /// Información completa de un token
 #[derive(Debug, Clone)]
 pub struct Token {
@@ -156,6 +248,30 @@ pub struct Token {
     
     /// Longitud del lexema
     pub length: usize,
+
+    /// Rango en bytes y posiciones de inicio y fin
+    pub span: Span,
+
+    /// Comentarios `///` que preceden al token (una línea por comentario)
+    pub doc: Option<String>,
+}
+
+/// Indica si un carácter es una marca diacrítica combinante (p. ej. la tilde
+/// de `o\u{301}`), que se dibuja sobre el carácter anterior
+pub fn is_combining_mark(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{300}'..='\u{36F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// Número de caracteres visibles de un texto (columnas que ocupa)
+pub fn display_width(text: &str) -> usize {
+    text.chars().filter(|c| !is_combining_mark(*c)).count()
 }
 
 impl Token {
@@ -166,13 +282,16 @@ impl Token {
         line: usize,
         column: usize,
     ) -> Self {
-        let length = lexeme.len();
+        let length = display_width(&lexeme);
+        let span = Span::of(&lexeme, 0, line, column);
         Self {
             token_type,
             lexeme,
             line,
             column,
             length,
+            span,
+            doc: None,
         }
     }
     
@@ -184,8 +303,23 @@ impl Token {
             line,
             column,
             length: 0,
+            span: Span::of("", 0, line, column),
+            doc: None,
         }
     }
+
+    /// Ubica el token en el byte `offset` del código fuente
+    pub fn at_offset(mut self, offset: usize) -> Self {
+        let len = self.span.len();
+        self.span.start = offset;
+        self.span.end = offset + len;
+        self
+    }
+
+    /// Verifica si el token es trivia (espacios o comentarios)
+    pub fn is_trivia(&self) -> bool {
+        self.token_type.should_ignore()
+    }
 }
 
 impl fmt::Display for Token {
@@ -218,6 +352,15 @@ mod tests {
         assert_eq!(token.length, 4);
     }
     
+    #[test]
+    fn test_token_span() {
+        let token = Token::new(TokenType::Comment, "/* a\n ñ */".to_string(), 2, 3).at_offset(10);
+
+        assert_eq!((token.span.start, token.span.end), (10, 21));
+        assert_eq!((token.span.line, token.span.column), (2, 3));
+        assert_eq!((token.span.end_line, token.span.end_column), (3, 6));
+    }
+
     #[test]
     fn test_eof_token() {
         let token = Token::eof(10, 20);
@@ -232,6 +375,7 @@ mod tests {
     fn test_should_ignore() {
         assert!(TokenType::Whitespace.should_ignore());
         assert!(TokenType::Comment.should_ignore());
+        assert!(!TokenType::DocComment.should_ignore());
         assert!(!TokenType::Identifier.should_ignore());
         assert!(!TokenType::Number.should_ignore());
     }
@@ -0,0 +1,388 @@
+// src/lexer_new/tokenize.rs
+// Flujo de tokens para el parser: sin trivia y con la documentación adjunta
+
+use super::automaton::{Automaton, Dialect};
+use super::byte_scanner::ByteScanner;
+use super::error::LexicalError;
+use super::scanner::Scanner;
+use super::token::{Token, TokenType};
+use crate::config::{has_override, load_automaton};
+use crate::error::{Diagnostic, report_errors};
+
+/// Tokeniza el código fuente para el parser
+///
+/// Usa el autómata activo de `config::load_automaton` (el incrustado salvo que
+/// se haya instalado otro con `config::set_automaton`). El escaneo se recupera
+/// de caracteres inválidos, así que se devuelven todos los errores léxicos a la
+/// vez. Espacios y comentarios se descartan; los comentarios `///` se adjuntan
+/// al siguiente token en `Token::doc`. La lista termina siempre en EOF.
+///
+/// Las palabras reservadas se reconocen en el dialecto del pragma de cabecera
+/// (`// @dialecto english`) o, sin pragma, en el predeterminado.
+pub fn tokenize(source: &str) -> Result<Vec<Token>, Vec<LexicalError>> {
+    tokenize_with_dialect(source, None)
+}
+
+/// Como `tokenize`, pero con un dialecto explícito que tiene prioridad sobre
+/// el pragma de cabecera (p. ej. el de `--dialect`)
+///
+/// Con el autómata incrustado se usa el DFA generado por build.rs, que da los
+/// mismos tokens sin interpretar tablas; con `--automaton`, el autómata
+/// instalado.
+pub fn tokenize_with_dialect(
+    source: &str,
+    dialect: Option<&str>,
+) -> Result<Vec<Token>, Vec<LexicalError>> {
+    if has_override() {
+        return tokenize_with_automaton(source, load_automaton(), dialect);
+    }
+
+    let mut scanner = ByteScanner::generated(source);
+    resolve_dialect(source, dialect, |name| scanner.set_dialect(name)).map_err(|err| vec![err])?;
+    let (tokens, errors) = scanner.scan_all_recovering();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(attach_docs(tokens.iter().map(|token| token.to_token())))
+}
+
+/// Como `tokenize_with_dialect`, pero con un autómata cualquiera en lugar del
+/// activo (p. ej. uno de pruebas junto al de producción)
+pub fn tokenize_with_automaton(
+    source: &str,
+    automaton: &Automaton,
+    dialect: Option<&str>,
+) -> Result<Vec<Token>, Vec<LexicalError>> {
+    let dialect = select_dialect(automaton, source, dialect).map_err(|err| vec![err])?;
+
+    let mut scanner = Scanner::new(source, automaton);
+    scanner
+        .set_dialect(dialect.name())
+        .expect("select_dialect devuelve un dialecto del mismo autómata");
+    let (tokens, errors) = scanner.scan_all_recovering();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(attach_docs(tokens))
+}
+
+/// Descarta los comentarios `///` y adjunta su texto al siguiente token
+fn attach_docs(tokens: impl IntoIterator<Item = Token>) -> Vec<Token> {
+    let mut result = Vec::new();
+    let mut pending_doc: Vec<String> = Vec::new();
+
+    for mut token in tokens {
+        // Los comentarios de documentación describen la declaración siguiente
+        if token.token_type == TokenType::DocComment {
+            pending_doc.push(doc_text(&token.lexeme));
+            continue;
+        }
+
+        if !pending_doc.is_empty() {
+            token.doc = Some(std::mem::take(&mut pending_doc).join("\n"));
+        }
+        result.push(token);
+    }
+
+    result
+}
+
+/// Dialecto de `automaton` para `source`: el pedido explícitamente, si no el
+/// del pragma de cabecera, si no el predeterminado
+///
+/// Un pragma con un dialecto desconocido se reporta en su posición; un
+/// dialecto explícito desconocido, al inicio del archivo.
+pub fn select_dialect<'a>(
+    automaton: &'a Automaton,
+    source: &str,
+    requested: Option<&str>,
+) -> Result<&'a Dialect, LexicalError> {
+    resolve_dialect(source, requested, |name| automaton.dialect(name))
+        .map(|dialect| dialect.unwrap_or_else(|| automaton.default_dialect()))
+}
+
+/// Busca con `lookup` el dialecto pedido o el del pragma (`None` si no hay
+/// ninguno) y sitúa el error de un nombre desconocido
+fn resolve_dialect<T>(
+    source: &str,
+    requested: Option<&str>,
+    mut lookup: impl FnMut(&str) -> Result<T, String>,
+) -> Result<Option<T>, LexicalError> {
+    if let Some(name) = requested {
+        return lookup(name)
+            .map(Some)
+            .map_err(|message| LexicalError::new(message, 1, 1, 1));
+    }
+
+    match dialect_pragma(source) {
+        Some((name, line, column)) => lookup(name)
+            .map(Some)
+            .map_err(|message| LexicalError::new(message, line, column, name.chars().count())),
+        None => Ok(None),
+    }
+}
+
+/// Busca el pragma `// @dialecto <nombre>` (o `// @dialect <nombre>`) en la
+/// cabecera: las líneas en blanco o de comentario antes de `programa`
+///
+/// Devuelve el nombre con su línea y columna. Va antes de la primera palabra
+/// reservada porque de él depende cómo se escribe `programa`. Solo cuenta un
+/// comentario `//` que abre la línea; lo que haya dentro de un `/* ... */` no
+/// es pragma.
+pub fn dialect_pragma(source: &str) -> Option<(&str, usize, usize)> {
+    let mut in_block = false;
+
+    for (index, line) in source.lines().enumerate() {
+        let mut rest = line.trim_start();
+
+        // Salta los comentarios de bloque que ocupan el principio de la línea
+        loop {
+            if in_block {
+                match rest.find("*/") {
+                    Some(end) => {
+                        in_block = false;
+                        rest = rest[end + 2..].trim_start();
+                    }
+                    None => rest = "",
+                }
+            }
+            match rest.strip_prefix("/*") {
+                Some(inner) => {
+                    in_block = true;
+                    rest = inner;
+                }
+                None => break,
+            }
+        }
+
+        if rest.is_empty() {
+            continue;
+        }
+
+        // La primera línea con código cierra la cabecera
+        let Some(comment) = rest.strip_prefix("//") else {
+            break;
+        };
+
+        let text = comment.trim_start_matches('/').trim_start();
+        let name_rest = text
+            .strip_prefix("@dialecto")
+            .or_else(|| text.strip_prefix("@dialect"))
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map(str::trim_start);
+
+        if let Some(name) = name_rest.and_then(|rest| rest.split_whitespace().next()) {
+            // `name_rest` es un sufijo de la línea: su inicio da la columna del nombre
+            let start = line.len() - name_rest.map_or(0, str::len);
+            return Some((name, index + 1, line[..start].chars().count() + 1));
+        }
+    }
+
+    None
+}
+
+/// Texto de un comentario `///` sin las barras ni el primer espacio
+fn doc_text(lexeme: &str) -> String {
+    let text = lexeme.trim_start_matches('/');
+    text.strip_prefix(' ').unwrap_or(text).trim_end().to_string()
+}
+
+/// Errores léxicos como diagnósticos
+pub fn lexical_diagnostics(errors: &[LexicalError]) -> Vec<Diagnostic> {
+    errors.iter().map(|err| {
+        Diagnostic::lexical_error(err.line, err.column, err.length, err.message.clone())
+    }).collect()
+}
+
+/// Muestra los errores léxicos como diagnósticos
+pub fn report_lexical_errors(errors: &[LexicalError], source: &str, filename: &str) {
+    report_errors(&lexical_diagnostics(errors), source, filename);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_reports_every_bad_character() {
+        let errors = tokenize("programa a@ b$;\n@").unwrap_err();
+
+        let positions: Vec<(usize, usize)> = errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(positions, vec![(1, 11), (1, 14), (2, 1)]);
+    }
+
+    #[test]
+    fn test_doc_comments_attach_to_next_token() {
+        let source = "programa p;\n/// Servidor\n///  principal\n// nota\ndefine maquinas a;";
+        let tokens = tokenize(source).unwrap();
+
+        assert_eq!(tokens[3].token_type, TokenType::Define);
+        assert_eq!(tokens[3].doc.as_deref(), Some("Servidor\n principal"));
+        assert!(tokens[4].doc.is_none());
+    }
+
+    #[test]
+    fn test_block_comments_are_skipped() {
+        let tokens = tokenize("programa /* uno /* anidado */ dos */ p;").unwrap();
+        assert_eq!(tokens.len(), 4); // programa p ; EOF
+
+        let errors = tokenize("programa p;\n  /* abierto").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column, errors[0].length), (2, 3, 2));
+    }
+
+    #[test]
+    fn test_arithmetic_operators_and_comments() {
+        let source = "a.x+30 - -1*b/2%c // fin\n/// doc\nd";
+        let types: Vec<TokenType> = tokenize(source).unwrap().into_iter().map(|t| t.token_type).collect();
+
+        use TokenType::*;
+        assert_eq!(
+            types,
+            vec![
+                Identifier, Dot, Identifier, Plus, Number, Minus, Minus, Number, Star,
+                Identifier, Slash, Number, Percent, Identifier, Identifier, Eof,
+            ]
+        );
+
+        // La especificación .spec equivalente reconoce los mismos tokens
+        let spec = Automaton::from_spec(include_str!("../../config/tokens.spec")).unwrap();
+        let from_spec: Vec<TokenType> = tokenize_with_automaton(source, &spec, None)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.token_type)
+            .collect();
+        assert_eq!(from_spec, types);
+    }
+
+    #[test]
+    fn test_string_literal_ends_at_closing_quote() {
+        let source = "incluye \"comun.net\"; escribe(\"a\\\"b\");";
+        let tokens = tokenize(source).unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::Incluye);
+        assert_eq!((tokens[1].token_type.clone(), tokens[1].lexeme.as_str()), (TokenType::String, "\"comun.net\""));
+        assert_eq!(tokens[2].token_type, TokenType::Semicolon);
+        assert_eq!(tokens[5].lexeme, "\"a\\\"b\"");
+    }
+
+    #[test]
+    fn test_tokenize_valid_source() {
+        let source = "programa a;";
+        let tokens = tokenize(source).unwrap();
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[1].lexeme, "a");
+        assert_eq!(tokens[1].span.text(source), "a");
+        assert_eq!(tokens[3].token_type, TokenType::Eof);
+    }
+
+    #[test]
+    fn test_dialect_pragma() {
+        assert_eq!(
+            dialect_pragma("// Red de prueba\n//  @dialecto english\nprogram p;"),
+            Some(("english", 2, 15))
+        );
+        assert_eq!(
+            dialect_pragma("\n  /* cabecera */ // @dialect english\nprogram p;"),
+            Some(("english", 2, 30))
+        );
+
+        // Después de la línea de `programa` ya no es cabecera
+        assert_eq!(dialect_pragma("program p; // @dialect english"), None);
+        assert_eq!(dialect_pragma("programa p;\n// @dialecto english"), None);
+        assert_eq!(dialect_pragma("// @dialectos english\nprograma p;"), None);
+    }
+
+    #[test]
+    fn test_dialect_pragma_inside_string() {
+        // El `//` de una cadena no abre un comentario
+        assert_eq!(dialect_pragma("x := \"// @dialecto english\";\nprograma p;"), None);
+        assert_eq!(dialect_pragma("programa p; escribir(\"// @dialect english\");"), None);
+    }
+
+    #[test]
+    fn test_dialect_pragma_inside_block_comment() {
+        let source = "/* Ejemplo:\n// @dialecto english\n*/\nprograma p;";
+        assert_eq!(dialect_pragma(source), None);
+        assert_eq!(dialect_pragma("/* // @dialecto english */\nprograma p;"), None);
+
+        // Tras cerrar el bloque la cabecera sigue
+        let source = "/* Red\n   de prueba */\n// @dialecto english\nprogram p;";
+        assert_eq!(dialect_pragma(source), Some(("english", 3, 14)));
+    }
+
+    #[test]
+    fn test_tokenize_english_dialect() {
+        let source = "// @dialecto english\nprogram p;\nbegin\n  place(a, 1, 2);\nend.";
+        let types: Vec<TokenType> = tokenize(source).unwrap().into_iter().map(|t| t.token_type).collect();
+
+        assert_eq!(types[0], TokenType::Programa);
+        assert_eq!(types[3], TokenType::Inicio);
+        assert_eq!(types[4], TokenType::Coloca);
+        assert!(types.contains(&TokenType::Fin));
+
+        // El dialecto explícito tiene prioridad sobre el pragma
+        let tokens = tokenize_with_dialect(source, Some("espanol")).unwrap();
+        assert_eq!(tokens[0].token_type, TokenType::Identifier);
+    }
+
+    #[test]
+    fn test_tokenize_with_other_automaton() {
+        let automaton = Automaton::parse(
+            "METADATA\ninitial_state: q0\nEND_METADATA\n\
+             TRANSITIONS\nq0, [a-z], q_id\nq_id, [a-z], q_id\nq0, \\s, q_ws\nEND_TRANSITIONS\n\
+             STATES\nq0\nq_id FINAL:IDENTIFIER\nq_ws FINAL:WHITESPACE\nEND_STATES\n\
+             KEYWORDS\nred, PROGRAMA\nEND_KEYWORDS\n",
+        )
+        .unwrap();
+
+        let tokens = tokenize_with_automaton("red programa", &automaton, None).unwrap();
+        let types: Vec<&TokenType> = tokens.iter().map(|t| &t.token_type).collect();
+        assert_eq!(types, vec![&TokenType::Programa, &TokenType::Identifier, &TokenType::Eof]);
+
+        // El autómata activo no cambia
+        assert_eq!(tokenize("red").unwrap()[0].token_type, TokenType::Identifier);
+    }
+
+    #[test]
+    fn test_generated_dfa_matches_embedded_automaton() {
+        let source = include_str!("../../ejemplo1.net");
+        let expected = tokenize_with_automaton(source, &crate::config::loader::AUTOMATON, None).unwrap();
+        let tokens = tokenize(source).unwrap();
+
+        let view = |tokens: &[Token]| -> Vec<(TokenType, String, Option<String>)> {
+            tokens.iter().map(|t| (t.token_type.clone(), t.lexeme.clone(), t.doc.clone())).collect()
+        };
+        assert_eq!(view(&tokens), view(&expected));
+    }
+
+    #[test]
+    fn test_unknown_dialect_pragma() {
+        let errors = tokenize("// @dialecto klingon\nprograma p;").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column, errors[0].length), (1, 14, 7));
+        assert!(errors[0].message.contains("klingon"));
+    }
+
+    #[test]
+    fn test_string_literal_stops_at_closing_quote() {
+        let tokens = tokenize("\"a\"; \"b\\\"c\"").unwrap();
+        let view: Vec<(TokenType, &str)> =
+            tokens.iter().map(|t| (t.token_type.clone(), t.lexeme.as_str())).collect();
+
+        assert_eq!(
+            view,
+            vec![
+                (TokenType::String, "\"a\""),
+                (TokenType::Semicolon, ";"),
+                (TokenType::String, "\"b\\\"c\""),
+                (TokenType::Eof, ""),
+            ]
+        );
+    }
+}
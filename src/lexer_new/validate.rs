@@ -0,0 +1,503 @@
+// src/lexer_new/validate.rs
+// Validación estática de autómatas (.aut)
+
+use super::automaton::{Automaton, Dialect, KeywordEntry, StateId, fold_keyword};
+use super::token::TokenType;
+use crate::error::{ConfigIssue, IssueKind};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Tipo de problema detectado en un autómata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationKind {
+    /// Dos transiciones del mismo estado aceptan un mismo carácter
+    Nondeterminism,
+    /// Estado al que no se llega desde el estado inicial
+    UnreachableState,
+    /// Estado desde el que no se alcanza ningún estado final
+    DeadState,
+    /// Caracteres o estados que el estado de error no cubre
+    ErrorCoverage,
+    /// Conflicto en la tabla de palabras reservadas
+    KeywordConflict,
+}
+
+impl IssueKind for ValidationKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ValidationKind::Nondeterminism => "no determinismo",
+            ValidationKind::UnreachableState => "estado inalcanzable",
+            ValidationKind::DeadState => "estado muerto",
+            ValidationKind::ErrorCoverage => "cobertura de error",
+            ValidationKind::KeywordConflict => "conflicto de palabra reservada",
+        }
+    }
+}
+
+/// Problema encontrado al validar un autómata
+pub type ValidationIssue = ConfigIssue<ValidationKind>;
+
+/// Formatea un carácter para mensajes de validación
+fn describe_char(ch: char) -> String {
+    format!("'{}'", ch.escape_debug())
+}
+
+impl Automaton {
+    /// Valida la estructura del autómata
+    ///
+    /// Detecta transiciones no deterministas, estados inalcanzables o muertos,
+    /// huecos en la cobertura del estado de error y conflictos en la tabla de
+    /// palabras reservadas. Los problemas se devuelven ordenados por línea.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        self.check_nondeterminism(&mut issues);
+        self.check_reachability(&mut issues);
+        self.check_error_coverage(&mut issues);
+        self.check_keywords(&mut issues);
+
+        issues.sort_by_key(|issue| issue.line);
+        issues
+    }
+
+    fn state_label(&self, state: StateId) -> String {
+        self.state_name(state)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("#{}", state.as_usize()))
+    }
+
+    fn state_line(&self, state: StateId) -> usize {
+        self.state_lines.get(&state).copied().unwrap_or(0)
+    }
+
+    /// Todos los estados conocidos, ordenados por línea de aparición
+    fn all_states(&self) -> Vec<StateId> {
+        let mut states: Vec<StateId> = self.state_map.values().copied().collect();
+        states.sort_by_key(|s| (self.state_line(*s), s.as_usize()));
+        states
+    }
+
+    fn check_nondeterminism(&self, issues: &mut Vec<ValidationIssue>) {
+        for (i, first) in self.transitions.iter().enumerate() {
+            for second in &self.transitions[i + 1..] {
+                if first.from != second.from || first.to == second.to {
+                    continue;
+                }
+                if let Some(ch) = first.char_class.overlap(&second.char_class) {
+                    issues.push(ValidationIssue::new(
+                        ValidationKind::Nondeterminism,
+                        second.line,
+                        format!(
+                            "desde '{}' el carácter {} lleva a '{}' (línea {}) y a '{}'; \
+                             solo se usará la primera transición",
+                            self.state_label(first.from),
+                            describe_char(ch),
+                            self.state_label(first.to),
+                            first.line,
+                            self.state_label(second.to),
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_reachability(&self, issues: &mut Vec<ValidationIssue>) {
+        // Alcanzables hacia adelante desde el estado inicial
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::from([self.initial_state]);
+        reachable.insert(self.initial_state);
+        while let Some(state) = queue.pop_front() {
+            for trans in self.transitions.iter().filter(|t| t.from == state) {
+                if reachable.insert(trans.to) {
+                    queue.push_back(trans.to);
+                }
+            }
+        }
+
+        // Estados que alcanzan algún estado final (búsqueda hacia atrás)
+        let mut productive: HashSet<StateId> = self.final_states.keys().copied().collect();
+        let mut queue: VecDeque<StateId> = productive.iter().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            for trans in self.transitions.iter().filter(|t| t.to == state) {
+                if productive.insert(trans.from) {
+                    queue.push_back(trans.from);
+                }
+            }
+        }
+
+        for state in self.all_states() {
+            // Los estados de error se reportan en la verificación de cobertura
+            if self.error_states.contains(&state) {
+                continue;
+            }
+
+            if !reachable.contains(&state) {
+                issues.push(ValidationIssue::new(
+                    ValidationKind::UnreachableState,
+                    self.state_line(state),
+                    format!(
+                        "el estado '{}' no es alcanzable desde el estado inicial '{}'",
+                        self.state_label(state),
+                        self.state_label(self.initial_state),
+                    ),
+                ));
+            } else if !productive.contains(&state) {
+                issues.push(ValidationIssue::new(
+                    ValidationKind::DeadState,
+                    self.state_line(state),
+                    format!(
+                        "desde el estado '{}' no se alcanza ningún estado final",
+                        self.state_label(state),
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn check_error_coverage(&self, issues: &mut Vec<ValidationIssue>) {
+        if self.error_states.is_empty() {
+            return;
+        }
+
+        for &error_state in &self.error_states {
+            if !self.transitions.iter().any(|t| t.to == error_state) {
+                issues.push(ValidationIssue::new(
+                    ValidationKind::ErrorCoverage,
+                    self.state_line(error_state),
+                    format!(
+                        "el estado de error '{}' no tiene transiciones de entrada",
+                        self.state_label(error_state),
+                    ),
+                ));
+            }
+        }
+
+        // Caracteres ASCII visibles y espacios que el estado inicial no acepta:
+        // con un estado de error declarado deberían llegar a él
+        let uncovered: Vec<char> = (' '..='~')
+            .chain(['\t', '\n', '\r'])
+            .filter(|ch| self.next_state(self.initial_state, *ch).is_none())
+            .collect();
+
+        if !uncovered.is_empty() {
+            let listed: Vec<String> = uncovered.iter().map(|c| describe_char(*c)).collect();
+            issues.push(ValidationIssue::new(
+                ValidationKind::ErrorCoverage,
+                self.state_line(self.initial_state),
+                format!(
+                    "el estado inicial '{}' no tiene transición para {} carácter(es) \
+                     que deberían llevar al estado de error: {}",
+                    self.state_label(self.initial_state),
+                    uncovered.len(),
+                    listed.join(" "),
+                ),
+            ));
+        }
+    }
+
+    fn check_keywords(&self, issues: &mut Vec<ValidationIssue>) {
+        for dialect in &self.dialects {
+            self.check_dialect(dialect, issues);
+        }
+
+        // Cada dialecto debe poder escribir todas las palabras del predeterminado;
+        // si no, hay programas que solo se pueden expresar en uno de ellos
+        let default = self.default_dialect();
+        for dialect in &self.dialects[1..] {
+            let mut missing: Vec<&KeywordEntry> = Vec::new();
+            for entry in default.entries() {
+                let covered = dialect.spelling(&entry.token_type).is_some()
+                    || missing.iter().any(|m| m.token_type == entry.token_type);
+                if !covered {
+                    missing.push(entry);
+                }
+            }
+
+            if !missing.is_empty() {
+                let listed: Vec<&str> = missing.iter().map(|e| e.lexeme.as_str()).collect();
+                issues.push(ValidationIssue::new(
+                    ValidationKind::KeywordConflict,
+                    dialect.entries().first().map_or(0, |e| e.line),
+                    format!(
+                        "el dialecto '{}' no traduce {} palabra(s) del dialecto '{}': {}",
+                        dialect.name(),
+                        missing.len(),
+                        default.name(),
+                        listed.join(" "),
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn check_dialect(&self, dialect: &Dialect, issues: &mut Vec<ValidationIssue>) {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        let mut seen_lower: HashMap<String, (usize, &TokenType)> = HashMap::new();
+
+        for entry in dialect.entries() {
+            // Duplicado exacto
+            if let Some(first_line) = seen.insert(&entry.lexeme, entry.line) {
+                issues.push(ValidationIssue::new(
+                    ValidationKind::KeywordConflict,
+                    entry.line,
+                    format!(
+                        "la palabra reservada '{}' ya fue definida en la línea {}",
+                        entry.lexeme, first_line,
+                    ),
+                ));
+                continue;
+            }
+
+            // classify_identifier busca primero sin mayúsculas ni acentos: dos
+            // entradas que solo difieren en eso se tapan entre sí
+            let lower = fold_keyword(&entry.lexeme);
+            match seen_lower.get(&lower) {
+                Some((first_line, first_type)) if *first_type != &entry.token_type => {
+                    issues.push(ValidationIssue::new(
+                        ValidationKind::KeywordConflict,
+                        entry.line,
+                        format!(
+                            "'{}' ({}) choca con la palabra de la línea {} ({}): \
+                             la búsqueda no distingue mayúsculas ni acentos",
+                            entry.lexeme,
+                            entry.token_type,
+                            first_line,
+                            first_type,
+                        ),
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    seen_lower.insert(lower, (entry.line, &entry.token_type));
+                }
+            }
+
+            // La palabra debe poder reconocerse como identificador
+            if !self.accepts_as_identifier(&entry.lexeme) {
+                issues.push(ValidationIssue::new(
+                    ValidationKind::KeywordConflict,
+                    entry.line,
+                    format!(
+                        "la palabra reservada '{}' nunca se reconoce: el autómata no la \
+                         acepta completa como IDENTIFIER",
+                        entry.lexeme,
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Verifica si el autómata reconoce el lexema completo como identificador
+    fn accepts_as_identifier(&self, lexeme: &str) -> bool {
+        let mut state = self.initial_state;
+        for ch in lexeme.chars() {
+            match self.next_state(state, ch) {
+                Some(next) => state = next,
+                None => return false,
+            }
+        }
+        !self.error_states.contains(&state)
+            && self.is_final(state) == Some(&TokenType::Identifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(content: &str) -> Vec<ValidationIssue> {
+        Automaton::parse(content).unwrap().validate()
+    }
+
+    fn kinds(issues: &[ValidationIssue]) -> Vec<ValidationKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn test_valid_automaton_has_no_issues() {
+        let issues = validate(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+
+STATES
+q0
+q_id FINAL:IDENTIFIER
+END_STATES
+
+TRANSITIONS
+q0, [a-z], q_id
+q_id, [a-z], q_id
+END_TRANSITIONS
+
+KEYWORDS
+si, SI
+END_KEYWORDS
+"#,
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_nondeterminism_reports_line() {
+        let issues = validate(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+STATES
+q0
+q_a FINAL:IDENTIFIER
+q_b FINAL:NUMBER
+END_STATES
+TRANSITIONS
+q0, [a-z], q_a
+q0, x, q_b
+END_TRANSITIONS
+"#,
+        );
+        assert_eq!(kinds(&issues), vec![ValidationKind::Nondeterminism]);
+        assert_eq!(issues[0].line, 12);
+        assert!(issues[0].message.contains("'x'"));
+    }
+
+    #[test]
+    fn test_unreachable_and_dead_states() {
+        let issues = validate(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+STATES
+q0
+q_id FINAL:IDENTIFIER
+q_orphan FINAL:NUMBER
+q_sink
+END_STATES
+TRANSITIONS
+q0, [a-z], q_id
+q0, &, q_sink
+END_TRANSITIONS
+"#,
+        );
+        assert_eq!(
+            kinds(&issues),
+            vec![ValidationKind::UnreachableState, ValidationKind::DeadState]
+        );
+        assert_eq!(issues[0].line, 8);
+        assert_eq!(issues[1].line, 9);
+    }
+
+    #[test]
+    fn test_error_state_coverage() {
+        let issues = validate(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+STATES
+q0
+q_id FINAL:IDENTIFIER
+q_error FINAL:ERROR
+END_STATES
+TRANSITIONS
+q0, [a-z], q_id
+END_TRANSITIONS
+"#,
+        );
+        assert!(issues.iter().all(|i| i.kind == ValidationKind::ErrorCoverage));
+        assert!(issues.iter().any(|i| i.line == 8 && i.message.contains("q_error")));
+        assert!(issues.iter().any(|i| i.message.contains("'@'")));
+    }
+
+    #[test]
+    fn test_keyword_conflicts() {
+        let issues = validate(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+STATES
+q0
+q_id FINAL:IDENTIFIER
+END_STATES
+TRANSITIONS
+q0, [a-zA-Z_], q_id
+q_id, [a-zA-Z0-9_], q_id
+END_TRANSITIONS
+KEYWORDS
+si, SI
+si, SINO
+Si, FIN
+fin_2, FIN
+END_KEYWORDS
+"#,
+        );
+        assert_eq!(kinds(&issues), vec![ValidationKind::KeywordConflict; 2]);
+        assert_eq!(issues[0].line, 15);
+        assert_eq!(issues[1].line, 16);
+    }
+
+    #[test]
+    fn test_dialects_checked_separately() {
+        let issues = validate(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+STATES
+q0
+q_id FINAL:IDENTIFIER
+END_STATES
+TRANSITIONS
+q0, [a-zA-Z_], q_id
+q_id, [a-zA-Z0-9_], q_id
+END_TRANSITIONS
+KEYWORDS
+inicio, INICIO
+fin, FIN
+END_KEYWORDS
+KEYWORDS english
+begin, INICIO
+fin, FIN
+begin, INICIO
+END_KEYWORDS
+KEYWORDS tiny
+begin, INICIO
+END_KEYWORDS
+"#,
+        );
+        // `fin` en dos dialectos no choca; el duplicado dentro de english sí,
+        // y a tiny le falta traducir `fin`
+        assert_eq!(kinds(&issues), vec![ValidationKind::KeywordConflict; 2]);
+        assert_eq!(issues[0].line, 20);
+        assert_eq!(issues[1].line, 23);
+        assert!(issues[1].message.contains("'tiny'"));
+        assert!(issues[1].message.ends_with(": fin"));
+    }
+
+    #[test]
+    fn test_keyword_not_recognized() {
+        let issues = validate(
+            r#"
+METADATA
+initial_state: q0
+END_METADATA
+STATES
+q0
+q_id FINAL:IDENTIFIER
+END_STATES
+TRANSITIONS
+q0, [a-z], q_id
+q_id, [a-z], q_id
+END_TRANSITIONS
+KEYWORDS
+colocaCoaxial, COLOCA_COAXIAL
+END_KEYWORDS
+"#,
+        );
+        assert_eq!(kinds(&issues), vec![ValidationKind::KeywordConflict]);
+        assert_eq!(issues[0].line, 14);
+    }
+}
@@ -1,17 +1,11 @@
 // src/lib.rs
 // Biblioteca principal
 
-// OLD lexer (legacy - to be removed after migration)
-pub mod lexer;
-
-// NEW lexer
+// Lexer basado en autómatas
 #[path = "lexer_new/mod.rs"]
 pub mod lexer_new;
 
-pub use lexer_new::{Automaton, Scanner, Token as NewToken, TokenType};
-
-// Bridge for converting between old and new lexer formats
-pub mod lexer_bridge;
+pub use lexer_new::{Automaton, ByteScanner, Scanner, Token, TokenType};
 
 #[path = "config/mod.rs"]
 pub mod config;
@@ -19,7 +13,8 @@ pub mod config;
 // Error reporting
 pub mod error;
 
-// Recursive Descent Parser (used by LL(1) for AST construction)
+// Parser recursivo descendente: solo como oráculo en las pruebas del LL(1)
+#[cfg(test)]
 pub mod parser;
 
 // LL(1) Predictive Parser
@@ -28,3 +23,6 @@ pub mod parser_ll1;
 
 // AST (required by parser)
 pub mod ast;
+
+// Directiva incluye: une bibliotecas al programa
+pub mod inclusion;
@@ -3,39 +3,120 @@ use std::env;
 use std::fs;
 use std::process;
 
-mod lexer;
 #[path = "lexer_new/mod.rs"]
 mod lexer_new;
-mod lexer_bridge;
+#[path = "config/mod.rs"]
+mod config;
+#[cfg(test)]
 mod parser;
 mod parser_ll1;
 mod ast;
 mod error;
+mod inclusion;
 mod semantic;
 mod interpreter;
 mod visualizer;
 
+use error::{Diagnostic, SourceMap, report_errors, report_errors_in};
 use parser_ll1::PredictiveParser;
 use semantic::SemanticAnalyzer;
 use interpreter::{Interpreter, ConexionMaquina};
 
 fn main() {
-    println!("{}", "=== Network Interpreter v1 ===".cyan().bold());
-    
     let args: Vec<String> = env::args().collect();
+    let positional = positional_args(&args);
+
+    // Con --trace-parse json la salida estándar es solo el documento JSON
+    if option_value(&args, "--trace-parse") != Some("json") {
+        println!("{}", "=== Network Interpreter v1 ===".cyan().bold());
+    }
+
+    let exporting_dot = args.iter().any(|a| a == "--export-dot");
 
-    if args.len() < 2 {
+    if positional.is_empty() && !exporting_dot {
         eprintln!("{}", "Error: No se especificó archivo de entrada".red().bold());
-        eprintln!("Uso: {} <archivo.net> [--visualize|-v]", args[0]);
+        eprintln!("Uso: {} <archivo.net> [--visualize|-v] [--automaton <archivo.aut|.spec>] [--grammar <archivo.txt>] [--dialect <nombre>] [--trace-parse <tabla|json>] [--max-iterations <n>] [--export-dot <archivo.dot>]", args[0]);
         eprintln!("\n{}", "Opciones:".yellow());
         eprintln!("  {} o {}  - Mostrar visualización gráfica de la topología", "--visualize".green(), "-v".green());
+        eprintln!("  {} <archivo.aut|.spec>  - Usar un autómata léxico alternativo", "--automaton".green());
+        eprintln!("  {} <archivo.txt>  - Usar una gramática LL(1) alternativa (formato de docs/gramatica.txt)", "--grammar".green());
+        eprintln!("  {} <nombre>  - Dialecto de palabras reservadas (p. ej. english); tiene prioridad sobre el pragma // @dialecto", "--dialect".green());
+        eprintln!("  {} <tabla|json>  - Mostrar cada paso del parser LL(1): pila, preanálisis y producción (json: solo la traza en stdout)", "--trace-parse".green());
+        eprintln!("  {} <n>  - Máximo de iteraciones de los ciclos mientras/para en toda la ejecución (predeterminado: {})", "--max-iterations".green(), interpreter::LIMITE_ITERACIONES);
+        eprintln!("  {} <archivo.dot>  - Exportar el autómata léxico a Graphviz", "--export-dot".green());
         eprintln!("\n{}", "Ejemplos:".yellow());
         eprintln!("  {} test_interpreter_simple.net", args[0]);
         eprintln!("  {} test_interpreter_coaxial.net --visualize", args[0]);
+        eprintln!("  {} ejemplo1.net --automaton mi_lexer.aut", args[0]);
+        eprintln!("  {} red_en.net --dialect english", args[0]);
+        eprintln!("  {} ejemplo1.net --trace-parse json", args[0]);
+        eprintln!("  {} --export-dot automata.dot", args[0]);
         process::exit(1);
     }
 
-    let filename = &args[1];
+    // Autómata alternativo (por defecto se usa el incrustado en el binario)
+    if args.iter().any(|a| a == "--automaton") {
+        match option_value(&args, "--automaton") {
+            Some(path) => install_automaton_override(path),
+            None => {
+                eprintln!("{}", "Error: --automaton requiere la ruta de un archivo .aut o .spec".red().bold());
+                process::exit(1);
+            }
+        }
+    }
+
+    // Gramática alternativa (por defecto se usa docs/gramatica.txt, incrustada)
+    if args.iter().any(|a| a == "--grammar") {
+        match option_value(&args, "--grammar") {
+            Some(path) => install_grammar_override(path),
+            None => {
+                eprintln!("{}", "Error: --grammar requiere la ruta de un archivo de gramática".red().bold());
+                process::exit(1);
+            }
+        }
+    }
+
+    // Traza del parser: se valida antes de analizar nada
+    let trace_format = if args.iter().any(|a| a == "--trace-parse") {
+        match option_value(&args, "--trace-parse").and_then(TraceFormat::parse) {
+            Some(format) => Some(format),
+            None => {
+                eprintln!("{}", "Error: --trace-parse requiere el formato 'tabla' o 'json'".red().bold());
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // Límite de iteraciones de los ciclos
+    let limite_iteraciones = if args.iter().any(|a| a == "--max-iterations") {
+        match option_value(&args, "--max-iterations").and_then(|v| v.parse::<u64>().ok()) {
+            Some(limite) => limite,
+            None => {
+                eprintln!("{}", "Error: --max-iterations requiere un número entero no negativo".red().bold());
+                process::exit(1);
+            }
+        }
+    } else {
+        interpreter::LIMITE_ITERACIONES
+    };
+
+    // Exportar el autómata activo a DOT; sin archivo de entrada no hay nada más que hacer
+    if exporting_dot {
+        match option_value(&args, "--export-dot") {
+            Some(path) => export_automaton_dot(path),
+            None => {
+                eprintln!("{}", "Error: --export-dot requiere la ruta del archivo .dot de salida".red().bold());
+                process::exit(1);
+            }
+        }
+        if positional.is_empty() {
+            return;
+        }
+    }
+
+    let filename = positional[0];
 
     // Leer archivo fuente
     let source = match fs::read_to_string(filename) {
@@ -46,6 +127,23 @@ fn main() {
         }
     };
 
+    // Dialecto de palabras reservadas: --dialect, pragma de cabecera o predeterminado
+    let requested_dialect = if args.iter().any(|a| a == "--dialect") {
+        match option_value(&args, "--dialect") {
+            Some(name) => Some(name),
+            None => {
+                eprintln!("{}", "Error: --dialect requiere el nombre de un dialecto".red().bold());
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    if trace_format == Some(TraceFormat::Json) {
+        process::exit(print_json_trace(&source, requested_dialect));
+    }
+
     println!("{} {}\n", "Archivo:".green(), filename);
     
     // Mostrar estadísticas del código fuente
@@ -70,10 +168,26 @@ fn main() {
     println!("{}", "─".repeat(80));
     println!();
 
+    let dialect = match lexer_new::select_dialect(config::load_automaton(), &source, requested_dialect) {
+        Ok(dialect) => dialect,
+        Err(err) if requested_dialect.is_some() => {
+            eprintln!("{} {}", "Error en --dialect:".red().bold(), err.message);
+            process::exit(1);
+        }
+        Err(err) => {
+            lexer_new::report_lexical_errors(&[err], &source, filename);
+            process::exit(1);
+        }
+    };
+
+    if dialect.name() != config::load_automaton().default_dialect().name() {
+        println!("{} {}\n", "Dialecto:".green(), dialect.name());
+    }
+
     // ANÁLISIS LÉXICO
     println!("{}", "Analizando léxicamente...".yellow().bold());
 
-    match lexer_bridge::tokenize_with_new_lexer(source.clone()) {
+    match lexer_new::tokenize_with_dialect(&source, Some(dialect.name())) {
         Ok(tokens) => {
             println!("{} {} tokens generados\n", "✓".green().bold(), tokens.len());
 
@@ -88,18 +202,8 @@ fn main() {
             println!("{}", "─".repeat(90));
 
             for token_info in &tokens {
-                let token_str = format!("{:?}", token_info.token);
-                let token_display = if token_str.len() > 23 {
-                    format!("{}...", &token_str[..20])
-                } else {
-                    token_str
-                };
-                
-                let lexeme_display = if token_info.lexeme.len() > 33 {
-                    format!("{}...", &token_info.lexeme[..30])
-                } else {
-                    token_info.lexeme.clone()
-                };
+                let token_display = abbreviate(&format!("{:?}", token_info.token_type), 23);
+                let lexeme_display = abbreviate(&token_info.lexeme, 33);
                 
                 println!("{:<6} {:<8} {:<25} {:<35}", 
                          token_info.line,
@@ -114,13 +218,56 @@ fn main() {
             // ========== ANÁLISIS SINTÁCTICO ==========
             println!("\n{}", "Analizando sintácticamente con parser LL(1)...".yellow().bold());
 
-            // Usar parser LL(1) predictivo (two-pass approach)
-            let mut parser_ll1 = PredictiveParser::new(tokens.clone());
+            // Parser LL(1) predictivo: valida y construye el AST en una pasada
+            let mut parser_ll1 = PredictiveParser::new(tokens.clone()).with_dialect(dialect);
+            if trace_format.is_some() {
+                parser_ll1 = parser_ll1.with_trace();
+            }
+
+            let parse_result = parser_ll1.parse();
+
+            // La traza se muestra también si hubo errores: ayuda a entenderlos
+            if let Some(trace) = parser_ll1.trace() {
+                print_parse_trace(trace);
+            }
 
-            match parser_ll1.parse() {
-                Ok(programa) => {
+            match parse_result {
+                Ok(mut programa) => {
                     println!("{}", "Análisis sintáctico completado exitosamente".green().bold());
 
+                    // El archivo principal no puede ser una biblioteca
+                    if programa.biblioteca {
+                        let location = &programa.location;
+                        let diagnostic = Diagnostic::syntax_error(
+                            location.line,
+                            location.column,
+                            location.length,
+                            "El archivo principal debe ser un programa, no una biblioteca".to_string(),
+                        ).with_help("las bibliotecas (solo incluye, define y modulo) se usan desde otro archivo con incluye \"archivo.net\";".to_string());
+                        report_errors(&[diagnostic], &source, filename);
+                        process::exit(1);
+                    }
+
+                    // ========== INCLUSIONES ==========
+                    let mut sources = SourceMap::new();
+                    sources.add(filename.to_string(), source.clone());
+
+                    if !programa.inclusiones.is_empty() {
+                        println!("\n{}", "Resolviendo inclusiones...".yellow().bold());
+
+                        let resultado = inclusion::resolver_inclusiones(&mut programa, &mut sources, |fuente| {
+                            parse_included_file(fuente, requested_dialect)
+                        });
+                        if let Err(errors) = resultado {
+                            report_errors_in(&errors, &sources);
+                            process::exit(1);
+                        }
+
+                        for file in &sources.files()[1..] {
+                            println!("  {} {}", "✓".green().bold(), file.name);
+                        }
+                    }
+
                     // Mostrar AST
                     programa.pretty_print();
 
@@ -139,7 +286,8 @@ fn main() {
                             // ========== EJECUCIÓN DEL PROGRAMA ==========
                             println!("\n{}", "Ejecutando programa...".yellow().bold());
 
-                            let mut interpreter = Interpreter::new(&semantic_analyzer.symbol_table);
+                            let mut interpreter = Interpreter::new(&semantic_analyzer.symbol_table)
+                                .with_limite_iteraciones(limite_iteraciones);
 
                             match interpreter.ejecutar(&programa) {
                                 Ok(_) => {
@@ -174,24 +322,196 @@ fn main() {
                             }
                         }
                         Err(semantic_errors) => {
-                            semantic::report_semantic_errors(&semantic_errors, &source, filename);
+                            semantic::report_semantic_errors(&semantic_errors, &sources);
                             process::exit(1);
                         }
                     }
                 }
-                Err(error_msg) => {
-                    eprintln!("\n{} {}", "Error de sintaxis (LL1):".red().bold(), error_msg);
+                Err(syntax_errors) => {
+                    report_errors(&syntax_errors, &source, filename);
                     process::exit(1);
                 }
             }
         }
-        Err(error_msg) => {
-            eprintln!("{} {}", "Error léxico:".red().bold(), error_msg);
+        Err(lexical_errors) => {
+            lexer_new::report_lexical_errors(&lexical_errors, &source, filename);
+            process::exit(1);
+        }
+    }
+}
+
+/// Análisis léxico y sintáctico de un archivo incluido, con el autómata del
+/// principal; el dialecto es el de --dialect o el del pragma del archivo
+fn parse_included_file(source: &str, requested_dialect: Option<&str>) -> Result<ast::Program, Vec<Diagnostic>> {
+    let dialect = lexer_new::select_dialect(config::load_automaton(), source, requested_dialect)
+        .map_err(|err| lexer_new::lexical_diagnostics(&[err]))?;
+    let tokens = lexer_new::tokenize_with_dialect(source, Some(dialect.name()))
+        .map_err(|errors| lexer_new::lexical_diagnostics(&errors))?;
+    PredictiveParser::new(tokens).with_dialect(dialect).parse()
+}
+
+/// Opciones de línea de comandos que reciben un valor
+const OPTIONS_WITH_VALUE: &[&str] = &["--automaton", "--grammar", "--dialect", "--trace-parse", "--max-iterations", "--export-dot"];
+
+/// Formato de salida de `--trace-parse`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TraceFormat {
+    Tabla,
+    Json,
+}
+
+impl TraceFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "tabla" | "table" => Some(TraceFormat::Tabla),
+            "json" => Some(TraceFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Obtiene el valor que sigue a una opción (p. ej. `--automaton <ruta>`)
+fn option_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .filter(|v| !v.starts_with('-'))
+        .map(|v| v.as_str())
+}
+
+/// Argumentos que no son opciones ni valores de opciones
+fn positional_args(args: &[String]) -> Vec<&str> {
+    let mut positional = Vec::new();
+    let mut skip_value = false;
+
+    for arg in args.iter().skip(1) {
+        if skip_value {
+            skip_value = false;
+            continue;
+        }
+        if OPTIONS_WITH_VALUE.contains(&arg.as_str()) {
+            skip_value = true;
+        } else if !arg.starts_with('-') {
+            positional.push(arg.as_str());
+        }
+    }
+
+    positional
+}
+
+/// Carga un autómata desde archivo y lo instala en lugar del incrustado
+fn install_automaton_override(path: &str) {
+    // Las especificaciones .spec se compilan a DFA; los .aut se leen tal cual
+    match config::load_automaton_from(path) {
+        Ok(automaton) => {
+            let _ = config::set_automaton(automaton);
+            eprintln!("{} {}", "Autómata:".green(), path);
+        }
+        Err(e) => {
+            // El archivo se relee solo para mostrar la línea del error
+            let content = fs::read_to_string(path).unwrap_or_default();
+            let diagnostic = Diagnostic::config_error(e.line, e.column, e.length, e.message)
+                .with_help("corrija el archivo .aut/.spec u omita --automaton para usar el autómata incrustado".to_string());
+            report_errors(&[diagnostic], &content, path);
+            process::exit(1);
+        }
+    }
+}
+
+/// Carga una gramática desde archivo y la instala en lugar de la incrustada
+///
+/// Los conflictos LL(1) no impiden usarla (la tabla resuelve cada celda), pero
+/// se avisan con los números de producción involucrados.
+fn install_grammar_override(path: &str) {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("{} {}: {}", "Error al leer gramática".red().bold(), path, e);
+            process::exit(1);
+        }
+    };
+
+    match parser_ll1::Grammar::parse(&content) {
+        Ok(grammar) => {
+            let table = parser_ll1::LL1Table::from_grammar(&grammar);
+            for conflict in table.conflicts() {
+                eprintln!("{} {}", "Advertencia: conflicto LL(1)".yellow().bold(), conflict);
+            }
+            let _ = config::set_grammar(grammar);
+            eprintln!("{} {}", "Gramática:".green(), path);
+        }
+        Err(diagnostic) => {
+            let diagnostic = diagnostic
+                .with_help("corrija la gramática u omita --grammar para usar la incrustada".to_string());
+            report_errors(&[diagnostic], &content, path);
             process::exit(1);
         }
     }
 }
 
+/// Escribe el autómata activo en formato Graphviz DOT
+fn export_automaton_dot(path: &str) {
+    let dot = config::load_automaton().to_dot();
+
+    match fs::write(path, &dot) {
+        Ok(_) => {
+            println!("{} {}", "Autómata exportado a".green(), path);
+            println!("  Render: dot -Tsvg {} -o automata.svg", path);
+        }
+        Err(e) => {
+            eprintln!("{} {}: {}", "Error al escribir".red().bold(), path, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Muestra los pasos del parser LL(1); el JSON va sin adornos para poder
+/// procesarlo con otras herramientas
+fn print_parse_trace(trace: &parser_ll1::ParseTrace) {
+    println!("\n{} ({} pasos)", "TRAZA DEL PARSER LL(1)".cyan().bold(), trace.len());
+    println!("{}", trace.to_table());
+}
+
+/// `--trace-parse json`: analiza el archivo y escribe en stdout solo la traza,
+/// para poder redirigirla a otra herramienta; los errores van a stderr.
+/// Devuelve el código de salida.
+fn print_json_trace(source: &str, requested_dialect: Option<&str>) -> i32 {
+    let dialect = match lexer_new::select_dialect(config::load_automaton(), source, requested_dialect) {
+        Ok(dialect) => dialect,
+        Err(err) => {
+            eprintln!("{}", lexer_new::lexical_diagnostics(&[err])[0]);
+            return 1;
+        }
+    };
+    let tokens = match lexer_new::tokenize_with_dialect(source, Some(dialect.name())) {
+        Ok(tokens) => tokens,
+        Err(errors) => {
+            for diagnostic in lexer_new::lexical_diagnostics(&errors) {
+                eprintln!("{}", diagnostic);
+            }
+            return 1;
+        }
+    };
+
+    let mut parser = PredictiveParser::new(tokens).with_dialect(dialect).with_trace();
+    let result = parser.parse();
+    if let Some(trace) = parser.trace() {
+        // Un lector que cierra la tubería antes de tiempo (p. ej. `head`) no es un error
+        use std::io::Write;
+        let _ = writeln!(std::io::stdout().lock(), "{}", trace.to_json());
+    }
+
+    match result {
+        Ok(_) => 0,
+        Err(errors) => {
+            for diagnostic in &errors {
+                eprintln!("{}", diagnostic);
+            }
+            1
+        }
+    }
+}
+
 fn print_symbol_table(table: &semantic::SymbolTable) {
     use colored::*;
     use std::io::{self, Write};
@@ -236,10 +556,26 @@ fn print_symbol_table(table: &semantic::SymbolTable) {
         }
     }
 
+    if !table.variables.is_empty() {
+        println!("{}", "\nVariables:".green());
+        let mut nombres: Vec<_> = table.variables.keys().collect();
+        nombres.sort();
+        for nombre in nombres {
+            println!("  • {} - entero", nombre.bold());
+        }
+    }
+
     if !table.modulos.is_empty() {
         println!("{}", "\nMódulos:".green());
-        for nombre in table.modulos.keys() {
-            println!("  • {}", nombre.bold());
+        for (nombre, modulo) in &table.modulos {
+            let parametros: Vec<String> = modulo.parametros.iter()
+                .map(|p| format!("{} {}", p.tipo.nombre(), p.nombre))
+                .collect();
+            if parametros.is_empty() {
+                println!("  • {}", nombre.bold());
+            } else {
+                println!("  • {}({})", nombre.bold(), parametros.join(", "));
+            }
         }
     }
 
@@ -336,21 +672,37 @@ fn print_network_state(env: &interpreter::Environment) {
         }
     }
 
+    // Mostrar variables
+    if !env.variables.is_empty() {
+        println!("{}", "\nVariables:".green());
+        let mut variables: Vec<_> = env.variables.iter().collect();
+        variables.sort();
+        for (nombre, valor) in variables {
+            println!("  • {} = {}", nombre.bold(), valor);
+        }
+    }
+
     println!("\n{}", "═".repeat(80));
     let _ = io::stdout().flush();
 }
 
-fn print_token_statistics(tokens: &[lexer::TokenInfo]) {
+/// Recorta `text` a `width` caracteres (no bytes: los identificadores pueden
+/// llevar tildes), terminando en `...` si no cabe
+fn abbreviate(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        format!("{}...", text.chars().take(width - 3).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+fn print_token_statistics(tokens: &[lexer_new::Token]) {
     use std::collections::HashMap;
     
     let mut token_counts: HashMap<String, usize> = HashMap::new();
     
     for token_info in tokens {
-        let token_type = format!("{:?}", token_info.token)
-            .split('(')
-            .next()
-            .unwrap_or("Unknown")
-            .to_string();
+        let token_type = format!("{:?}", token_info.token_type);
         *token_counts.entry(token_type).or_insert(0) += 1;
     }
     
@@ -1,7 +1,7 @@
 // Parser - Análisis Sintáctico
 // Convierte tokens en AST
 
-use crate::lexer::{Token, TokenInfo};
+use crate::lexer_new::{Dialect, Token, TokenType};
 use crate::ast::*;
 
 // ============================================================================
@@ -25,35 +25,58 @@ impl ParseError {
 // ============================================================================
 
 pub struct Parser {
-    tokens: Vec<TokenInfo>,
+    tokens: Vec<Token>,
     current: usize,
+    // Dialecto con el que se escriben las palabras reservadas en los errores
+    dialect: Dialect,
 }
 
 impl Parser {
-    pub fn new(tokens: Vec<TokenInfo>) -> Self {
-        Self { tokens, current: 0 }
+    pub fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, current: 0, dialect: Dialect::default() }
+    }
+
+    // Los errores nombran las palabras reservadas como en el dialecto del programa
+    pub fn with_dialect(mut self, dialect: &Dialect) -> Self {
+        self.dialect = dialect.clone();
+        self
+    }
+
+    // Texto de un tipo de token en el dialecto activo
+    fn spell(&self, token_type: &TokenType) -> String {
+        self.dialect.display(token_type)
     }
 
     // ========== UTILIDADES ==========
 
-    fn peek(&self) -> &Token {
-        if self.current < self.tokens.len() {
-            &self.tokens[self.current].token
-        } else {
-            // Retornar un token punto como EOF
-            &Token::Punto
+    fn peek(&self) -> &TokenType {
+        match self.peek_info() {
+            Some(token) => &token.token_type,
+            None => &TokenType::Eof,
         }
     }
 
-    fn peek_info(&self) -> Option<&TokenInfo> {
-        if self.current < self.tokens.len() {
-            Some(&self.tokens[self.current])
-        } else {
-            None
-        }
+    fn peek_info(&self) -> Option<&Token> {
+        self.tokens.get(self.current)
     }
 
-    fn advance(&mut self) -> Option<&TokenInfo> {
+    // Lexema del token actual
+    fn peek_lexeme(&self) -> String {
+        self.peek_info().map(|t| t.lexeme.clone()).unwrap_or_default()
+    }
+
+    // Valor del número actual
+    fn peek_number(&self) -> Result<i32, ParseError> {
+        let lexeme = self.peek_lexeme();
+        lexeme.parse::<i32>().map_err(|_| {
+            ParseError::new(
+                format!("El número {} está fuera de rango", lexeme),
+                self.current_location(),
+            )
+        })
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
         if self.current < self.tokens.len() {
             self.current += 1;
             Some(&self.tokens[self.current - 1])
@@ -62,27 +85,30 @@ impl Parser {
         }
     }
 
-    fn expect(&mut self, expected: Token) -> Result<&TokenInfo, ParseError> {
-        let current_info = self.peek_info().cloned();
-        
-        if std::mem::discriminant(self.peek()) == std::mem::discriminant(&expected) {
+    fn expect(&mut self, expected: TokenType) -> Result<&Token, ParseError> {
+        if self.peek() == &expected {
             self.advance()
                 .ok_or_else(|| ParseError::new(
                     "Token inesperado al final del archivo".into(),
                     Location::unknown(),
                 ))
         } else {
-            let loc = current_info
-                .map(|t| Location::from_token(&t))
-                .unwrap_or_else(Location::unknown);
-            
             Err(ParseError::new(
-                format!("Se esperaba {:?}, se encontró {:?}", expected, self.peek()),
-                loc,
+                format!(
+                    "Se esperaba '{}', se encontró '{}'",
+                    self.spell(&expected),
+                    self.spell(self.peek()),
+                ),
+                self.current_location(),
             ))
         }
     }
 
+    // Comentarios /// adjuntos al token actual
+    fn current_doc(&self) -> Option<String> {
+        self.peek_info().and_then(|t| t.doc.clone())
+    }
+
     fn current_location(&self) -> Location {
         self.peek_info()
             .map(Location::from_token)
@@ -90,18 +116,18 @@ impl Parser {
     }
 
     // Convierte un token en un nombre de campo (permite palabras reservadas)
-    fn token_to_field_name(&self, token: &Token) -> Option<String> {
+    fn token_to_field_name(&self, token: &TokenType) -> Option<String> {
         match token {
-            Token::Identificador(s) => Some(s.clone()),
+            TokenType::Identifier => Some(self.peek_lexeme()),
             // Palabras reservadas que pueden ser nombres de campo
-            Token::Coaxial => Some("coaxial".to_string()),
-            Token::Segmento => Some("segmento".to_string()),
-            Token::Maquinas => Some("maquinas".to_string()),
-            Token::Concentradores => Some("concentradores".to_string()),
-            Token::Derecha => Some("derecha".to_string()),
-            Token::Izquierda => Some("izquierda".to_string()),
-            Token::Arriba => Some("arriba".to_string()),
-            Token::Abajo => Some("abajo".to_string()),
+            TokenType::Coaxial => Some("coaxial".to_string()),
+            TokenType::Segmento => Some("segmento".to_string()),
+            TokenType::Maquinas => Some("maquinas".to_string()),
+            TokenType::Concentradores => Some("concentradores".to_string()),
+            TokenType::Derecha => Some("derecha".to_string()),
+            TokenType::Izquierda => Some("izquierda".to_string()),
+            TokenType::Arriba => Some("arriba".to_string()),
+            TokenType::Abajo => Some("abajo".to_string()),
             _ => None,
         }
     }
@@ -133,12 +159,12 @@ impl Parser {
         let loc = self.current_location();
 
         // Esperar "programa"
-        self.expect(Token::Programa)?;
+        self.expect(TokenType::Programa)?;
 
         // Esperar identificador (nombre del programa)
         let nombre = match self.peek() {
-            Token::Identificador(name) => {
-                let n = name.clone();
+            TokenType::Identifier => {
+                let n = self.peek_lexeme();
                 self.advance();
                 n
             }
@@ -151,28 +177,30 @@ impl Parser {
         };
 
         // Esperar punto y coma
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::Semicolon)?;
 
         // Parsear definiciones
         let definiciones = self.parse_definiciones()?;
 
         // Parsear módulos (opcionales)
         let mut modulos = Vec::new();
-        while self.peek() == &Token::Modulo {
+        while self.peek() == &TokenType::Modulo {
             modulos.push(self.parse_modulo()?);
         }
 
         // Parsear bloque principal: inicio sentencias fin.
-        self.expect(Token::Inicio)?;
+        self.expect(TokenType::Inicio)?;
         let sentencias = self.parse_sentencias()?;
-        self.expect(Token::Fin)?;
-        self.expect(Token::Punto)?;
+        self.expect(TokenType::Fin)?;
+        self.expect(TokenType::Dot)?;
 
         Ok(Program {
             nombre,
+            inclusiones: Vec::new(),
             definiciones,
             modulos,
             sentencias,
+            biblioteca: false,
             location: loc,
         })
     }
@@ -182,11 +210,12 @@ impl Parser {
 
     fn parse_modulo(&mut self) -> Result<Modulo, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::Modulo)?;
+        let doc = self.current_doc();
+        self.expect(TokenType::Modulo)?;
 
         let nombre = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -198,14 +227,16 @@ impl Parser {
             }
         };
 
-        self.expect(Token::PuntoYComa)?;
-        self.expect(Token::Inicio)?;
+        self.expect(TokenType::Semicolon)?;
+        self.expect(TokenType::Inicio)?;
         let sentencias = self.parse_sentencias()?;
-        self.expect(Token::Fin)?;
+        self.expect(TokenType::Fin)?;
 
         Ok(Modulo {
             nombre,
+            parametros: Vec::new(),
             sentencias,
+            doc,
             location: loc,
         })
     }
@@ -220,14 +251,15 @@ impl Parser {
         let mut coaxiales = Vec::new();
 
         // Intentar parsear define maquinas
-        if self.peek() == &Token::Define {
+        if self.peek() == &TokenType::Define {
+            let define_doc = self.current_doc();
             self.advance();
             
             match self.peek() {
-                Token::Maquinas => {
+                TokenType::Maquinas => {
                     self.advance();
-                    maquinas = self.parse_lista_maquinas()?;
-                    self.expect(Token::PuntoYComa)?;
+                    maquinas = self.parse_lista_maquinas(define_doc)?;
+                    self.expect(TokenType::Semicolon)?;
                 }
                 _ => {
                     // No es define maquinas, retroceder
@@ -237,14 +269,15 @@ impl Parser {
         }
 
         // Intentar parsear define concentradores
-        if self.peek() == &Token::Define {
+        if self.peek() == &TokenType::Define {
+            let define_doc = self.current_doc();
             self.advance();
             
             match self.peek() {
-                Token::Concentradores => {
+                TokenType::Concentradores => {
                     self.advance();
-                    concentradores = self.parse_lista_concentradores()?;
-                    self.expect(Token::PuntoYComa)?;
+                    concentradores = self.parse_lista_concentradores(define_doc)?;
+                    self.expect(TokenType::Semicolon)?;
                 }
                 _ => {
                     // No es define concentradores, retroceder
@@ -254,14 +287,15 @@ impl Parser {
         }
 
         // ⚡ Intentar parsear define coaxial o segmento
-        if self.peek() == &Token::Define {
+        if self.peek() == &TokenType::Define {
+            let define_doc = self.current_doc();
             self.advance();
             
             match self.peek() {
-                Token::Coaxial | Token::Segmento => {
+                TokenType::Coaxial | TokenType::Segmento => {
                     self.advance();
-                    coaxiales = self.parse_lista_coaxial()?;
-                    self.expect(Token::PuntoYComa)?;
+                    coaxiales = self.parse_lista_coaxial(define_doc)?;
+                    self.expect(TokenType::Semicolon)?;
                 }
                 _ => {
                     // No es define coaxial/segmento, retroceder
@@ -274,26 +308,31 @@ impl Parser {
             maquinas,
             concentradores,
             coaxiales,
+            enteros: Vec::new(),
             location: loc,
         })
     }
 
     // ========== LISTA DE MÁQUINAS ==========
     // lista_ids ::= IDENTIFICADOR ("," IDENTIFICADOR)*
+    // Los comentarios /// antes de `define` documentan cada nombre de la lista
+    // que no tenga los suyos propios
 
-    fn parse_lista_maquinas(&mut self) -> Result<Vec<MaquinaDecl>, ParseError> {
+    fn parse_lista_maquinas(&mut self, define_doc: Option<String>) -> Result<Vec<MaquinaDecl>, ParseError> {
         let mut maquinas = Vec::new();
 
         loop {
             let loc = self.current_location();
+            let doc = self.current_doc().or_else(|| define_doc.clone());
 
             match self.peek() {
-                Token::Identificador(nombre) => {
-                    let n = nombre.clone();
+                TokenType::Identifier => {
+                    let n = self.peek_lexeme();
                     self.advance();
 
                     maquinas.push(MaquinaDecl {
                         nombre: n,
+                        doc,
                         location: loc,
                     });
                 }
@@ -306,7 +345,7 @@ impl Parser {
             }
 
             // Si no hay coma, terminar
-            if self.peek() != &Token::Coma {
+            if self.peek() != &TokenType::Comma {
                 break;
             }
             self.advance(); // Consumir coma
@@ -318,16 +357,17 @@ impl Parser {
     // ========== LISTA DE CONCENTRADORES ==========
     // def_concentrador ::= IDENTIFICADOR "=" NUMERO ("." "1")?
 
-    fn parse_lista_concentradores(&mut self) -> Result<Vec<ConcentradorDecl>, ParseError> {
+    fn parse_lista_concentradores(&mut self, define_doc: Option<String>) -> Result<Vec<ConcentradorDecl>, ParseError> {
         let mut concentradores = Vec::new();
 
         loop {
             let loc = self.current_location();
+            let doc = self.current_doc().or_else(|| define_doc.clone());
 
             // Nombre del concentrador
             let nombre = match self.peek() {
-                Token::Identificador(n) => {
-                    let name = n.clone();
+                TokenType::Identifier => {
+                    let name = self.peek_lexeme();
                     self.advance();
                     name
                 }
@@ -340,12 +380,12 @@ impl Parser {
             };
 
             // Igual
-            self.expect(Token::Igual)?;
+            self.expect(TokenType::Equal)?;
 
             // Número de puertos
             let puertos = match self.peek() {
-                Token::Numero(p) => {
-                    let ports = *p;
+                TokenType::Number => {
+                    let ports = self.peek_number()?;
                     self.advance();
                     ports
                 }
@@ -358,10 +398,10 @@ impl Parser {
             };
 
             // ⚡ Verificar si tiene .1 (salida coaxial)
-            let tiene_coaxial = if self.peek() == &Token::Punto {
+            let tiene_coaxial = if self.peek() == &TokenType::Dot {
                 self.advance();
                 match self.peek() {
-                    Token::Numero(1) => {
+                    TokenType::Number if self.peek_lexeme() == "1" => {
                         self.advance();
                         true
                     }
@@ -380,11 +420,12 @@ impl Parser {
                 nombre,
                 puertos,
                 tiene_coaxial,
+                doc,
                 location: loc,
             });
 
             // Si no hay coma, terminar
-            if self.peek() != &Token::Coma {
+            if self.peek() != &TokenType::Comma {
                 break;
             }
             self.advance(); // Consumir coma
@@ -396,16 +437,17 @@ impl Parser {
     // ========== LISTA DE COAXIALES ==========
     // def_coaxial ::= IDENTIFICADOR "=" NUMERO
 
-    fn parse_lista_coaxial(&mut self) -> Result<Vec<CoaxialDecl>, ParseError> {
+    fn parse_lista_coaxial(&mut self, define_doc: Option<String>) -> Result<Vec<CoaxialDecl>, ParseError> {
         let mut coaxiales = Vec::new();
 
         loop {
             let loc = self.current_location();
+            let doc = self.current_doc().or_else(|| define_doc.clone());
 
             // Nombre del coaxial
             let nombre = match self.peek() {
-                Token::Identificador(n) => {
-                    let name = n.clone();
+                TokenType::Identifier => {
+                    let name = self.peek_lexeme();
                     self.advance();
                     name
                 }
@@ -418,12 +460,12 @@ impl Parser {
             };
 
             // Igual
-            self.expect(Token::Igual)?;
+            self.expect(TokenType::Equal)?;
 
             // Longitud
             let longitud = match self.peek() {
-                Token::Numero(l) => {
-                    let len = *l;
+                TokenType::Number => {
+                    let len = self.peek_number()?;
                     self.advance();
                     len
                 }
@@ -438,11 +480,12 @@ impl Parser {
             coaxiales.push(CoaxialDecl {
                 nombre,
                 longitud,
+                doc,
                 location: loc,
             });
 
             // Si no hay coma, terminar
-            if self.peek() != &Token::Coma {
+            if self.peek() != &TokenType::Comma {
                 break;
             }
             self.advance(); // Consumir coma
@@ -458,12 +501,12 @@ impl Parser {
 
         loop {
             // Si encontramos 'fin', terminamos
-            if self.peek() == &Token::Fin {
+            if self.peek() == &TokenType::Fin {
                 break;
             }
 
             // Si llegamos al final de los tokens, terminamos
-            if self.current >= self.tokens.len() {
+            if self.peek() == &TokenType::Eof {
                 break;
             }
 
@@ -477,27 +520,28 @@ impl Parser {
         let loc = self.current_location();
 
         match self.peek() {
-            Token::Coloca => self.parse_coloca(),
-            Token::ColocaCoaxial => self.parse_coloca_coaxial(),
-            Token::ColocaCoaxialConcentrador => self.parse_coloca_coaxial_concentrador(),
-            Token::UneMaquinaPuerto => self.parse_une_maquina_puerto(),
-            Token::AsignaPuerto => self.parse_asigna_puerto(),
-            Token::MaquinaCoaxial => self.parse_maquina_coaxial(),
-            Token::AsignaMaquinaCoaxial => self.parse_asigna_maquina_coaxial(),
-            Token::Escribe => self.parse_escribe(),
-            Token::Si => self.parse_si(),
-            Token::Identificador(nombre) => {
+            TokenType::Coloca => self.parse_coloca(),
+            TokenType::ColocaCoaxial => self.parse_coloca_coaxial(),
+            TokenType::ColocaCoaxialConcentrador => self.parse_coloca_coaxial_concentrador(),
+            TokenType::UneMaquinaPuerto => self.parse_une_maquina_puerto(),
+            TokenType::AsignaPuerto => self.parse_asigna_puerto(),
+            TokenType::MaquinaCoaxial => self.parse_maquina_coaxial(),
+            TokenType::AsignaMaquinaCoaxial => self.parse_asigna_maquina_coaxial(),
+            TokenType::Escribe => self.parse_escribe(),
+            TokenType::Si => self.parse_si(),
+            TokenType::Identifier => {
                 // Llamada a módulo
-                let n = nombre.clone();
+                let n = self.peek_lexeme();
                 self.advance();
-                self.expect(Token::PuntoYComa)?;
+                self.expect(TokenType::Semicolon)?;
                 Ok(Statement::LlamadaModulo {
                     nombre: n,
+                    argumentos: Vec::new(),
                     location: loc,
                 })
             }
             _ => Err(ParseError::new(
-                format!("Sentencia inválida: {:?}", self.peek()),
+                format!("Sentencia inválida: '{}'", self.spell(self.peek())),
                 self.current_location(),
             )),
         }
@@ -506,12 +550,12 @@ impl Parser {
     // coloca(objeto, x, y);
     fn parse_coloca(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::Coloca)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::Coloca)?;
+        self.expect(TokenType::LParen)?;
 
         let objeto = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -523,12 +567,12 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
         let x = self.parse_expresion()?;
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
         let y = self.parse_expresion()?;
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::Coloca {
             objeto,
@@ -541,12 +585,12 @@ impl Parser {
     // colocaCoaxial(coaxial, x, y, direccion);
     fn parse_coloca_coaxial(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::ColocaCoaxial)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::ColocaCoaxial)?;
+        self.expect(TokenType::LParen)?;
 
         let coaxial = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -558,39 +602,48 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
         let x = self.parse_expresion()?;
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
         let y = self.parse_expresion()?;
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
 
         let direccion = match self.peek() {
-            Token::Arriba => {
+            TokenType::Arriba => {
                 self.advance();
                 Direccion::Arriba
             }
-            Token::Abajo => {
+            TokenType::Abajo => {
                 self.advance();
                 Direccion::Abajo
             }
-            Token::Izquierda => {
+            TokenType::Izquierda => {
                 self.advance();
                 Direccion::Izquierda
             }
-            Token::Derecha => {
+            TokenType::Derecha => {
                 self.advance();
                 Direccion::Derecha
             }
             _ => {
+                let direcciones: Vec<String> = [
+                    TokenType::Arriba,
+                    TokenType::Abajo,
+                    TokenType::Izquierda,
+                    TokenType::Derecha,
+                ]
+                .iter()
+                .map(|d| self.spell(d))
+                .collect();
                 return Err(ParseError::new(
-                    "Se esperaba dirección (arriba, abajo, izquierda, derecha)".into(),
+                    format!("Se esperaba dirección ({})", direcciones.join(", ")),
                     self.current_location(),
                 ))
             }
         };
 
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::ColocaCoaxial {
             coaxial,
@@ -604,12 +657,12 @@ impl Parser {
     // colocaCoaxialConcentrador(coaxial, concentrador);
     fn parse_coloca_coaxial_concentrador(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::ColocaCoaxialConcentrador)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::ColocaCoaxialConcentrador)?;
+        self.expect(TokenType::LParen)?;
 
         let coaxial = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -621,11 +674,11 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
 
         let concentrador = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -637,8 +690,8 @@ impl Parser {
             }
         };
 
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::ColocaCoaxialConcentrador {
             coaxial,
@@ -650,12 +703,12 @@ impl Parser {
     // uneMaquinaPuerto(maquina, concentrador, puerto);
     fn parse_une_maquina_puerto(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::UneMaquinaPuerto)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::UneMaquinaPuerto)?;
+        self.expect(TokenType::LParen)?;
 
         let maquina = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -667,11 +720,11 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
 
         let concentrador = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -683,10 +736,10 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
         let puerto = self.parse_expresion()?;
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::UneMaquinaPuerto {
             maquina,
@@ -699,12 +752,12 @@ impl Parser {
     // asignaPuerto(maquina, concentrador);
     fn parse_asigna_puerto(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::AsignaPuerto)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::AsignaPuerto)?;
+        self.expect(TokenType::LParen)?;
 
         let maquina = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -716,11 +769,11 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
 
         let concentrador = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -732,8 +785,8 @@ impl Parser {
             }
         };
 
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::AsignaPuerto {
             maquina,
@@ -745,12 +798,12 @@ impl Parser {
     // maquinaCoaxial(maquina, coaxial, posicion);
     fn parse_maquina_coaxial(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::MaquinaCoaxial)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::MaquinaCoaxial)?;
+        self.expect(TokenType::LParen)?;
 
         let maquina = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -762,11 +815,11 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
 
         let coaxial = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -778,10 +831,10 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
         let posicion = self.parse_expresion()?;
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::MaquinaCoaxial {
             maquina,
@@ -794,12 +847,12 @@ impl Parser {
     // asignaMaquinaCoaxial(maquina, coaxial);
     fn parse_asigna_maquina_coaxial(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::AsignaMaquinaCoaxial)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::AsignaMaquinaCoaxial)?;
+        self.expect(TokenType::LParen)?;
 
         let maquina = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -811,11 +864,11 @@ impl Parser {
             }
         };
 
-        self.expect(Token::Coma)?;
+        self.expect(TokenType::Comma)?;
 
         let coaxial = match self.peek() {
-            Token::Identificador(n) => {
-                let name = n.clone();
+            TokenType::Identifier => {
+                let name = self.peek_lexeme();
                 self.advance();
                 name
             }
@@ -827,8 +880,8 @@ impl Parser {
             }
         };
 
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::AsignaMaquinaCoaxial {
             maquina,
@@ -840,11 +893,11 @@ impl Parser {
     // escribe(expresion);
     fn parse_escribe(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::Escribe)?;
-        self.expect(Token::ParenIzq)?;
+        self.expect(TokenType::Escribe)?;
+        self.expect(TokenType::LParen)?;
         let contenido = self.parse_expresion()?;
-        self.expect(Token::ParenDer)?;
-        self.expect(Token::PuntoYComa)?;
+        self.expect(TokenType::RParen)?;
+        self.expect(TokenType::Semicolon)?;
 
         Ok(Statement::Escribe {
             contenido,
@@ -856,23 +909,23 @@ impl Parser {
     // Los paréntesis se manejan como parte de la expresión
     fn parse_si(&mut self) -> Result<Statement, ParseError> {
         let loc = self.current_location();
-        self.expect(Token::Si)?;
+        self.expect(TokenType::Si)?;
 
         // Parsear la condición (los paréntesis se manejan en parse_expresion)
         let condicion = self.parse_expresion()?;
 
-        self.expect(Token::Inicio)?;
+        self.expect(TokenType::Inicio)?;
 
         // Parsear sentencias del bloque entonces
         let entonces = self.parse_sentencias()?;
-        self.expect(Token::Fin)?;
+        self.expect(TokenType::Fin)?;
 
         // Verificar si hay bloque sino
-        let sino = if self.peek() == &Token::Sino {
+        let sino = if self.peek() == &TokenType::Sino {
             self.advance();
-            self.expect(Token::Inicio)?;
+            self.expect(TokenType::Inicio)?;
             let sentencias_sino = self.parse_sentencias()?;
-            self.expect(Token::Fin)?;
+            self.expect(TokenType::Fin)?;
             Some(sentencias_sino)
         } else {
             None
@@ -881,6 +934,7 @@ impl Parser {
         Ok(Statement::Si {
             condicion,
             entonces,
+            sino_si: Vec::new(),
             sino,
             location: loc,
         })
@@ -902,7 +956,7 @@ impl Parser {
     fn parse_expresion_or(&mut self) -> Result<Expr, ParseError> {
         let mut izq = self.parse_expresion_and()?;
 
-        while self.peek() == &Token::Or {
+        while self.peek() == &TokenType::Or {
             self.advance();
             let der = self.parse_expresion_and()?;
             izq = Expr::Logico {
@@ -919,7 +973,7 @@ impl Parser {
     fn parse_expresion_and(&mut self) -> Result<Expr, ParseError> {
         let mut izq = self.parse_expresion_relacional()?;
 
-        while self.peek() == &Token::And {
+        while self.peek() == &TokenType::And {
             self.advance();
             let der = self.parse_expresion_relacional()?;
             izq = Expr::Logico {
@@ -937,12 +991,12 @@ impl Parser {
         let izq = self.parse_expresion_not()?;
 
         let op = match self.peek() {
-            Token::Igual => OpRelacional::Igual,
-            Token::Diferente => OpRelacional::Diferente,
-            Token::Menor => OpRelacional::Menor,
-            Token::Mayor => OpRelacional::Mayor,
-            Token::MenorIgual => OpRelacional::MenorIgual,
-            Token::MayorIgual => OpRelacional::MayorIgual,
+            TokenType::Equal => OpRelacional::Igual,
+            TokenType::NotEqual => OpRelacional::Diferente,
+            TokenType::Less => OpRelacional::Menor,
+            TokenType::Greater => OpRelacional::Mayor,
+            TokenType::LessEqual => OpRelacional::MenorIgual,
+            TokenType::GreaterEqual => OpRelacional::MayorIgual,
             _ => return Ok(izq),
         };
 
@@ -958,7 +1012,7 @@ impl Parser {
 
     // NOT: !expr
     fn parse_expresion_not(&mut self) -> Result<Expr, ParseError> {
-        if self.peek() == &Token::Not {
+        if self.peek() == &TokenType::Not {
             self.advance();
             let expr = self.parse_expresion_not()?;
             return Ok(Expr::Not(Box::new(expr)));
@@ -969,35 +1023,38 @@ impl Parser {
 
     // Expresiones primarias: números, cadenas, identificadores, accesos
     fn parse_expresion_primaria(&mut self) -> Result<Expr, ParseError> {
-        match self.peek().clone() {
+        match self.peek() {
             // Paréntesis
-            Token::ParenIzq => {
+            TokenType::LParen => {
                 self.advance();
                 let expr = self.parse_expresion()?;
-                self.expect(Token::ParenDer)?;
+                self.expect(TokenType::RParen)?;
                 Ok(expr)
             }
 
             // Número
-            Token::Numero(n) => {
+            TokenType::Number => {
+                let n = self.peek_number()?;
                 self.advance();
                 Ok(Expr::Numero(n))
             }
 
-            // Cadena
-            Token::Cadena(s) => {
+            // Cadena (sin comillas)
+            TokenType::String => {
+                let s = self.peek_lexeme().trim_matches('"').to_string();
                 self.advance();
                 Ok(Expr::Cadena(s))
             }
 
             // Identificador (puede tener accesos)
-            Token::Identificador(nombre) => {
+            TokenType::Identifier => {
+                let nombre = self.peek_lexeme();
                 self.advance();
                 self.parse_accesos(nombre)
             }
 
             _ => Err(ParseError::new(
-                format!("Se esperaba una expresión, se encontró {:?}", self.peek()),
+                format!("Se esperaba una expresión, se encontró '{}'", self.spell(self.peek())),
                 self.current_location(),
             )),
         }
@@ -1007,7 +1064,7 @@ impl Parser {
     fn parse_accesos(&mut self, objeto: String) -> Result<Expr, ParseError> {
         match self.peek() {
             // Acceso a campo: objeto.campo
-            Token::Punto => {
+            TokenType::Dot => {
                 self.advance();
 
                 // Intentar obtener el nombre del campo (puede ser identificador o palabra reservada)
@@ -1018,10 +1075,10 @@ impl Parser {
                         self.advance();
 
                         // Verificar si hay acceso a arreglo después: obj.p[1]
-                        if self.peek() == &Token::CorcheteIzq {
+                        if self.peek() == &TokenType::LBracket {
                             self.advance();
                             let indice = self.parse_expresion()?;
-                            self.expect(Token::CorcheteDer)?;
+                            self.expect(TokenType::RBracket)?;
 
                             // Crear identificador compuesto para el acceso
                             let campo_completo = format!("{}.{}", objeto, c);
@@ -1044,10 +1101,10 @@ impl Parser {
             }
 
             // Acceso a arreglo: objeto[indice]
-            Token::CorcheteIzq => {
+            TokenType::LBracket => {
                 self.advance();
                 let indice = self.parse_expresion()?;
-                self.expect(Token::CorcheteDer)?;
+                self.expect(TokenType::RBracket)?;
                 Ok(Expr::AccesoArreglo {
                     objeto,
                     indice: Box::new(indice),
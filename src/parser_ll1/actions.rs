@@ -0,0 +1,811 @@
+// src/parser_ll1/actions.rs
+// Acciones semánticas de las producciones LL(1): construyen el AST
+//
+// Cada producción de la tabla lleva una `Action`. Cuando el parser predictivo
+// termina de reconocer el lado derecho de una producción, toma de la pila de
+// valores un valor por símbolo (los terminales aportan su token) y la acción
+// los combina en el valor del no-terminal.
+
+use crate::ast::*;
+use crate::error::{Diagnostic, DiagnosticKind};
+use crate::lexer_new::{Token, TokenType};
+
+/// Acción semántica de una producción
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Devuelve el valor del símbolo `n` del lado derecho (p. ej. `( Expresion )`)
+    Pasa(usize),
+    /// Producción ε que no aporta nada (sino, accesos, salida coaxial ausentes)
+    Nada,
+    /// Producción ε que termina una lista
+    ListaVacia,
+    /// Antepone el símbolo `n` a la lista del último símbolo (`X Lista'`)
+    Lista(usize),
+
+    Programa,
+    Biblioteca,
+    Inclusion,
+    Definiciones,
+    DefinicionesVacias,
+    Definicion,
+    DefMaquinas,
+    DefEnteros,
+    DeclConcentrador,
+    SalidaCoaxial,
+    DeclCoaxial,
+    Modulo,
+    Parametro,
+    TipoParametro(TipoParametro),
+
+    Coloca,
+    ColocaCoaxial,
+    ColocaCoaxialConcentrador,
+    UneMaquinaPuerto,
+    AsignaPuerto,
+    MaquinaCoaxial,
+    AsignaMaquinaCoaxial,
+    Escribe,
+    Si,
+    Mientras,
+    Para,
+    /// `IDENTIFICADOR SentenciaIdentificador`: llamada a módulo o asignación
+    SentenciaIdentificador,
+    Direccion(Direccion),
+
+    /// `ExpresionAnd ExpresionOr'` y similares: pliega por la izquierda
+    Logico(OpLogico),
+    Relacional,
+    OperandoRelacional,
+    Operador(OpRelacional),
+    /// `ExpresionUnaria ExpresionMultiplicativa'` y similares: pliega por la
+    /// izquierda los pares operador-operando de la lista
+    Aritmetico,
+    /// `OperadorAritmetico Operando X'`: antepone el par a la lista de `X'`
+    OperandoAritmetico,
+    OperadorAritmetico(OpAritmetico),
+    Not,
+    Negativo,
+    Numero,
+    Cadena,
+    Acceso,
+    AccesoCampo,
+    Indice,
+}
+
+/// Valor semántico de un símbolo ya reconocido
+#[derive(Debug, Clone)]
+pub enum Value {
+    Token(Token),
+    Nada,
+    Lista(Vec<Value>),
+    Programa(Program),
+    Inclusion(Inclusion),
+    Definiciones(Definitions),
+    Maquina(MaquinaDecl),
+    Concentrador(ConcentradorDecl),
+    Coaxial(CoaxialDecl),
+    Variable(VariableDecl),
+    Modulo(Modulo),
+    Parametro(Parametro),
+    TipoParametro(TipoParametro),
+    Sentencia(Statement),
+    Direccion(Direccion),
+    Expr(Expr),
+    Operador(OpRelacional),
+    /// Operador relacional y operando derecho pendientes (`OpRelacional`)
+    Relacion(OpRelacional, Expr),
+    OperadorAritmetico(OpAritmetico),
+    /// Operador aritmético y operando derecho pendientes (`ExpresionAditiva'`)
+    Operacion(OpAritmetico, Expr),
+    /// Campo y, opcionalmente, índice (`.p[1]`)
+    Campo(String, Option<Expr>),
+    /// Índice de un acceso a arreglo
+    Indice(Expr),
+}
+
+/// Extrae la variante esperada de un valor; con otra variante devuelve un
+/// `mismatch`: la acción no corresponde al lado derecho de su producción
+macro_rules! take {
+    ($value:expr, $variant:ident) => {
+        match $value {
+            Value::$variant(inner) => inner,
+            other => return Err(mismatch(stringify!($variant), &other)),
+        }
+    };
+}
+
+/// Error de una acción aplicada a valores que no son los que espera
+///
+/// Solo ocurre con una gramática cargada con `--grammar` cuya acción no encaja
+/// con su producción; el parser le agrega la producción (ver `is_mismatch`).
+fn mismatch(expected: &str, found: &Value) -> Diagnostic {
+    Diagnostic::config_error(0, 0, 0, format!("se esperaba un valor {} y se obtuvo {}", expected, found.kind()))
+}
+
+/// Indica si el error de `Action::apply` viene de una acción que no encaja con
+/// su producción (y no de un error del programa, como un número fuera de rango)
+pub fn is_mismatch(error: &Diagnostic) -> bool {
+    matches!(error.kind, DiagnosticKind::ConfigError)
+}
+
+impl Value {
+    /// Nombre de la variante, para los mensajes
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Token(_) => "Token",
+            Value::Nada => "Nada",
+            Value::Lista(_) => "Lista",
+            Value::Programa(_) => "Programa",
+            Value::Inclusion(_) => "Inclusion",
+            Value::Definiciones(_) => "Definiciones",
+            Value::Maquina(_) => "Maquina",
+            Value::Concentrador(_) => "Concentrador",
+            Value::Coaxial(_) => "Coaxial",
+            Value::Variable(_) => "Variable",
+            Value::Modulo(_) => "Modulo",
+            Value::Parametro(_) => "Parametro",
+            Value::TipoParametro(_) => "TipoParametro",
+            Value::Sentencia(_) => "Sentencia",
+            Value::Direccion(_) => "Direccion",
+            Value::Expr(_) => "Expr",
+            Value::Operador(_) => "Operador",
+            Value::Relacion(..) => "Relacion",
+            Value::OperadorAritmetico(_) => "OperadorAritmetico",
+            Value::Operacion(..) => "Operacion",
+            Value::Campo(..) => "Campo",
+            Value::Indice(_) => "Indice",
+        }
+    }
+
+    fn token(self) -> Result<Token, Diagnostic> {
+        Ok(take!(self, Token))
+    }
+
+    fn expr(self) -> Result<Expr, Diagnostic> {
+        Ok(take!(self, Expr))
+    }
+
+    fn list(self) -> Result<Vec<Value>, Diagnostic> {
+        Ok(take!(self, Lista))
+    }
+
+    /// Elementos de una lista, convertidos con `item`
+    fn list_of<T>(self, item: impl FnMut(Value) -> Result<T, Diagnostic>) -> Result<Vec<T>, Diagnostic> {
+        self.list()?.into_iter().map(item).collect()
+    }
+
+    /// Nombre de un identificador
+    fn name(self) -> Result<String, Diagnostic> {
+        Ok(self.token()?.lexeme)
+    }
+
+    /// Sentencias de un bloque
+    fn statements(self) -> Result<Vec<Statement>, Diagnostic> {
+        self.list_of(|v| Ok(take!(v, Sentencia)))
+    }
+}
+
+impl Action {
+    /// Lee una acción tal como se anota en la gramática (`Coloca`, `Pasa(1)`,
+    /// `Direccion(Arriba)`, `Operador(MenorIgual)`, ...)
+    pub fn parse(text: &str) -> Result<Action, String> {
+        let (name, argument) = match text.split_once('(') {
+            Some((name, rest)) => match rest.strip_suffix(')') {
+                Some(argument) => (name, Some(argument)),
+                None => return Err(format!("Falta ')' en la acción '{}'", text)),
+            },
+            None => (text, None),
+        };
+
+        let unknown = || format!("Acción desconocida '{}'", text);
+        let index = || argument.and_then(|a| a.parse::<usize>().ok()).ok_or_else(unknown);
+
+        let action = match (name, argument) {
+            ("Pasa", _) => Action::Pasa(index()?),
+            ("Lista", _) => Action::Lista(index()?),
+            ("Direccion", Some(argument)) => Action::Direccion(match argument {
+                "Arriba" => Direccion::Arriba,
+                "Abajo" => Direccion::Abajo,
+                "Izquierda" => Direccion::Izquierda,
+                "Derecha" => Direccion::Derecha,
+                _ => return Err(unknown()),
+            }),
+            ("TipoParametro", Some(argument)) => Action::TipoParametro(match argument {
+                "Maquina" => TipoParametro::Maquina,
+                "Concentrador" => TipoParametro::Concentrador,
+                "Coaxial" => TipoParametro::Coaxial,
+                "Entero" => TipoParametro::Entero,
+                _ => return Err(unknown()),
+            }),
+            ("Logico", Some(argument)) => Action::Logico(match argument {
+                "And" => OpLogico::And,
+                "Or" => OpLogico::Or,
+                _ => return Err(unknown()),
+            }),
+            ("Operador", Some(argument)) => Action::Operador(match argument {
+                "Igual" => OpRelacional::Igual,
+                "Diferente" => OpRelacional::Diferente,
+                "Menor" => OpRelacional::Menor,
+                "Mayor" => OpRelacional::Mayor,
+                "MenorIgual" => OpRelacional::MenorIgual,
+                "MayorIgual" => OpRelacional::MayorIgual,
+                _ => return Err(unknown()),
+            }),
+            ("OperadorAritmetico", Some(argument)) => Action::OperadorAritmetico(match argument {
+                "Suma" => OpAritmetico::Suma,
+                "Resta" => OpAritmetico::Resta,
+                "Multiplicacion" => OpAritmetico::Multiplicacion,
+                "Division" => OpAritmetico::Division,
+                "Modulo" => OpAritmetico::Modulo,
+                _ => return Err(unknown()),
+            }),
+            (_, Some(_)) => return Err(unknown()),
+            ("Nada", None) => Action::Nada,
+            ("ListaVacia", None) => Action::ListaVacia,
+            ("Programa", None) => Action::Programa,
+            ("Biblioteca", None) => Action::Biblioteca,
+            ("Inclusion", None) => Action::Inclusion,
+            ("Definiciones", None) => Action::Definiciones,
+            ("DefinicionesVacias", None) => Action::DefinicionesVacias,
+            ("Definicion", None) => Action::Definicion,
+            ("DefMaquinas", None) => Action::DefMaquinas,
+            ("DefEnteros", None) => Action::DefEnteros,
+            ("DeclConcentrador", None) => Action::DeclConcentrador,
+            ("SalidaCoaxial", None) => Action::SalidaCoaxial,
+            ("DeclCoaxial", None) => Action::DeclCoaxial,
+            ("Modulo", None) => Action::Modulo,
+            ("Parametro", None) => Action::Parametro,
+            ("Coloca", None) => Action::Coloca,
+            ("ColocaCoaxial", None) => Action::ColocaCoaxial,
+            ("ColocaCoaxialConcentrador", None) => Action::ColocaCoaxialConcentrador,
+            ("UneMaquinaPuerto", None) => Action::UneMaquinaPuerto,
+            ("AsignaPuerto", None) => Action::AsignaPuerto,
+            ("MaquinaCoaxial", None) => Action::MaquinaCoaxial,
+            ("AsignaMaquinaCoaxial", None) => Action::AsignaMaquinaCoaxial,
+            ("Escribe", None) => Action::Escribe,
+            ("Si", None) => Action::Si,
+            ("Mientras", None) => Action::Mientras,
+            ("Para", None) => Action::Para,
+            ("SentenciaIdentificador", None) => Action::SentenciaIdentificador,
+            ("Relacional", None) => Action::Relacional,
+            ("OperandoRelacional", None) => Action::OperandoRelacional,
+            ("Aritmetico", None) => Action::Aritmetico,
+            ("OperandoAritmetico", None) => Action::OperandoAritmetico,
+            ("Not", None) => Action::Not,
+            ("Negativo", None) => Action::Negativo,
+            ("Numero", None) => Action::Numero,
+            ("Cadena", None) => Action::Cadena,
+            ("Acceso", None) => Action::Acceso,
+            ("AccesoCampo", None) => Action::AccesoCampo,
+            ("Indice", None) => Action::Indice,
+            _ => return Err(unknown()),
+        };
+        Ok(action)
+    }
+
+    /// Aplica la acción a los valores del lado derecho (en orden)
+    ///
+    /// `location` es la del primer token de la producción, o la del token
+    /// siguiente si la producción es ε.
+    pub fn apply(&self, values: Vec<Value>, location: Location) -> Result<Value, Diagnostic> {
+        let mut values = values.into_iter();
+        let mut next = move || {
+            values.next().ok_or_else(|| {
+                Diagnostic::config_error(0, 0, 0, "faltan valores del lado derecho".to_string())
+            })
+        };
+
+        let value = match self {
+            Action::Pasa(n) => {
+                for _ in 0..*n {
+                    next()?;
+                }
+                next()?
+            }
+            Action::Nada => Value::Nada,
+            Action::ListaVacia => Value::Lista(Vec::new()),
+            Action::Lista(n) => {
+                for _ in 0..*n {
+                    next()?;
+                }
+                let head = next()?;
+                let mut list = vec![head];
+                list.extend(next()?.list()?);
+                Value::Lista(list)
+            }
+
+            // Programa → PROGRAMA IDENTIFICADOR ; Inclusiones Definiciones Modulos BloqueInicio .
+            Action::Programa => {
+                next()?;
+                let nombre = next()?.name()?;
+                next()?;
+                let inclusiones = next()?.list_of(|v| Ok(take!(v, Inclusion)))?;
+                let definiciones = take!(next()?, Definiciones);
+                let modulos = next()?.list_of(|v| Ok(take!(v, Modulo)))?;
+                let sentencias = next()?.statements()?;
+                Value::Programa(Program {
+                    nombre,
+                    inclusiones,
+                    definiciones,
+                    modulos,
+                    sentencias,
+                    biblioteca: false,
+                    location,
+                })
+            }
+            // Programa → Inclusiones Definiciones Modulos
+            Action::Biblioteca => {
+                let inclusiones = next()?.list_of(|v| Ok(take!(v, Inclusion)))?;
+                let definiciones = take!(next()?, Definiciones);
+                let modulos = next()?.list_of(|v| Ok(take!(v, Modulo)))?;
+                Value::Programa(Program {
+                    nombre: String::new(),
+                    inclusiones,
+                    definiciones,
+                    modulos,
+                    sentencias: Vec::new(),
+                    biblioteca: true,
+                    location,
+                })
+            }
+            // INCLUYE CADENA ;
+            Action::Inclusion => {
+                next()?;
+                let token = next()?.token()?;
+                Value::Inclusion(Inclusion {
+                    ruta: token.lexeme.trim_matches('"').to_string(),
+                    location: Location::from_token(&token),
+                })
+            }
+
+            // Definicion Definiciones: los bloques `define` pueden ir en cualquier orden
+            Action::Definiciones => {
+                let bloque = next()?.list()?;
+                let mut definiciones = take!(next()?, Definiciones);
+                for decl in bloque.into_iter().rev() {
+                    match decl {
+                        Value::Maquina(decl) => definiciones.maquinas.insert(0, decl),
+                        Value::Concentrador(decl) => definiciones.concentradores.insert(0, decl),
+                        Value::Variable(decl) => definiciones.enteros.insert(0, decl),
+                        other => definiciones.coaxiales.insert(0, take!(other, Coaxial)),
+                    }
+                }
+                definiciones.location = location;
+                Value::Definiciones(definiciones)
+            }
+            Action::DefinicionesVacias => Value::Definiciones(Definitions { location, ..Definitions::empty() }),
+
+            // DEFINE ClaseDefinicion
+            // Los comentarios /// antes de `define` documentan cada nombre de la
+            // lista que no tenga los suyos propios
+            Action::Definicion => {
+                let define_doc = next()?.token()?.doc;
+                let bloque = next()?.list_of(|decl| {
+                    Ok(match decl {
+                        Value::Maquina(mut decl) => {
+                            decl.doc = decl.doc.or_else(|| define_doc.clone());
+                            Value::Maquina(decl)
+                        }
+                        Value::Concentrador(mut decl) => {
+                            decl.doc = decl.doc.or_else(|| define_doc.clone());
+                            Value::Concentrador(decl)
+                        }
+                        Value::Variable(mut decl) => {
+                            decl.doc = decl.doc.or_else(|| define_doc.clone());
+                            Value::Variable(decl)
+                        }
+                        other => {
+                            let mut decl = take!(other, Coaxial);
+                            decl.doc = decl.doc.or_else(|| define_doc.clone());
+                            Value::Coaxial(decl)
+                        }
+                    })
+                })?;
+                Value::Lista(bloque)
+            }
+            // MAQUINAS ListaMaquinas ;
+            Action::DefMaquinas => {
+                next()?;
+                let maquinas = next()?.list_of(|v| {
+                    let token = v.token()?;
+                    Ok(Value::Maquina(MaquinaDecl {
+                        location: Location::from_token(&token),
+                        doc: token.doc,
+                        nombre: token.lexeme,
+                    }))
+                })?;
+                Value::Lista(maquinas)
+            }
+            // ENTEROS ListaMaquinas ;
+            Action::DefEnteros => {
+                next()?;
+                let enteros = next()?.list_of(|v| {
+                    let token = v.token()?;
+                    Ok(Value::Variable(VariableDecl {
+                        location: Location::from_token(&token),
+                        doc: token.doc,
+                        nombre: token.lexeme,
+                    }))
+                })?;
+                Value::Lista(enteros)
+            }
+
+            // IDENTIFICADOR = NUMERO OpcionCoaxial
+            Action::DeclConcentrador => {
+                let token = next()?.token()?;
+                next()?;
+                let puertos = number(&next()?.token()?)?;
+                let tiene_coaxial = !matches!(next()?, Value::Nada);
+                Value::Concentrador(ConcentradorDecl {
+                    nombre: token.lexeme,
+                    puertos,
+                    tiene_coaxial,
+                    doc: token.doc,
+                    location,
+                })
+            }
+            // . NUMERO: solo `.1` indica salida coaxial
+            Action::SalidaCoaxial => {
+                next()?;
+                let token = next()?.token()?;
+                if token.lexeme != "1" {
+                    return Err(Diagnostic::syntax_error(
+                        token.line,
+                        token.column,
+                        token.length,
+                        "Después del punto debe ir 1 para indicar salida coaxial".to_string(),
+                    ));
+                }
+                Value::Token(token)
+            }
+            Action::DeclCoaxial => {
+                let token = next()?.token()?;
+                next()?;
+                let longitud = number(&next()?.token()?)?;
+                Value::Coaxial(CoaxialDecl { nombre: token.lexeme, longitud, doc: token.doc, location })
+            }
+
+            // MODULO IDENTIFICADOR ParametrosModulo ; BloqueInicio
+            Action::Modulo => {
+                let doc = next()?.token()?.doc;
+                let nombre = next()?.name()?;
+                let parametros = next()?.list_of(|v| Ok(take!(v, Parametro)))?;
+                next()?;
+                let sentencias = next()?.statements()?;
+                Value::Modulo(Modulo { nombre, parametros, sentencias, doc, location })
+            }
+            // TipoParametro IDENTIFICADOR
+            Action::Parametro => {
+                let tipo = take!(next()?, TipoParametro);
+                let token = next()?.token()?;
+                Value::Parametro(Parametro { location: Location::from_token(&token), nombre: token.lexeme, tipo })
+            }
+            Action::TipoParametro(tipo) => Value::TipoParametro(*tipo),
+
+            Action::Coloca => {
+                let [_, _, objeto, _, x, _, y] = first(&mut next)?;
+                Value::Sentencia(Statement::Coloca { objeto: objeto.name()?, x: x.expr()?, y: y.expr()?, location })
+            }
+            Action::ColocaCoaxial => {
+                let [_, _, coaxial, _, x, _, y, _, direccion] = first(&mut next)?;
+                Value::Sentencia(Statement::ColocaCoaxial {
+                    coaxial: coaxial.name()?,
+                    x: x.expr()?,
+                    y: y.expr()?,
+                    direccion: take!(direccion, Direccion),
+                    location,
+                })
+            }
+            Action::ColocaCoaxialConcentrador => {
+                let [_, _, coaxial, _, concentrador] = first(&mut next)?;
+                Value::Sentencia(Statement::ColocaCoaxialConcentrador {
+                    coaxial: coaxial.name()?,
+                    concentrador: concentrador.name()?,
+                    location,
+                })
+            }
+            Action::UneMaquinaPuerto => {
+                let [_, _, maquina, _, concentrador, _, puerto] = first(&mut next)?;
+                Value::Sentencia(Statement::UneMaquinaPuerto {
+                    maquina: maquina.name()?,
+                    concentrador: concentrador.name()?,
+                    puerto: puerto.expr()?,
+                    location,
+                })
+            }
+            Action::AsignaPuerto => {
+                let [_, _, maquina, _, concentrador] = first(&mut next)?;
+                Value::Sentencia(Statement::AsignaPuerto {
+                    maquina: maquina.name()?,
+                    concentrador: concentrador.name()?,
+                    location,
+                })
+            }
+            Action::MaquinaCoaxial => {
+                let [_, _, maquina, _, coaxial, _, posicion] = first(&mut next)?;
+                Value::Sentencia(Statement::MaquinaCoaxial {
+                    maquina: maquina.name()?,
+                    coaxial: coaxial.name()?,
+                    posicion: posicion.expr()?,
+                    location,
+                })
+            }
+            Action::AsignaMaquinaCoaxial => {
+                let [_, _, maquina, _, coaxial] = first(&mut next)?;
+                Value::Sentencia(Statement::AsignaMaquinaCoaxial {
+                    maquina: maquina.name()?,
+                    coaxial: coaxial.name()?,
+                    location,
+                })
+            }
+            Action::Escribe => {
+                let [_, _, contenido] = first(&mut next)?;
+                Value::Sentencia(Statement::Escribe { contenido: contenido.expr()?, location })
+            }
+            // SI Expresion INICIO Sentencias FIN OpcionSino
+            // Un `sino si` llega como otro Si: sus ramas se suben a esta para
+            // que la cadena quede plana
+            Action::Si => {
+                let [_, condicion, _, entonces, _, sino] = first(&mut next)?;
+                let (sino_si, sino) = match sino {
+                    Value::Nada => (Vec::new(), None),
+                    Value::Sentencia(Statement::Si { condicion, entonces, sino_si, sino, location }) => {
+                        let mut ramas = vec![RamaSi { condicion, sentencias: entonces, location }];
+                        ramas.extend(sino_si);
+                        (ramas, sino)
+                    }
+                    bloque => (Vec::new(), Some(bloque.statements()?)),
+                };
+                Value::Sentencia(Statement::Si {
+                    condicion: condicion.expr()?,
+                    entonces: entonces.statements()?,
+                    sino_si,
+                    sino,
+                    location,
+                })
+            }
+            // MIENTRAS Expresion INICIO Sentencias FIN
+            Action::Mientras => {
+                let [_, condicion, _, cuerpo] = first(&mut next)?;
+                Value::Sentencia(Statement::Mientras {
+                    condicion: condicion.expr()?,
+                    cuerpo: cuerpo.statements()?,
+                    location,
+                })
+            }
+            // PARA IDENTIFICADOR IGUAL Expresion HASTA Expresion INICIO Sentencias FIN
+            Action::Para => {
+                let [_, variable, _, desde, _, hasta, _, cuerpo] = first(&mut next)?;
+                Value::Sentencia(Statement::Para {
+                    variable: variable.name()?,
+                    desde: desde.expr()?,
+                    hasta: hasta.expr()?,
+                    cuerpo: cuerpo.statements()?,
+                    location,
+                })
+            }
+            // `nombre;` deja Nada, `nombre(args);` la lista de argumentos y
+            // `x = expr;` la expresión
+            Action::SentenciaIdentificador => {
+                let nombre = next()?.name()?;
+                match next()? {
+                    Value::Expr(valor) => Value::Sentencia(Statement::Asignacion { variable: nombre, valor, location }),
+                    Value::Lista(argumentos) => Value::Sentencia(Statement::LlamadaModulo {
+                        nombre,
+                        argumentos: argumentos.into_iter().map(Value::expr).collect::<Result<_, _>>()?,
+                        location,
+                    }),
+                    _ => Value::Sentencia(Statement::LlamadaModulo { nombre, argumentos: Vec::new(), location }),
+                }
+            }
+            Action::Direccion(direccion) => Value::Direccion(direccion.clone()),
+
+            // Operando seguido de la lista de operandos de `op`
+            Action::Logico(op) => {
+                let mut izq = next()?.expr()?;
+                for der in next()?.list()? {
+                    izq = Expr::Logico { izq: Box::new(izq), op: op.clone(), der: Box::new(der.expr()?) };
+                }
+                Value::Expr(izq)
+            }
+            Action::Relacional => {
+                let izq = next()?.expr()?;
+                match next()? {
+                    Value::Relacion(op, der) => {
+                        Value::Expr(Expr::Relacional { izq: Box::new(izq), op, der: Box::new(der) })
+                    }
+                    _ => Value::Expr(izq),
+                }
+            }
+            Action::OperandoRelacional => {
+                let op = take!(next()?, Operador);
+                Value::Relacion(op, next()?.expr()?)
+            }
+            Action::Operador(op) => Value::Operador(op.clone()),
+            Action::Aritmetico => {
+                let mut izq = next()?.expr()?;
+                for operacion in next()?.list()? {
+                    let (op, der) = match operacion {
+                        Value::Operacion(op, der) => (op, der),
+                        other => return Err(mismatch("Operacion", &other)),
+                    };
+                    izq = Expr::Aritmetico { izq: Box::new(izq), op, der: Box::new(der) };
+                }
+                Value::Expr(izq)
+            }
+            Action::OperandoAritmetico => {
+                let op = take!(next()?, OperadorAritmetico);
+                let der = next()?.expr()?;
+                let mut list = vec![Value::Operacion(op, der)];
+                list.extend(next()?.list()?);
+                Value::Lista(list)
+            }
+            Action::OperadorAritmetico(op) => Value::OperadorAritmetico(op.clone()),
+            Action::Not => {
+                next()?;
+                Value::Expr(Expr::Not(Box::new(next()?.expr()?)))
+            }
+            Action::Negativo => {
+                next()?;
+                Value::Expr(Expr::Negativo(Box::new(next()?.expr()?)))
+            }
+            Action::Numero => Value::Expr(Expr::Numero(number(&next()?.token()?)?)),
+            // Cadena sin comillas
+            Action::Cadena => Value::Expr(Expr::Cadena(next()?.name()?.trim_matches('"').to_string())),
+
+            // IDENTIFICADOR Accesos
+            Action::Acceso => {
+                let objeto = next()?.name()?;
+                let expr = match next()? {
+                    // obj.p[1] se representa como acceso al arreglo "obj.p"
+                    Value::Campo(campo, Some(indice)) => Expr::AccesoArreglo {
+                        objeto: format!("{}.{}", objeto, campo),
+                        indice: Box::new(indice),
+                    },
+                    Value::Campo(campo, None) => Expr::AccesoCampo { objeto, campo },
+                    Value::Indice(indice) => Expr::AccesoArreglo { objeto, indice: Box::new(indice) },
+                    _ => Expr::Identificador(objeto),
+                };
+                Value::Expr(expr)
+            }
+            // . IDENTIFICADOR AccesoArreglo
+            Action::AccesoCampo => {
+                next()?;
+                let campo = field_name(next()?.token()?);
+                let indice = match next()? {
+                    Value::Indice(indice) => Some(indice),
+                    _ => None,
+                };
+                Value::Campo(campo, indice)
+            }
+            // [ Expresion ]
+            Action::Indice => {
+                next()?;
+                Value::Indice(next()?.expr()?)
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+/// Los primeros `N` valores del lado derecho
+fn first<const N: usize>(next: &mut impl FnMut() -> Result<Value, Diagnostic>) -> Result<[Value; N], Diagnostic> {
+    let mut error = None;
+    let values = std::array::from_fn(|_| next().unwrap_or_else(|e| {
+        error.get_or_insert(e);
+        Value::Nada
+    }));
+    match error {
+        Some(error) => Err(error),
+        None => Ok(values),
+    }
+}
+
+/// Valor de un token NUMERO
+fn number(token: &Token) -> Result<i32, Diagnostic> {
+    token.lexeme.parse::<i32>().map_err(|_| {
+        Diagnostic::syntax_error(
+            token.line,
+            token.column,
+            token.length,
+            format!("El número {} está fuera de rango", token.lexeme),
+        )
+    })
+}
+
+/// Nombre de campo: un identificador o una palabra reservada que puede usarse
+/// como campo (`uno.coaxial`), que se nombra siempre en español
+fn field_name(token: Token) -> String {
+    match token.token_type {
+        TokenType::Identifier => token.lexeme,
+        keyword => keyword.as_str().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(token_type: TokenType, lexeme: &str) -> Value {
+        Value::Token(Token::new(token_type, lexeme.to_string(), 1, 1))
+    }
+
+    #[test]
+    fn test_logico_folds_left() {
+        let values = vec![
+            Value::Expr(Expr::Numero(1)),
+            Value::Lista(vec![Value::Expr(Expr::Numero(2)), Value::Expr(Expr::Numero(3))]),
+        ];
+        let value = Action::Logico(OpLogico::Or).apply(values, Location::unknown()).unwrap();
+
+        // (1 || 2) || 3
+        match value {
+            Value::Expr(Expr::Logico { izq, der, .. }) => {
+                assert!(matches!(*izq, Expr::Logico { .. }));
+                assert!(matches!(*der, Expr::Numero(3)));
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_aritmetico_folds_left() {
+        // Lista de `+ 2 - 3`
+        let operando = |op: OpAritmetico, n: i32, resto: Value| {
+            let values = vec![Value::OperadorAritmetico(op), Value::Expr(Expr::Numero(n)), resto];
+            Action::OperandoAritmetico.apply(values, Location::unknown()).unwrap()
+        };
+        let lista = operando(OpAritmetico::Suma, 2, operando(OpAritmetico::Resta, 3, Value::Lista(Vec::new())));
+
+        let values = vec![Value::Expr(Expr::Numero(1)), lista];
+        let value = Action::Aritmetico.apply(values, Location::unknown()).unwrap();
+
+        // (1 + 2) - 3
+        match value {
+            Value::Expr(Expr::Aritmetico { izq, op: OpAritmetico::Resta, der }) => {
+                assert!(matches!(*izq, Expr::Aritmetico { op: OpAritmetico::Suma, .. }));
+                assert!(matches!(*der, Expr::Numero(3)));
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_campo_with_index_and_keyword_field() {
+        let campo = Value::Campo("p".to_string(), Some(Expr::Numero(1)));
+        let value = Action::Acceso.apply(vec![token(TokenType::Identifier, "uno"), campo], Location::unknown());
+        assert!(matches!(
+            value.unwrap(),
+            Value::Expr(Expr::AccesoArreglo { objeto, .. }) if objeto == "uno.p"
+        ));
+
+        let values = vec![token(TokenType::Dot, "."), token(TokenType::Coaxial, "coaxial"), Value::Nada];
+        let value = Action::AccesoCampo.apply(values, Location::unknown()).unwrap();
+        assert!(matches!(value, Value::Campo(campo, None) if campo == "coaxial"));
+    }
+
+    #[test]
+    fn test_number_out_of_range() {
+        let err = Action::Numero
+            .apply(vec![token(TokenType::Number, "99999999999")], Location::unknown())
+            .unwrap_err();
+        assert!(err.message.contains("fuera de rango"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_action_names() {
+        assert_eq!(Action::parse("Coloca"), Ok(Action::Coloca));
+        assert_eq!(Action::parse("Pasa(2)"), Ok(Action::Pasa(2)));
+        assert_eq!(Action::parse("Operador(MenorIgual)"), Ok(Action::Operador(OpRelacional::MenorIgual)));
+        assert_eq!(Action::parse("Direccion(Arriba)"), Ok(Action::Direccion(Direccion::Arriba)));
+        assert_eq!(
+            Action::parse("OperadorAritmetico(Modulo)"),
+            Ok(Action::OperadorAritmetico(OpAritmetico::Modulo))
+        );
+
+        assert_eq!(Action::parse("Pasa(x)"), Err("Acción desconocida 'Pasa(x)'".to_string()));
+        assert_eq!(Action::parse("Coloca(1)"), Err("Acción desconocida 'Coloca(1)'".to_string()));
+        assert!(Action::parse("Lista(0").unwrap_err().contains("Falta ')'"));
+    }
+}
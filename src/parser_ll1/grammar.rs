@@ -0,0 +1,381 @@
+// src/parser_ll1/grammar.rs
+// Gramática LL(1) leída de un archivo con el formato de docs/gramatica.txt
+//
+// Solo cuentan las líneas de producción:
+//
+//     [41] SentenciaColoca → COLOCA PAREN_IZQ IDENTIFICADOR ... PUNTO_COMA  {Coloca}
+//
+// El resto del archivo (encabezados, lista de terminales, notas) se ignora.
+// Los no-terminales son los nombres que aparecen como lado izquierdo; los
+// terminales, los de `TERMINALS` (tokens del lexer).
+
+use super::actions::Action;
+use super::first_follow::{NonTerminal, Symbol};
+use crate::error::Diagnostic;
+use crate::lexer_new::TokenType;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Representa una producción de la gramática
+#[derive(Debug, Clone, PartialEq)]
+pub struct Production {
+    pub id: usize,
+    pub lhs: NonTerminal,
+    pub rhs: Vec<Symbol>,
+    /// Acción semántica que construye el valor de `lhs` a partir del de `rhs`
+    pub action: Action,
+}
+
+/// Nombres de los terminales en la gramática
+const TERMINALS: &[(&str, TokenType)] = &[
+    ("PROGRAMA", TokenType::Programa),
+    ("DEFINE", TokenType::Define),
+    ("MAQUINAS", TokenType::Maquinas),
+    ("CONCENTRADORES", TokenType::Concentradores),
+    ("COAXIAL", TokenType::Coaxial),
+    ("SEGMENTO", TokenType::Segmento),
+    ("ENTEROS", TokenType::Enteros),
+    ("MODULO", TokenType::Modulo),
+    ("INICIO", TokenType::Inicio),
+    ("FIN", TokenType::Fin),
+    ("SI", TokenType::Si),
+    ("SINO", TokenType::Sino),
+    ("MIENTRAS", TokenType::Mientras),
+    ("PARA", TokenType::Para),
+    ("HASTA", TokenType::Hasta),
+    ("MAQUINA", TokenType::Maquina),
+    ("CONCENTRADOR", TokenType::Concentrador),
+    ("ENTERO", TokenType::Entero),
+    ("INCLUYE", TokenType::Incluye),
+    ("COLOCA", TokenType::Coloca),
+    ("COLOCA_COAXIAL", TokenType::ColocaCoaxial),
+    ("COLOCA_COAXIAL_CONCENTRADOR", TokenType::ColocaCoaxialConcentrador),
+    ("UNE_MAQUINA_PUERTO", TokenType::UneMaquinaPuerto),
+    ("ASIGNA_PUERTO", TokenType::AsignaPuerto),
+    ("MAQUINA_COAXIAL", TokenType::MaquinaCoaxial),
+    ("ASIGNA_MAQUINA_COAXIAL", TokenType::AsignaMaquinaCoaxial),
+    ("ESCRIBE", TokenType::Escribe),
+    ("ARRIBA", TokenType::Arriba),
+    ("ABAJO", TokenType::Abajo),
+    ("IZQUIERDA", TokenType::Izquierda),
+    ("DERECHA", TokenType::Derecha),
+    ("IGUAL", TokenType::Equal),
+    ("MENOR", TokenType::Less),
+    ("MAYOR", TokenType::Greater),
+    ("MENOR_IGUAL", TokenType::LessEqual),
+    ("MAYOR_IGUAL", TokenType::GreaterEqual),
+    ("DIFERENTE", TokenType::NotEqual),
+    ("AND", TokenType::And),
+    ("OR", TokenType::Or),
+    ("NOT", TokenType::Not),
+    ("MAS", TokenType::Plus),
+    ("MENOS", TokenType::Minus),
+    ("POR", TokenType::Star),
+    ("DIV", TokenType::Slash),
+    ("MOD", TokenType::Percent),
+    ("COMA", TokenType::Comma),
+    ("PUNTO_COMA", TokenType::Semicolon),
+    ("PUNTO", TokenType::Dot),
+    ("PAREN_IZQ", TokenType::LParen),
+    ("PAREN_DER", TokenType::RParen),
+    ("CORCHETE_IZQ", TokenType::LBracket),
+    ("CORCHETE_DER", TokenType::RBracket),
+    ("IDENTIFICADOR", TokenType::Identifier),
+    ("NUMERO", TokenType::Number),
+    ("CADENA", TokenType::String),
+    ("EOF", TokenType::Eof),
+];
+
+/// Terminal con el nombre `name` en la gramática (`PUNTO_COMA`, `COLOCA`, ...)
+pub fn terminal(name: &str) -> Option<TokenType> {
+    TERMINALS.iter().find(|(n, _)| *n == name).map(|(_, t)| t.clone())
+}
+
+/// Nombre de un terminal en la gramática (el del lexer si no aparece en ella)
+pub fn terminal_name(token: &TokenType) -> &'static str {
+    TERMINALS
+        .iter()
+        .find(|(_, t)| t == token)
+        .map(|(name, _)| *name)
+        .unwrap_or_else(|| token.as_str())
+}
+
+/// Nombre de un símbolo en la gramática
+pub fn symbol_name(symbol: &Symbol) -> &'static str {
+    match symbol {
+        Symbol::Terminal(token) => terminal_name(token),
+        Symbol::NonTerminal(nt) => nt.as_str(),
+        Symbol::Epsilon => "ε",
+        Symbol::Eof => "EOF",
+    }
+}
+
+impl fmt::Display for Production {
+    /// `[n] A → α` con los nombres de la gramática
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {} →", self.id, self.lhs.as_str())?;
+        for symbol in &self.rhs {
+            write!(f, " {}", symbol_name(symbol))?;
+        }
+        Ok(())
+    }
+}
+
+/// Gramática: producciones en el orden del archivo; la primera define el
+/// símbolo inicial
+#[derive(Debug, Clone)]
+pub struct Grammar {
+    productions: Vec<Production>,
+    /// Línea del archivo de cada producción (por número)
+    lines: HashMap<usize, usize>,
+}
+
+impl Grammar {
+    /// Lee las producciones de una gramática
+    ///
+    /// Los no-terminales salen del propio archivo: un símbolo del lado derecho
+    /// que no es terminal debe tener producciones.
+    pub fn parse(source: &str) -> Result<Grammar, Diagnostic> {
+        let production_lines: Vec<(usize, Vec<(usize, &str)>)> = source
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, words(line)))
+            .filter(|(_, words)| words.first().is_some_and(|(_, word)| word.starts_with('[')))
+            .collect();
+
+        // Primera pasada: lados izquierdos
+        let mut nonterminals: HashSet<&str> = HashSet::new();
+        for (number, words) in &production_lines {
+            if let Some(&(column, name)) = words.get(1) {
+                if terminal(name).is_some() || name == "ε" || name == "EOF" {
+                    return Err(error(
+                        *number,
+                        column,
+                        name,
+                        format!("'{}' es un terminal y no puede ser lado izquierdo de una producción", name),
+                    ));
+                }
+                nonterminals.insert(name);
+            }
+        }
+
+        let mut productions: Vec<Production> = Vec::new();
+        let mut lines: HashMap<usize, usize> = HashMap::new();
+
+        for (number, words) in &production_lines {
+            let number = *number;
+            let production = parse_production(words, number, &nonterminals)?;
+            if let Some(previous) = lines.insert(production.id, number) {
+                let (column, word) = words[0];
+                return Err(error(
+                    number,
+                    column,
+                    word,
+                    format!("La producción [{}] ya está definida en la línea {}", production.id, previous),
+                ));
+            }
+            productions.push(production);
+        }
+
+        if productions.is_empty() {
+            return Err(Diagnostic::config_error(0, 0, 0, "La gramática no tiene producciones".to_string()));
+        }
+
+        Ok(Grammar { productions, lines })
+    }
+
+    /// Lee una gramática desde un archivo
+    pub fn from_file(path: &str) -> Result<Grammar, Diagnostic> {
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            Diagnostic::config_error(0, 0, 0, format!("Error leyendo la gramática {}: {}", path, e))
+        })?;
+        Self::parse(&source)
+    }
+
+    /// Producciones en el orden del archivo
+    pub fn productions(&self) -> &[Production] {
+        &self.productions
+    }
+
+    /// Producción con el número `id`
+    pub fn production(&self, id: usize) -> Option<&Production> {
+        self.productions.iter().find(|p| p.id == id)
+    }
+
+    /// Línea del archivo donde está la producción `id` (0 si no existe)
+    pub fn line(&self, id: usize) -> usize {
+        self.lines.get(&id).copied().unwrap_or(0)
+    }
+
+    /// Símbolo inicial (lado izquierdo de la primera producción)
+    pub fn start(&self) -> NonTerminal {
+        self.productions[0].lhs
+    }
+
+    /// No-terminales con producciones, en orden de aparición
+    pub fn nonterminals(&self) -> Vec<NonTerminal> {
+        let mut nonterminals = Vec::new();
+        for production in &self.productions {
+            if !nonterminals.contains(&production.lhs) {
+                nonterminals.push(production.lhs);
+            }
+        }
+        nonterminals
+    }
+}
+
+/// Palabras de una línea con su columna (1-based, en caracteres)
+fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+
+    for (column, (offset, ch)) in line.char_indices().enumerate() {
+        match (ch.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, offset)),
+            (true, Some((col, from))) => {
+                words.push((col, &line[from..offset]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((col, from)) = start {
+        words.push((col, &line[from..]));
+    }
+    words
+}
+
+fn error(line: usize, column: usize, word: &str, message: String) -> Diagnostic {
+    Diagnostic::config_error(line, column, word.chars().count(), message)
+}
+
+/// `[n] A → α  {Accion}`, donde los no-terminales son los de `nonterminals`
+fn parse_production(
+    words: &[(usize, &str)],
+    line: usize,
+    nonterminals: &HashSet<&str>,
+) -> Result<Production, Diagnostic> {
+    let (column, label) = words[0];
+    let id = label
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .and_then(|n| n.parse::<usize>().ok())
+        .ok_or_else(|| error(line, column, label, format!("Número de producción inválido '{}'", label)))?;
+
+    let (column, name) = *words
+        .get(1)
+        .ok_or_else(|| error(line, column, label, format!("La producción [{}] no tiene lado izquierdo", id)))?;
+    let lhs = NonTerminal::new(name);
+
+    match words.get(2) {
+        Some((_, "→" | "->")) => {}
+        _ => return Err(error(line, column, name, format!("Se esperaba '→' después de '{}'", name))),
+    }
+
+    let mut symbols = &words[3..];
+    let mut action = Action::Nada;
+    if let Some(((column, word), rest)) = symbols.split_last()
+        && let Some(text) = word.strip_prefix('{')
+    {
+        let text = text.strip_suffix('}').unwrap_or(text);
+        action = Action::parse(text).map_err(|message| error(line, *column, word, message))?;
+        symbols = rest;
+    }
+
+    if symbols.is_empty() {
+        return Err(error(line, column, name, format!("La producción [{}] no tiene lado derecho (use ε)", id)));
+    }
+
+    let mut rhs = Vec::new();
+    for &(column, word) in symbols {
+        let symbol = if word == "ε" {
+            if symbols.len() > 1 {
+                return Err(error(line, column, word, "ε debe ser el único símbolo del lado derecho".to_string()));
+            }
+            Symbol::Epsilon
+        } else if let Some(token) = terminal(word) {
+            Symbol::Terminal(token)
+        } else if nonterminals.contains(word) {
+            Symbol::NonTerminal(NonTerminal::new(word))
+        } else {
+            return Err(error(
+                line,
+                column,
+                word,
+                format!("Símbolo desconocido '{}': no es un terminal ni tiene producciones", word),
+            ));
+        };
+        rhs.push(symbol);
+    }
+
+    Ok(Production { id, lhs, rhs, action })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_productions() {
+        let grammar = Grammar::parse(
+            "Comentarios y encabezados\n\
+             [1] Programa → PROGRAMA IDENTIFICADOR PUNTO_COMA BloqueInicio PUNTO  {Programa}\n\
+             \n\
+             [2] BloqueInicio → INICIO Sentencias FIN  {Pasa(1)}\n\
+             [3] Sentencias → ε\n",
+        )
+        .unwrap();
+
+        assert_eq!(grammar.start(), NonTerminal::new("Programa"));
+        assert_eq!(grammar.productions().len(), 3);
+        assert_eq!(grammar.production(2).unwrap().action, Action::Pasa(1));
+        assert_eq!(grammar.production(3).unwrap().rhs, vec![Symbol::Epsilon]);
+        assert_eq!(grammar.production(3).unwrap().action, Action::Nada);
+        assert_eq!(
+            grammar.production(1).unwrap().rhs[..2],
+            [Symbol::Terminal(TokenType::Programa), Symbol::Terminal(TokenType::Identifier)]
+        );
+    }
+
+    #[test]
+    fn test_nonterminals_come_from_the_file() {
+        // Un no-terminal que el código no conoce: basta con que tenga producciones
+        let grammar = Grammar::parse(
+            "[1] Programa → PROGRAMA Bloque' PUNTO\n\
+             [2] Bloque' → INICIO FIN\n",
+        )
+        .unwrap();
+
+        let bloque = NonTerminal::new("Bloque'");
+        assert_eq!(grammar.nonterminals(), vec![NonTerminal::new("Programa"), bloque]);
+        assert_eq!(grammar.production(1).unwrap().rhs[1], Symbol::NonTerminal(bloque));
+        assert_eq!(bloque.as_str(), "Bloque'");
+
+        // Usado pero sin producciones
+        let err = Grammar::parse("[1] Programa → PROGRAMA Bloque PUNTO\n").unwrap_err();
+        assert_eq!(err.message, "Símbolo desconocido 'Bloque': no es un terminal ni tiene producciones");
+        assert_eq!((err.line, err.column), (1, 25));
+    }
+
+    #[test]
+    fn test_parse_errors_point_to_symbol() {
+        let err = Grammar::parse("[1] Programa → PROGRAMA PUNTOYCOMA\n").unwrap_err();
+        assert_eq!(err.message, "Símbolo desconocido 'PUNTOYCOMA': no es un terminal ni tiene producciones");
+        assert_eq!((err.line, err.column, err.length), (1, 25, 10));
+
+        let err = Grammar::parse("[1] COMA → ε\n").unwrap_err();
+        assert_eq!(err.message, "'COMA' es un terminal y no puede ser lado izquierdo de una producción");
+
+        let err = Grammar::parse("[1] Programa → ε\n[1] Programa → ε  {Accion}\n").unwrap_err();
+        assert_eq!(err.message, "Acción desconocida 'Accion'");
+        assert_eq!((err.line, err.column), (2, 19));
+
+        let err = Grammar::parse("[1] Programa → ε\n[1] Programa → ε\n").unwrap_err();
+        assert_eq!(err.message, "La producción [1] ya está definida en la línea 1");
+
+        let err = Grammar::parse("[1] Programa = ε\n").unwrap_err();
+        assert_eq!(err.message, "Se esperaba '→' después de 'Programa'");
+
+        assert!(Grammar::parse("sin producciones\n").is_err());
+    }
+}
@@ -1,10 +1,18 @@
 // src/parser_ll1/mod.rs
 // Parser LL(1) predictivo con tabla
 
+pub mod actions;
 pub mod first_follow;
+pub mod grammar;
 pub mod ll1_table;
 pub mod predictive;
+pub mod trace;
+pub mod validate;
 
+pub use actions::{Action, Value};
 pub use first_follow::{FirstFollowSets, NonTerminal, Symbol};
-pub use ll1_table::{LL1Table, Production, TokenClass};
+pub use grammar::{Grammar, Production};
+pub use ll1_table::LL1Table;
 pub use predictive::PredictiveParser;
+pub use trace::{ParseTrace, TraceAction, TraceStep};
+pub use validate::{GrammarIssue, GrammarIssueKind};
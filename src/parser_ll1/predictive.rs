@@ -1,41 +1,107 @@
 // src/parser_ll1/predictive.rs
 // Parser LL(1) predictivo con pila explícita
 // Este parser NO usa recursión, usa una pila explícita para implementar
-// el análisis predictivo según la tabla LL(1)
-
-use crate::lexer::{Token, TokenInfo};
-use crate::ast::Program;
-use crate::parser::Parser as RecursiveParser;
-use super::first_follow::{Symbol, NonTerminal};
-use super::ll1_table::{LL1Table, TokenClass};
+// el análisis predictivo según la tabla LL(1), y construye el AST en la
+// misma pasada con las acciones semánticas de cada producción
+
+use crate::lexer_new::{Dialect, Token, TokenType};
+use crate::ast::{Location, Program};
+use crate::config::load_grammar;
+use crate::error::Diagnostic;
+use super::actions::{self, Value};
+use super::first_follow::{FirstFollowSets, Symbol, NonTerminal};
+use super::grammar::{Grammar, symbol_name, terminal_name};
+use super::ll1_table::LL1Table;
+use super::trace::{ParseTrace, TraceAction, TraceStep};
+
+/// Elemento de la pila del parser
+#[derive(Debug, Clone)]
+enum StackEntry {
+    /// Símbolo de la gramática por reconocer
+    Symbol(Symbol),
+    /// Fin del lado derecho de la producción `production`, que empezó en el
+    /// token `start`: toca aplicar su acción semántica
+    Reduce { production: usize, start: usize },
+}
 
 /// Parser LL(1) predictivo
 pub struct PredictiveParser {
     table: LL1Table,
-    tokens: Vec<TokenInfo>,
+    sets: FirstFollowSets,
+    /// Símbolo inicial de la gramática
+    start: NonTerminal,
+    tokens: Vec<Token>,
     position: usize,
-    stack: Vec<Symbol>,
-    errors: Vec<String>,
+    stack: Vec<StackEntry>,
+    /// Valores semánticos de los símbolos ya reconocidos
+    values: Vec<Value>,
+    errors: Vec<Diagnostic>,
+    /// Recuperándose de un error: no se reportan los siguientes
+    panic_mode: bool,
+    dialect: Dialect,
+    /// Pasos del último análisis, si se pidió la traza
+    trace: Option<ParseTrace>,
 }
 
 impl PredictiveParser {
     /// Crea un nuevo parser predictivo
-    pub fn new(tokens: Vec<TokenInfo>) -> Self {
-        Self {
+    pub fn new(tokens: Vec<Token>) -> Self {
+        let mut parser = Self {
             table: LL1Table::new(),
+            sets: FirstFollowSets::new(),
+            start: load_grammar().start(),
             tokens,
             position: 0,
-            stack: vec![Symbol::Eof, Symbol::NonTerminal(NonTerminal::Programa)],
+            stack: Vec::new(),
+            values: Vec::new(),
             errors: Vec::new(),
-        }
+            panic_mode: false,
+            dialect: Dialect::default(),
+            trace: None,
+        };
+        parser.stack = parser.initial_stack();
+        parser
+    }
+
+    /// Usa las palabras reservadas del dialecto en los mensajes de error
+    pub fn with_dialect(mut self, dialect: &Dialect) -> Self {
+        self.dialect = dialect.clone();
+        self
+    }
+
+    /// Analiza con `grammar` en lugar de la gramática activa
+    pub fn with_grammar(mut self, grammar: &Grammar) -> Self {
+        self.table = LL1Table::from_grammar(grammar);
+        self.sets = FirstFollowSets::from_grammar(grammar);
+        self.start = grammar.start();
+        self.stack = self.initial_stack();
+        self
+    }
+
+    /// Registra cada paso del análisis (ver `trace`)
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(ParseTrace::new());
+        self
+    }
+
+    /// Traza del último análisis; `None` si no se activó con `with_trace`
+    pub fn trace(&self) -> Option<&ParseTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Pila inicial: $ y el símbolo inicial
+    fn initial_stack(&self) -> Vec<StackEntry> {
+        vec![
+            StackEntry::Symbol(Symbol::Eof),
+            StackEntry::Symbol(Symbol::NonTerminal(self.start)),
+        ]
     }
 
     /// Obtiene el token actual
-    fn current_token(&self) -> &Token {
-        if self.position < self.tokens.len() {
-            &self.tokens[self.position].token
-        } else {
-            &Token::Punto // EOF representado como Punto final
+    fn current_token(&self) -> &TokenType {
+        match self.tokens.get(self.position) {
+            Some(token) => &token.token_type,
+            None => &TokenType::Eof,
         }
     }
 
@@ -47,180 +113,353 @@ impl PredictiveParser {
     }
 
     /// Registra un error
-    fn error(&mut self, msg: String) {
-        self.errors.push(msg);
+    fn error(&mut self, diagnostic: Diagnostic) {
+        self.errors.push(diagnostic);
     }
 
-    /// Parser principal - Two-pass approach:
-    /// Pass 1: LL(1) predictive parser validates syntax with parsing table
-    /// Pass 2: Recursive descent parser builds AST
-    ///
-    /// This approach combines the strengths of both parsers:
-    /// - LL(1) ensures rigorous validation against the formal grammar
-    /// - Recursive descent provides clean AST construction
-    pub fn parse(&mut self) -> Result<Program, String> {
-        println!("🔍 Iniciando análisis híbrido (Two-Pass Approach)");
-        println!("   Pass 1: Validación de sintaxis LL(1)");
-        println!("   Pass 2: Construcción de AST con parser recursivo");
-
-        // PASS 1: Validate syntax using LL(1) predictive parser
-        self.validate_syntax()?;
-
-        println!("   ✅ Pass 1 completado - Sintaxis válida");
-        println!("   🔨 Pass 2: Construyendo AST...");
-
-        // PASS 2: Build AST using recursive descent parser
-        let mut recursive_parser = RecursiveParser::new(self.tokens.clone());
-        match recursive_parser.parse() {
-            Ok(program) => {
-                println!("   ✅ Pass 2 completado - AST construido exitosamente");
-                println!("✨ Análisis híbrido completado con éxito\n");
-                Ok(program)
-            }
-            Err(errors) => {
-                // This shouldn't happen if Pass 1 succeeded
-                let error_msgs: Vec<String> = errors.iter()
-                    .map(|e| format!("  - {}", e.message))
-                    .collect();
-                Err(format!(
-                    "Error inesperado en Pass 2 (construcción de AST):\n{}",
-                    error_msgs.join("\n")
-                ))
-            }
+    /// Analiza el programa y construye su AST en una sola pasada
+    pub fn parse(&mut self) -> Result<Program, Vec<Diagnostic>> {
+        match self.run(true)? {
+            Some(Value::Programa(program)) => Ok(program),
+            other => Err(vec![Self::internal_error(format!("el análisis terminó con {:?}", other))]),
         }
     }
 
-    /// Validates syntax only using LL(1) predictive algorithm
+    /// Solo valida la sintaxis, sin construir el AST
     ///
     /// Algoritmo LL(1) predictivo:
     /// 1. Inicializar pila con $ (EOF) y símbolo inicial
-    /// 2. Mientras la pila no esté vacía:
-    ///    a. Sea X el tope de la pila
-    ///    b. Sea a el token actual
-    ///    c. Si X es terminal:
-    ///       - Si X == a: hacer pop y avanzar
-    ///       - Si X != a: error
-    ///    d. Si X es no-terminal:
-    ///       - Consultar M[X, a]
-    ///       - Si existe producción X → Y₁Y₂...Yₖ:
-    ///         * Hacer pop de X
-    ///         * Hacer push de Yₖ, Yₖ₋₁, ..., Y₁ (en orden inverso)
-    ///       - Si no existe: error
-    pub fn validate_syntax(&mut self) -> Result<(), String> {
-        // Reset parser state for validation
+    /// 2. Mientras la pila no esté vacía, sea X el tope y a el token actual:
+    ///    - Si X es terminal:
+    ///      - Si X == a: hacer pop y avanzar
+    ///      - Si X != a: error
+    ///    - Si X es no-terminal, consultar M[X, a]:
+    ///      - Si existe producción X → Y₁Y₂...Yₖ: hacer pop de X y push de
+    ///        Yₖ, Yₖ₋₁, ..., Y₁ (en orden inverso)
+    ///      - Si no existe: error
+    ///
+    /// Los errores se acumulan en `get_errors` (ver `run`).
+    pub fn validate_syntax(&mut self) -> Result<(), Vec<Diagnostic>> {
+        self.run(false).map(|_| ())
+    }
+
+    /// Recorre la entrada con la tabla LL(1)
+    ///
+    /// Con `build`, cada terminal reconocido deja su token en la pila de
+    /// valores y, al terminar el lado derecho de una producción, su acción
+    /// semántica reemplaza esos valores por el del no-terminal. Al final queda
+    /// un único valor: el programa.
+    ///
+    /// Los errores no detienen el análisis: se registran, se recupera en modo
+    /// pánico (ver `synchronize`) y se sigue hasta el final, de modo que se
+    /// reportan todos los errores del archivo. Tras el primer error ya no se
+    /// construye el AST.
+    fn run(&mut self, build: bool) -> Result<Option<Value>, Vec<Diagnostic>> {
+        // Reiniciar el estado por si el parser se reutiliza
         self.position = 0;
-        self.stack = vec![Symbol::Eof, Symbol::NonTerminal(NonTerminal::Programa)];
+        self.stack = self.initial_stack();
+        self.values.clear();
         self.errors.clear();
+        self.panic_mode = false;
+        if let Some(trace) = &mut self.trace {
+            *trace = ParseTrace::new();
+        }
 
-        let mut step = 0;
+        let mut building = build;
 
-        while !self.stack.is_empty() {
-            step += 1;
-            let top = self.stack.pop().unwrap();
+        while let Some(top) = self.stack.pop() {
+            let symbol = match top {
+                StackEntry::Symbol(symbol) => symbol,
+                StackEntry::Reduce { production, start } => {
+                    if building && let Err(message) = self.reduce(production, start) {
+                        self.error(message);
+                    }
+                    building = building && self.errors.is_empty();
+                    continue;
+                }
+            };
             let current = self.current_token();
 
-            if step <= 10 || step % 50 == 0 {
-                println!("   Paso {}: Top={:?}, Token={:?}", step, top, current);
-            }
-
-            match top {
+            match symbol {
                 Symbol::Epsilon => {
                     // Epsilon: no hacer nada, continuar
+                    self.record(&symbol, TraceAction::Epsilon);
                     continue;
                 }
 
                 Symbol::Eof => {
                     // Verificar fin de archivo
-                    if self.position >= self.tokens.len() {
-                        println!("   ✅ Validación LL(1) completada exitosamente en {} pasos", step);
-                        return Ok(());
+                    if current != &TokenType::Eof {
+                        self.record(&symbol, TraceAction::Error);
+                        self.syntax_error(vec![TokenType::Eof], None);
                     } else {
-                        self.error(format!(
-                            "Se esperaba EOF pero se encontró {:?} en posición {}",
-                            current, self.position
-                        ));
-                        return Err(self.errors.join("\n"));
+                        self.record(&symbol, TraceAction::Accept);
                     }
+                    break;
                 }
 
-                Symbol::Terminal(expected) => {
+                Symbol::Terminal(ref expected) => {
                     // Comparar terminal con token actual
-                    if Self::tokens_match(&expected, current) {
+                    if self.accepts(expected) {
+                        self.record(&symbol, TraceAction::Match);
+                        if building {
+                            self.values.push(Value::Token(self.tokens[self.position].clone()));
+                        }
                         self.advance();
+                        self.panic_mode = false;
                     } else {
-                        self.error(format!(
-                            "Error de sintaxis: se esperaba {:?} pero se encontró {:?} en posición {}",
-                            expected, current, self.position
-                        ));
-                        return Err(self.errors.join("\n"));
+                        // Se da por insertado el terminal que falta
+                        self.record(&symbol, TraceAction::Error);
+                        self.syntax_error(vec![expected.clone()], None);
+                        building = false;
                     }
                 }
 
                 Symbol::NonTerminal(nt) => {
+                    if self.trace.is_some() {
+                        let action = match self.table.get(nt, current) {
+                            Some(production) => TraceAction::expand(production),
+                            None => TraceAction::Error,
+                        };
+                        self.record(&symbol, action);
+                    }
+
                     // Consultar tabla LL(1)
-                    match self.table.get(nt, current) {
+                    match self.table.get(nt, self.current_token()) {
                         Some(production) => {
-                            // Aplicar producción: hacer push de RHS en orden inverso
-                            for symbol in production.rhs.iter().rev() {
-                                self.stack.push(symbol.clone());
+                            // La acción se aplica cuando se haya reconocido todo el lado derecho
+                            if building {
+                                self.stack.push(StackEntry::Reduce {
+                                    production: production.id,
+                                    start: self.position,
+                                });
                             }
 
-                            if step <= 10 {
-                                println!("   Aplicando producción {}: {} → {:?}",
-                                    production.id, nt.as_str(), production.rhs);
+                            // Aplicar producción: hacer push de RHS en orden inverso
+                            for symbol in production.rhs.iter().rev() {
+                                self.stack.push(StackEntry::Symbol(symbol.clone()));
                             }
                         }
                         None => {
-                            self.error(format!(
-                                "Error de sintaxis: no hay producción para M[{}, {:?}] en posición {}",
-                                nt.as_str(), TokenClass::from_token(current), self.position
-                            ));
-                            return Err(self.errors.join("\n"));
+                            self.syntax_error(self.table.expected(nt), Some(nt));
+                            building = false;
+                            self.synchronize(nt);
                         }
                     }
                 }
             }
         }
 
-        // Should never reach here if stack is properly initialized
-        Err("Error interno: el stack quedó vacío sin completar el análisis".to_string())
+        if !self.errors.is_empty() {
+            return Err(self.errors.clone());
+        }
+
+        match self.values.pop() {
+            Some(value) => Ok(Some(value)),
+            None if !build => Ok(None),
+            None => Err(vec![Self::internal_error(
+                "el stack quedó vacío sin completar el análisis".to_string(),
+            )]),
+        }
+    }
+
+    /// Agrega un paso a la traza, si está activa
+    ///
+    /// `symbol` es el tope que se acaba de sacar: la pila registrada lo
+    /// incluye, sin las marcas de fin de producción.
+    fn record(&mut self, symbol: &Symbol, action: TraceAction) {
+        let Some(trace) = &self.trace else {
+            return;
+        };
+
+        let mut stack: Vec<String> = self.stack.iter()
+            .filter_map(|entry| match entry {
+                StackEntry::Symbol(s) => Some(symbol_name(s).to_string()),
+                StackEntry::Reduce { .. } => None,
+            })
+            .collect();
+        stack.push(symbol_name(symbol).to_string());
+
+        let (lexeme, line, column) = match self.tokens.get(self.position) {
+            Some(token) => (token.lexeme.clone(), token.line, token.column),
+            None => (String::new(), 0, 0),
+        };
+
+        let step = TraceStep {
+            step: trace.len() + 1,
+            stack,
+            lookahead: terminal_name(self.current_token()).to_string(),
+            lexeme,
+            line,
+            column,
+            action,
+        };
+        if let Some(trace) = &mut self.trace {
+            trace.push(step);
+        }
+    }
+
+    /// Recuperación en modo pánico para un no-terminal sin producción
+    ///
+    /// Descarta tokens hasta uno de sincronización: si está en FIRST(nt) se
+    /// vuelve a intentar `nt`; si está en FOLLOW(nt) se abandona `nt` y sigue
+    /// quien lo esperaba. Las sentencias se sincronizan además tras `;`: el
+    /// resto de la sentencia errónea se descarta y el análisis continúa con la
+    /// siguiente (o con el `fin` del bloque, que está en FOLLOW(Sentencias)).
+    fn synchronize(&mut self, nt: NonTerminal) {
+        loop {
+            let current = self.current_token().clone();
+
+            if self.table.get(nt, &current).is_some() {
+                self.stack.push(StackEntry::Symbol(Symbol::NonTerminal(nt)));
+                return;
+            }
+
+            let follows = current == TokenType::Eof
+                || self.sets.is_in_follow(nt, &Symbol::Terminal(current.clone()));
+            if follows {
+                return;
+            }
+
+            self.advance();
+
+            if current == TokenType::Semicolon && Self::is_statement(nt) {
+                // Fin de la sentencia errónea: seguir con las siguientes
+                if nt.as_str() == "Sentencias" {
+                    self.stack.push(StackEntry::Symbol(Symbol::NonTerminal(nt)));
+                }
+                return;
+            }
+        }
+    }
+
+    /// No-terminales en los que una sentencia puede empezar mal
+    ///
+    /// Se reconocen por su nombre en la gramática: con otros nombres la
+    /// recuperación usa solo FIRST y FOLLOW.
+    fn is_statement(nt: NonTerminal) -> bool {
+        matches!(nt.as_str(), "Sentencias" | "Sentencia")
+    }
+
+    /// Registra un error de sintaxis en el token actual
+    ///
+    /// `expected` son los terminales válidos en este punto: el terminal del
+    /// tope de la pila o la fila de la tabla LL(1) del no-terminal `nt`.
+    ///
+    /// Mientras se recupera de un error (hasta reconocer el siguiente
+    /// terminal) no se reportan otros, que suelen ser consecuencia del primero.
+    fn syntax_error(&mut self, mut expected: Vec<TokenType>, nt: Option<NonTerminal>) {
+        if !self.panic_mode {
+            let found = self.dialect.display(self.current_token());
+            let mut names: Vec<String> = expected.drain(..).map(|t| self.describe(&t)).collect();
+            names.sort();
+            names.dedup();
+
+            let message = match names.as_slice() {
+                [single] => format!("Se esperaba {}, se encontró '{}'", single, found),
+                _ => format!("Se encontró '{}', se esperaba uno de: {}", found, names.join(", ")),
+            };
+
+            let location = self.tokens.get(self.position)
+                .map(Location::from_token)
+                .unwrap_or_else(Location::unknown);
+            let mut diagnostic = Diagnostic::syntax_error(location.line, location.column, location.length, message);
+            if let Some(nt) = nt {
+                diagnostic = diagnostic.with_help(format!("no hay producción para M[{}, '{}']", nt.as_str(), found));
+            }
+            self.error(diagnostic);
+        }
+        self.panic_mode = true;
+    }
+
+    /// Nombre de un terminal para los mensajes: las palabras tal cual y los
+    /// símbolos entre comillas (`';'`)
+    fn describe(&self, token_type: &TokenType) -> String {
+        let text = self.dialect.display(token_type);
+        if text.starts_with(char::is_alphabetic) {
+            text
+        } else {
+            format!("'{}'", text)
+        }
+    }
+
+    /// Error que indica un fallo del propio parser, sin posición en el código
+    fn internal_error(message: String) -> Diagnostic {
+        Diagnostic::syntax_error(0, 0, 0, format!("Error interno: {}", message))
+    }
+
+    /// Aplica la acción semántica de una producción ya reconocida
+    fn reduce(&mut self, id: usize, start: usize) -> Result<(), Diagnostic> {
+        let production = self.table.get_production(id)
+            .expect("la pila solo contiene producciones de la tabla");
+
+        // Un valor por símbolo del lado derecho (ε no aporta ninguno)
+        let arity = production.rhs.iter().filter(|s| **s != Symbol::Epsilon).count();
+        let values = self.values.split_off(self.values.len() - arity);
+
+        let location = self.tokens.get(start)
+            .map(Location::from_token)
+            .unwrap_or_else(Location::unknown);
+
+        // Una acción que no encaja con su producción es un error de la gramática
+        let value = production.action.apply(values, location.clone()).map_err(|error| {
+            if !actions::is_mismatch(&error) {
+                return error;
+            }
+            Diagnostic::config_error(
+                location.line,
+                location.column,
+                location.length,
+                format!("La acción {:?} no corresponde a la producción {}: {}", production.action, production, error.message),
+            )
+            .with_help("corrija la acción en la gramática u omita --grammar para usar la incrustada".to_string())
+        })?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Verifica si el token actual es el terminal esperado
+    ///
+    /// Las palabras reservadas solo valen como identificador cuando son el
+    /// nombre de un campo, es decir, justo después de '.'
+    fn accepts(&self, expected: &TokenType) -> bool {
+        let current = self.current_token();
+        if expected == &TokenType::Identifier && current != &TokenType::Identifier {
+            let after_dot = self.position > 0
+                && self.tokens.get(self.position - 1).map(|t| &t.token_type) == Some(&TokenType::Dot);
+            return after_dot && Self::tokens_match(expected, current);
+        }
+
+        Self::tokens_match(expected, current)
     }
 
-    /// Compara si dos tokens coinciden (ignorando valores en Some variants)
+    /// Compara si el token actual coincide con el terminal esperado
     ///
     /// Nota especial: Cuando esperamos un IDENTIFICADOR, también aceptamos palabras
     /// reservadas que pueden usarse como nombres de campo (ej: uno.coaxial, seg1.completo)
-    fn tokens_match(expected: &Token, actual: &Token) -> bool {
-        use Token::*;
-
+    fn tokens_match(expected: &TokenType, actual: &TokenType) -> bool {
         match (expected, actual) {
-            // Tokens con valores: comparar solo el tipo
-            (Identificador(_), Identificador(_)) => true,
-            (Numero(_), Numero(_)) => true,
-            (Cadena(_), Cadena(_)) => true,
-
             // Caso especial: permitir keywords como identificadores (nombres de campo)
             // Esto maneja casos como: uno.coaxial, seg1.completo, uno.presente
-            (Identificador(_), keyword) if Self::is_valid_field_name(keyword) => true,
+            (TokenType::Identifier, keyword) if Self::is_valid_field_name(keyword) => true,
 
-            // Tokens sin valores: comparación exacta
-            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+            (a, b) => a == b,
         }
     }
 
     /// Verifica si un token puede usarse como nombre de campo
     /// (compatible con parser.rs token_to_field_name)
-    fn is_valid_field_name(token: &Token) -> bool {
+    fn is_valid_field_name(token: &TokenType) -> bool {
         matches!(token,
-            Token::Coaxial | Token::Segmento | Token::Maquinas | Token::Concentradores |
-            Token::Derecha | Token::Izquierda | Token::Arriba | Token::Abajo |
+            TokenType::Coaxial | TokenType::Segmento | TokenType::Maquinas | TokenType::Concentradores |
+            TokenType::Derecha | TokenType::Izquierda | TokenType::Arriba | TokenType::Abajo |
             // Estos se usan en ejemplo1.net
-            Token::Modulo  // para campos personalizados
+            TokenType::Modulo  // para campos personalizados
         )
     }
 
     /// Obtiene los errores acumulados
-    pub fn get_errors(&self) -> &[String] {
+    pub fn get_errors(&self) -> &[Diagnostic] {
         &self.errors
     }
 }
@@ -228,29 +467,17 @@ impl PredictiveParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lexer_new::tokenize;
+    use crate::ast::{Expr, Statement};
+    use crate::parser::Parser as RecursiveParser;
 
-    fn make_token_info(token: Token, line: usize, column: usize) -> TokenInfo {
-        TokenInfo {
-            token: token.clone(),
-            line,
-            column,
-            length: 1,
-            lexeme: format!("{:?}", token),
-        }
+    fn tokens(source: &str) -> Vec<Token> {
+        tokenize(source).expect("el código de prueba debe ser léxicamente válido")
     }
 
     #[test]
     fn test_parser_creation() {
-        let tokens = vec![
-            make_token_info(Token::Programa, 1, 1),
-            make_token_info(Token::Identificador("test".to_string()), 1, 10),
-            make_token_info(Token::PuntoYComa, 1, 14),
-            make_token_info(Token::Inicio, 2, 1),
-            make_token_info(Token::Fin, 3, 1),
-            make_token_info(Token::Punto, 3, 4),
-        ];
-
-        let parser = PredictiveParser::new(tokens);
+        let parser = PredictiveParser::new(tokens("programa test;\ninicio\nfin."));
         assert_eq!(parser.position, 0);
         assert!(!parser.stack.is_empty());
     }
@@ -258,43 +485,34 @@ mod tests {
     #[test]
     fn test_tokens_match() {
         assert!(PredictiveParser::tokens_match(
-            &Token::Identificador("a".to_string()),
-            &Token::Identificador("b".to_string())
+            &TokenType::Identifier,
+            &TokenType::Identifier
         ));
 
         assert!(PredictiveParser::tokens_match(
-            &Token::Numero(0),
-            &Token::Numero(42)
+            &TokenType::Programa,
+            &TokenType::Programa
         ));
 
+        // Palabras reservadas usadas como nombre de campo
         assert!(PredictiveParser::tokens_match(
-            &Token::Programa,
-            &Token::Programa
+            &TokenType::Identifier,
+            &TokenType::Coaxial
         ));
 
         assert!(!PredictiveParser::tokens_match(
-            &Token::Programa,
-            &Token::Modulo
+            &TokenType::Programa,
+            &TokenType::Modulo
         ));
     }
 
     #[test]
     fn test_simple_program() {
-        // programa test; inicio fin .
-        let tokens = vec![
-            make_token_info(Token::Programa, 1, 1),
-            make_token_info(Token::Identificador("test".to_string()), 1, 10),
-            make_token_info(Token::PuntoYComa, 1, 14),
-            make_token_info(Token::Inicio, 2, 1),
-            make_token_info(Token::Fin, 3, 1),
-            make_token_info(Token::Punto, 3, 4),
-        ];
-
-        let mut parser = PredictiveParser::new(tokens);
+        let mut parser = PredictiveParser::new(tokens("programa test;\ninicio\nfin."));
         let result = parser.parse();
 
-        if let Err(e) = &result {
-            println!("Error: {}", e);
+        if let Err(errors) = &result {
+            println!("Errores: {:?}", errors);
         }
 
         assert!(result.is_ok(), "Parser should accept valid program");
@@ -302,41 +520,386 @@ mod tests {
 
     #[test]
     fn test_program_with_definitions() {
-        // programa test; define maquinas m1; inicio fin .
-        let tokens = vec![
-            make_token_info(Token::Programa, 1, 1),
-            make_token_info(Token::Identificador("test".to_string()), 1, 10),
-            make_token_info(Token::PuntoYComa, 1, 14),
-            make_token_info(Token::Define, 2, 1),
-            make_token_info(Token::Maquinas, 2, 8),
-            make_token_info(Token::Identificador("m1".to_string()), 2, 17),
-            make_token_info(Token::PuntoYComa, 2, 19),
-            make_token_info(Token::Inicio, 3, 1),
-            make_token_info(Token::Fin, 4, 1),
-            make_token_info(Token::Punto, 4, 4),
-        ];
-
-        let mut parser = PredictiveParser::new(tokens);
+        let source = "programa test;\ndefine maquinas m1;\ninicio\nfin.";
+        let mut parser = PredictiveParser::new(tokens(source));
         let result = parser.parse();
 
         assert!(result.is_ok(), "Parser should accept program with definitions");
     }
 
+    #[test]
+    fn test_errors_use_active_dialect() {
+        let dialect = crate::config::load_automaton().dialect("english").unwrap();
+        let source = "// @dialecto english\nprogram test;\nbegin\nplace(a, 1, 2)\nend.";
+        let mut parser = PredictiveParser::new(tokens(source)).with_dialect(dialect);
+
+        let errors = parser.parse().unwrap_err();
+        assert_eq!(errors[0].message, "Se esperaba ';', se encontró 'end'");
+    }
+
+    /// El AST de una sola pasada coincide con el del parser recursivo
+    fn assert_same_ast(source: &str) {
+        let tokens = tokens(source);
+        let expected = RecursiveParser::new(tokens.clone()).parse().expect("el oráculo acepta el programa");
+        let program = PredictiveParser::new(tokens).parse().unwrap();
+        assert_eq!(format!("{:?}", program), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn test_ast_matches_recursive_parser() {
+        assert_same_ast(include_str!("../../ejemplo1.net"));
+        assert_same_ast(
+            "programa p;\n\
+             /// Equipos\ndefine maquinas a, /// el servidor\n b;\n\
+             define concentradores h = 4.1, g = 8;\n\
+             define segmento s = 30;\n\
+             modulo m;\ninicio\nescribe(a.presente);\nfin\n\
+             inicio\n\
+             coloca(a, 1, 2);\n\
+             colocaCoaxial(s, 10, 20, derecha);\n\
+             colocaCoaxialConcentrador(s, h);\n\
+             uneMaquinaPuerto(a, h, (1));\n\
+             asignaPuerto(b, g);\n\
+             maquinaCoaxial(a, s, 5);\n\
+             asignaMaquinaCoaxial(b, s);\n\
+             si a.presente && !(h.p[1] = 0) || g.coaxial <> 2 inicio m; fin\n\
+             sino inicio escribe(h[2]); fin\n\
+             fin.",
+        );
+    }
+
+    #[test]
+    fn test_define_blocks_in_any_order() {
+        let source = "programa p;\n\
+                      /// Cables\n\
+                      define coaxial c = 10;\n\
+                      define concentradores h = 4;\n\
+                      define maquinas a, b;\n\
+                      define maquinas c2;\n\
+                      inicio\nfin.";
+        let programa = PredictiveParser::new(tokens(source)).parse().unwrap();
+        let definiciones = programa.definiciones;
+
+        let maquinas: Vec<&str> = definiciones.maquinas.iter().map(|m| m.nombre.as_str()).collect();
+        assert_eq!(maquinas, ["a", "b", "c2"]);
+        assert_eq!(definiciones.concentradores[0].nombre, "h");
+        assert_eq!(definiciones.coaxiales[0].doc.as_deref(), Some("Cables"));
+        assert_eq!(definiciones.location.line, 3);
+    }
+
+    #[test]
+    fn test_repeated_define_blocks_keep_their_docs() {
+        let source = "programa p;\n\
+                      /// Servidores\n\
+                      define maquinas a;\n\
+                      /// Clientes\n\
+                      define maquinas b, c;\n\
+                      define segmento s = 5;\n\
+                      define concentradores h = 4;\n\
+                      define coaxial t = 8;\n\
+                      inicio\nfin.";
+        let definiciones = PredictiveParser::new(tokens(source)).parse().unwrap().definiciones;
+
+        let docs: Vec<Option<&str>> = definiciones.maquinas.iter().map(|m| m.doc.as_deref()).collect();
+        assert_eq!(docs, [Some("Servidores"), Some("Clientes"), Some("Clientes")]);
+        let coaxiales: Vec<&str> = definiciones.coaxiales.iter().map(|c| c.nombre.as_str()).collect();
+        assert_eq!(coaxiales, ["s", "t"]);
+    }
+
+    #[test]
+    fn test_accesses_without_conflicts() {
+        // Accesos → [ Expresion ] | AccesoCampo | ε: el identificador solo,
+        // con índice o con campo e índice
+        let source = "programa p;\ninicio\nescribe(h);\nescribe(h[1]);\nescribe(h.p[2]);\nescribe(h.p);\nfin.";
+        let sentencias = PredictiveParser::new(tokens(source)).parse().unwrap().sentencias;
+        let escritos: Vec<&Expr> = sentencias
+            .iter()
+            .map(|s| match s {
+                Statement::Escribe { contenido, .. } => contenido,
+                other => panic!("se esperaba escribe: {:?}", other),
+            })
+            .collect();
+
+        assert!(matches!(escritos[0], Expr::Identificador(o) if o == "h"));
+        assert!(matches!(escritos[1], Expr::AccesoArreglo { objeto, .. } if objeto == "h"));
+        assert!(matches!(escritos[2], Expr::AccesoArreglo { objeto, .. } if objeto == "h.p"));
+        assert!(matches!(escritos[3], Expr::AccesoCampo { objeto, campo } if objeto == "h" && campo == "p"));
+    }
+
+    #[test]
+    fn test_keywords_only_as_field_names() {
+        let mut parser = PredictiveParser::new(tokens("programa p;\ninicio\ncoloca(coaxial, 1, 2);\nfin."));
+        let errors = parser.parse().unwrap_err();
+        assert_eq!(errors[0].message, "Se esperaba identificador, se encontró 'coaxial'");
+    }
+
+    #[test]
+    fn test_semantic_action_errors() {
+        let mut parser = PredictiveParser::new(tokens("programa p;\ndefine maquinas a;\ndefine concentradores h = 4.2;\ninicio\nfin."));
+        let errors = parser.parse().unwrap_err();
+        assert!(errors[0].message.contains("Después del punto debe ir 1"), "{}", errors[0]);
+        assert_eq!((errors[0].line, errors[0].column), (3, 29));
+    }
+
+    #[test]
+    fn test_action_mismatch_is_a_grammar_error() {
+        // `Sentencias → ε` deja Nada donde `Programa` espera la lista de sentencias
+        let source = crate::config::loader::DEFAULT_GRAMMAR_SOURCE
+            .replace("[44] Sentencias → ε  {ListaVacia}", "[44] Sentencias → ε  {Nada}");
+        let grammar = Grammar::parse(&source).unwrap();
+
+        let mut parser = PredictiveParser::new(tokens("programa p;\ninicio\nfin.")).with_grammar(&grammar);
+        let errors = parser.parse().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, crate::error::DiagnosticKind::ConfigError));
+        assert!(errors[0].message.contains("se esperaba un valor Lista y se obtuvo Nada"), "{}", errors[0]);
+        assert!(errors[0].help.as_ref().unwrap().contains("--grammar"));
+    }
+
+    #[test]
+    fn test_reports_every_syntax_error() {
+        let source = "programa p;\n\
+                      define maquinas a b;\n\
+                      inicio\n\
+                      coloca(a, 1 2);\n\
+                      escribe(a.x);\n\
+                      coloca(a, 1, 2) ) ;\n\
+                      si a.x inicio\n\
+                        uneMaquinaPuerto(a, , 3);\n\
+                      fin\n\
+                      fin.";
+        let mut parser = PredictiveParser::new(tokens(source));
+
+        assert!(parser.parse().is_err());
+        let errors = parser.get_errors();
+        assert_eq!(errors.len(), 4, "{:#?}", errors);
+        let positions: Vec<(usize, usize)> = errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(positions, vec![(2, 19), (4, 13), (6, 17), (8, 21)]);
+        assert!(errors[0].help.as_deref().unwrap().contains("M[ListaMaquinas', 'identificador']"));
+        assert_eq!(errors[2].message, "Se esperaba ';', se encontró ')'");
+        assert_eq!(errors[3].message, "Se esperaba identificador, se encontró ','");
+    }
+
+    #[test]
+    fn test_statements_resync_after_semicolon() {
+        // Basura al inicio de una sentencia: se descarta hasta ';'
+        let source = "programa p;\ninicio\n= 1 2;\ncoloca(a, 1, 2);\n< 3;\nescribe(a);\nfin.";
+        let mut parser = PredictiveParser::new(tokens(source));
+
+        assert!(parser.validate_syntax().is_err());
+        let errors = parser.get_errors();
+        assert_eq!(errors.len(), 2, "{:#?}", errors);
+        assert!(errors.iter().all(|e| e.help.as_deref().unwrap().contains("M[Sentencias,")));
+        assert_eq!((errors[1].line, errors[1].column), (5, 1));
+    }
+
+    #[test]
+    fn test_expected_set_from_table_row() {
+        let mut parser = PredictiveParser::new(tokens("programa p;\ninicio\n  = 1;\nfin."));
+        let errors = parser.parse().unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column, errors[0].length), (3, 3, 1));
+        assert_eq!(
+            errors[0].message,
+            "Se encontró '=', se esperaba uno de: asignaMaquinaCoaxial, asignaPuerto, coloca, \
+             colocaCoaxial, colocaCoaxialConcentrador, escribe, fin, identificador, maquinaCoaxial, \
+             mientras, para, si, uneMaquinaPuerto"
+        );
+    }
+
     #[test]
     fn test_invalid_program() {
         // programa sin PUNTO_COMA
-        let tokens = vec![
-            make_token_info(Token::Programa, 1, 1),
-            make_token_info(Token::Identificador("test".to_string()), 1, 10),
-            // Falta PUNTO_COMA
-            make_token_info(Token::Inicio, 2, 1),
-            make_token_info(Token::Fin, 3, 1),
-            make_token_info(Token::Punto, 3, 4),
-        ];
-
-        let mut parser = PredictiveParser::new(tokens);
+        let mut parser = PredictiveParser::new(tokens("programa test\ninicio\nfin."));
         let result = parser.parse();
 
         assert!(result.is_err(), "Parser should reject invalid program");
     }
+
+    #[test]
+    fn test_trace_records_steps() {
+        let source = "programa test;\ninicio\nfin.";
+        let mut parser = PredictiveParser::new(tokens(source));
+        parser.parse().unwrap();
+        assert!(parser.trace().is_none(), "sin with_trace no se registra nada");
+
+        let mut parser = PredictiveParser::new(tokens(source)).with_trace();
+        parser.parse().unwrap();
+        let steps = parser.trace().unwrap().steps();
+
+        assert_eq!(steps[0].stack, vec!["EOF", "Programa"]);
+        assert_eq!((steps[0].lookahead.as_str(), steps[0].line, steps[0].column), ("PROGRAMA", 1, 1));
+        assert!(matches!(steps[0].action, TraceAction::Expand { production: 1, .. }));
+        assert_eq!(steps[1].stack.last().unwrap(), "PROGRAMA");
+        assert_eq!(steps[1].action, TraceAction::Match);
+        assert_eq!(steps.last().unwrap().action, TraceAction::Accept);
+        assert!(steps.iter().enumerate().all(|(i, s)| s.step == i + 1));
+
+        // Reutilizar el parser reinicia la traza
+        let count = steps.len();
+        parser.validate_syntax().unwrap();
+        assert_eq!(parser.trace().unwrap().len(), count);
+    }
+
+    #[test]
+    fn test_trace_marks_errors() {
+        let mut parser = PredictiveParser::new(tokens("programa test\ninicio\nfin.")).with_trace();
+        assert!(parser.parse().is_err());
+
+        let error = parser.trace().unwrap().steps().iter()
+            .find(|s| s.action == TraceAction::Error)
+            .expect("el paso del error queda en la traza");
+        assert_eq!(error.stack.last().unwrap(), "PUNTO_COMA");
+        assert_eq!(error.lookahead, "INICIO");
+    }
+
+    #[test]
+    fn test_arithmetic_precedence() {
+        let source = "programa p;\ninicio\n  escribe(1 + 2 * -3 % 4 < a.x - 1);\nfin.";
+        let program = PredictiveParser::new(tokens(source)).parse().unwrap();
+
+        use crate::ast::{Expr, OpAritmetico, Statement};
+        let Statement::Escribe { contenido: Expr::Relacional { izq, der, .. }, .. } = &program.sentencias[0] else {
+            panic!("{:?}", program.sentencias[0]);
+        };
+
+        // 1 + ((2 * (-3)) % 4)
+        let Expr::Aritmetico { izq: uno, op: OpAritmetico::Suma, der: producto } = izq.as_ref() else {
+            panic!("{:?}", izq);
+        };
+        assert!(matches!(uno.as_ref(), Expr::Numero(1)));
+        let Expr::Aritmetico { izq: por, op: OpAritmetico::Modulo, .. } = producto.as_ref() else {
+            panic!("{:?}", producto);
+        };
+        assert!(matches!(
+            por.as_ref(),
+            Expr::Aritmetico { op: OpAritmetico::Multiplicacion, der, .. } if matches!(der.as_ref(), Expr::Negativo(_))
+        ));
+
+        // a.x - 1
+        assert!(matches!(der.as_ref(), Expr::Aritmetico { op: OpAritmetico::Resta, .. }));
+    }
+
+    #[test]
+    fn test_integer_variables_and_assignment() {
+        let source = "programa p;\ndefine enteros x, y;\ninicio\n  x = x + 1;\n  prueba;\nfin.";
+        let program = PredictiveParser::new(tokens(source)).parse().unwrap();
+
+        let nombres: Vec<&str> = program.definiciones.enteros.iter().map(|v| v.nombre.as_str()).collect();
+        assert_eq!(nombres, ["x", "y"]);
+
+        use crate::ast::{Expr, OpAritmetico, Statement};
+        assert!(matches!(
+            &program.sentencias[0],
+            Statement::Asignacion { variable, valor: Expr::Aritmetico { op: OpAritmetico::Suma, .. }, .. } if variable == "x"
+        ));
+        assert!(matches!(&program.sentencias[1], Statement::LlamadaModulo { nombre, .. } if nombre == "prueba"));
+    }
+
+    #[test]
+    fn test_loops() {
+        let source = "programa p;\ndefine enteros i;\ninicio\n  mientras (i < 3) inicio i = i + 1; fin\n  para i = 1 hasta 2 * 10 inicio\n    coloca(A, i * 30, 10);\n  fin\nfin.";
+        let program = PredictiveParser::new(tokens(source)).parse().unwrap();
+
+        use crate::ast::{Expr, OpAritmetico, Statement};
+        assert!(matches!(
+            &program.sentencias[0],
+            Statement::Mientras { condicion: Expr::Relacional { .. }, cuerpo, .. } if cuerpo.len() == 1
+        ));
+        let Statement::Para { variable, desde, hasta, cuerpo, .. } = &program.sentencias[1] else {
+            panic!("{:?}", program.sentencias[1]);
+        };
+        assert_eq!(variable, "i");
+        assert!(matches!(desde, Expr::Numero(1)));
+        assert!(matches!(hasta, Expr::Aritmetico { op: OpAritmetico::Multiplicacion, .. }));
+        assert!(matches!(&cuerpo[..], [Statement::Coloca { .. }]));
+    }
+
+    #[test]
+    fn test_module_parameters_and_arguments() {
+        let source = "programa p;\n\
+                      modulo conectaPC(maquina m, concentrador hub, entero p);\n\
+                      inicio uneMaquinaPuerto(m, hub, p); fin\n\
+                      modulo vacio();\ninicio fin\n\
+                      inicio\n  conectaPC(A, dos, 1 + 2);\n  vacio();\n  vacio;\nfin.";
+        let program = PredictiveParser::new(tokens(source)).parse().unwrap();
+
+        use crate::ast::{Expr, Statement, TipoParametro};
+        let parametros: Vec<(&str, TipoParametro)> = program.modulos[0].parametros.iter()
+            .map(|p| (p.nombre.as_str(), p.tipo))
+            .collect();
+        assert_eq!(parametros, [
+            ("m", TipoParametro::Maquina),
+            ("hub", TipoParametro::Concentrador),
+            ("p", TipoParametro::Entero),
+        ]);
+        assert!(program.modulos[1].parametros.is_empty());
+
+        let Statement::LlamadaModulo { nombre, argumentos, .. } = &program.sentencias[0] else {
+            panic!("{:?}", program.sentencias[0]);
+        };
+        assert_eq!(nombre, "conectaPC");
+        assert!(matches!(
+            &argumentos[..],
+            [Expr::Identificador(a), Expr::Identificador(b), Expr::Aritmetico { .. }] if a == "A" && b == "dos"
+        ));
+        assert!(matches!(&program.sentencias[1], Statement::LlamadaModulo { argumentos, .. } if argumentos.is_empty()));
+        assert!(matches!(&program.sentencias[2], Statement::LlamadaModulo { argumentos, .. } if argumentos.is_empty()));
+    }
+
+    #[test]
+    fn test_else_if_chain_is_flat() {
+        let source = "programa p;\ninicio\n\
+                      si (a = 1) inicio escribe(1); fin\n\
+                      sino si (a = 2) inicio escribe(2); fin\n\
+                      sino si (a = 3) inicio fin\n\
+                      sino inicio escribe(4); escribe(5); fin\n\
+                      si (b) inicio fin sino inicio si (c) inicio fin fin\nfin.";
+        let program = PredictiveParser::new(tokens(source)).parse().unwrap();
+
+        use crate::ast::Statement;
+        let Statement::Si { condicion, sino_si, sino, .. } = &program.sentencias[0] else {
+            panic!("{:?}", program.sentencias[0]);
+        };
+        assert_eq!(condicion.to_string(), "a = 1");
+        let condiciones: Vec<String> = sino_si.iter().map(|rama| rama.condicion.to_string()).collect();
+        assert_eq!(condiciones, ["a = 2", "a = 3"]);
+        assert_eq!(sino_si[0].sentencias.len(), 1);
+        assert!(sino_si[1].sentencias.is_empty());
+        assert_eq!(sino.as_ref().map(Vec::len), Some(2));
+
+        // `sino inicio si ... fin fin` es un bloque con un si adentro, no una cadena
+        let Statement::Si { sino_si, sino: Some(bloque), .. } = &program.sentencias[1] else {
+            panic!("{:?}", program.sentencias[1]);
+        };
+        assert!(sino_si.is_empty());
+        assert!(matches!(&bloque[..], [Statement::Si { .. }]));
+    }
+
+    #[test]
+    fn test_includes_and_library_files() {
+        let source = "programa p;\nincluye \"comun.net\";\nincluye \"lib/otra.net\";\ndefine maquinas A;\ninicio\nfin.";
+        let program = PredictiveParser::new(tokens(source)).parse().unwrap();
+
+        assert!(!program.biblioteca);
+        let rutas: Vec<&str> = program.inclusiones.iter().map(|i| i.ruta.as_str()).collect();
+        assert_eq!(rutas, ["comun.net", "lib/otra.net"]);
+        assert_eq!((program.inclusiones[1].location.line, program.inclusiones[1].location.column), (3, 9));
+
+        // Una biblioteca no tiene encabezado ni bloque principal
+        let library = "incluye \"base.net\";\ndefine concentradores hub = 4;\nmodulo m;\ninicio\nfin";
+        let program = PredictiveParser::new(tokens(library)).parse().unwrap();
+        assert!(program.biblioteca);
+        assert_eq!(program.inclusiones.len(), 1);
+        assert_eq!(program.definiciones.concentradores.len(), 1);
+        assert_eq!(program.modulos.len(), 1);
+        assert!(program.sentencias.is_empty());
+
+        // Las inclusiones van antes que las definiciones
+        let errors = PredictiveParser::new(tokens("programa p;\ndefine maquinas A;\nincluye \"c.net\";\ninicio\nfin."))
+            .parse()
+            .unwrap_err();
+        assert_eq!(errors[0].line, 3);
+    }
 }
@@ -0,0 +1,246 @@
+// src/parser_ll1/trace.rs
+// Traza del parser LL(1): un paso por símbolo sacado de la pila
+//
+// El parser no imprime nada; con `PredictiveParser::with_trace` guarda cada
+// paso como dato y quien lo llame decide cómo mostrarlo (tabla o JSON).
+
+use super::grammar::Production;
+use std::fmt::Write;
+
+/// Qué hizo el parser con el tope de la pila
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceAction {
+    /// No-terminal reemplazado por el lado derecho de una producción
+    Expand { production: usize, rule: String },
+    /// Terminal reconocido: se consume el token
+    Match,
+    /// ε: no consume nada
+    Epsilon,
+    /// Fin de la entrada reconocido
+    Accept,
+    /// Error de sintaxis en este paso
+    Error,
+}
+
+impl TraceAction {
+    /// Expansión con la producción `production`
+    pub fn expand(production: &Production) -> Self {
+        TraceAction::Expand { production: production.id, rule: production.to_string() }
+    }
+
+    /// Nombre corto de la acción (`expand`, `match`, ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TraceAction::Expand { .. } => "expand",
+            TraceAction::Match => "match",
+            TraceAction::Epsilon => "epsilon",
+            TraceAction::Accept => "accept",
+            TraceAction::Error => "error",
+        }
+    }
+}
+
+/// Un paso del análisis
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceStep {
+    /// Número de paso (1-based)
+    pub step: usize,
+    /// Contenido de la pila, del fondo al tope (el tope es el símbolo tratado)
+    pub stack: Vec<String>,
+    /// Terminal de preanálisis con el nombre de la gramática
+    pub lookahead: String,
+    /// Lexema del token de preanálisis (vacío al final de la entrada)
+    pub lexeme: String,
+    pub line: usize,
+    pub column: usize,
+    pub action: TraceAction,
+}
+
+/// Pasos registrados en un análisis
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseTrace {
+    steps: Vec<TraceStep>,
+}
+
+impl ParseTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Agrega un paso
+    pub fn push(&mut self, step: TraceStep) {
+        self.steps.push(step);
+    }
+
+    /// Pasos en orden
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Tabla de texto: paso, pila, preanálisis y acción
+    ///
+    /// Las pilas muy profundas se recortan por el fondo: el tope siempre se ve.
+    pub fn to_table(&self) -> String {
+        let stacks: Vec<String> = self.steps.iter().map(|s| stack_tail(&s.stack)).collect();
+        let stack_width = stacks.iter().map(|s| s.chars().count()).max().unwrap_or(0).max(4);
+        let lookahead_width = self.steps.iter()
+            .map(|s| s.lookahead.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(12);
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{:>5}  {:<stack_width$}  {:<lookahead_width$}  {:<9}  Acción",
+            "Paso", "Pila", "Preanálisis", "Posición",
+        );
+        let _ = writeln!(out, "{}", "─".repeat(5 + stack_width + lookahead_width + 30));
+
+        for (step, stack) in self.steps.iter().zip(&stacks) {
+            let action = match &step.action {
+                TraceAction::Expand { rule, .. } => rule.clone(),
+                other => other.as_str().to_string(),
+            };
+            let _ = writeln!(
+                out,
+                "{:>5}  {:<stack_width$}  {:<lookahead_width$}  {:<9}  {}",
+                step.step,
+                stack,
+                step.lookahead,
+                format!("{}:{}", step.line, step.column),
+                action,
+            );
+        }
+        out
+    }
+
+    /// Arreglo JSON con un objeto por paso
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[\n");
+        for (index, step) in self.steps.iter().enumerate() {
+            let stack: Vec<String> = step.stack.iter().map(|s| json_string(s)).collect();
+            let _ = write!(
+                out,
+                "  {{\"step\": {}, \"stack\": [{}], \"lookahead\": {}, \"lexeme\": {}, \"line\": {}, \"column\": {}, \"action\": \"{}\"",
+                step.step,
+                stack.join(", "),
+                json_string(&step.lookahead),
+                json_string(&step.lexeme),
+                step.line,
+                step.column,
+                step.action.as_str(),
+            );
+            if let TraceAction::Expand { production, rule } = &step.action {
+                let _ = write!(out, ", \"production\": {}, \"rule\": {}", production, json_string(rule));
+            }
+            out.push('}');
+            if index + 1 < self.steps.len() {
+                out.push(',');
+            }
+            out.push('\n');
+        }
+        out.push(']');
+        out
+    }
+}
+
+/// Ancho máximo de la columna de la pila en `to_table`
+const MAX_STACK_WIDTH: usize = 60;
+
+/// Símbolos del tope de la pila que caben en `MAX_STACK_WIDTH`
+fn stack_tail(stack: &[String]) -> String {
+    let mut shown: Vec<&str> = Vec::new();
+    let mut width = 0;
+    for symbol in stack.iter().rev() {
+        let extra = symbol.chars().count() + usize::from(!shown.is_empty());
+        if width + extra > MAX_STACK_WIDTH - 2 && shown.len() < stack.len() {
+            shown.push("…");
+            break;
+        }
+        width += extra;
+        shown.push(symbol);
+    }
+    shown.reverse();
+    shown.join(" ")
+}
+
+/// Cadena JSON entre comillas con los caracteres especiales escapados
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(action: TraceAction) -> TraceStep {
+        TraceStep {
+            step: 1,
+            stack: vec!["EOF".to_string(), "Programa".to_string()],
+            lookahead: "CADENA".to_string(),
+            lexeme: "\"a\\b\"".to_string(),
+            line: 1,
+            column: 1,
+            action,
+        }
+    }
+
+    #[test]
+    fn test_json_escapes_strings() {
+        let mut trace = ParseTrace::new();
+        trace.push(step(TraceAction::Match));
+
+        let json = trace.to_json();
+        assert!(json.contains("\"stack\": [\"EOF\", \"Programa\"]"));
+        assert!(json.contains("\"lexeme\": \"\\\"a\\\\b\\\"\""));
+        assert!(json.contains("\"action\": \"match\""));
+        assert!(!json.contains("\"production\""));
+    }
+
+    #[test]
+    fn test_table_shows_rule() {
+        let mut trace = ParseTrace::new();
+        trace.push(step(TraceAction::Expand { production: 1, rule: "[1] Programa → ...".to_string() }));
+
+        let table = trace.to_table();
+        assert!(table.lines().next().unwrap().contains("Preanálisis"));
+        assert!(table.contains("EOF Programa"));
+        assert!(table.contains("[1] Programa → ..."));
+    }
+
+    #[test]
+    fn test_table_trims_deep_stacks() {
+        let stack: Vec<String> = (0..40).map(|i| format!("Simbolo{}", i)).collect();
+        let shown = stack_tail(&stack);
+
+        assert!(shown.starts_with("… "));
+        assert!(shown.ends_with("Simbolo39"));
+        assert!(shown.chars().count() <= MAX_STACK_WIDTH);
+        assert_eq!(stack_tail(&stack[..2]), "Simbolo0 Simbolo1");
+    }
+}
@@ -0,0 +1,320 @@
+// src/parser_ll1/validate.rs
+// Validación estática de la gramática LL(1) (docs/gramatica.txt)
+
+use super::first_follow::{FirstFollowSets, NonTerminal, Symbol};
+use super::grammar::{Grammar, terminal_name};
+use super::ll1_table::LL1Table;
+use crate::error::{ConfigIssue, IssueKind};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// Conflictos agrupados: (no-terminal, producciones) → (elegida, terminales)
+type ConflictGroups<'a> = BTreeMap<(NonTerminal, Vec<usize>), (usize, Vec<&'a str>)>;
+
+/// Tipo de problema detectado en una gramática
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrammarIssueKind {
+    /// Una celda de la tabla admite varias producciones
+    Conflict,
+    /// A ⇒+ A α: el parser predictivo no puede elegir producción
+    LeftRecursion,
+    /// No-terminal al que no se llega desde el símbolo inicial
+    Unreachable,
+    /// No-terminal que no deriva ninguna cadena de terminales
+    NonProductive,
+    /// Producción que no aparece en ninguna celda de la tabla
+    UnusedProduction,
+}
+
+impl IssueKind for GrammarIssueKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            GrammarIssueKind::Conflict => "conflicto LL(1)",
+            GrammarIssueKind::LeftRecursion => "recursión izquierda",
+            GrammarIssueKind::Unreachable => "no-terminal inalcanzable",
+            GrammarIssueKind::NonProductive => "no-terminal improductivo",
+            GrammarIssueKind::UnusedProduction => "producción sin uso",
+        }
+    }
+}
+
+/// Problema encontrado al validar una gramática
+pub type GrammarIssue = ConfigIssue<GrammarIssueKind>;
+
+impl Grammar {
+    /// Valida que la gramática sea LL(1) y no tenga partes muertas
+    ///
+    /// Detecta conflictos en la tabla, recursión izquierda (directa o
+    /// indirecta), no-terminales inalcanzables o improductivos y producciones
+    /// que ninguna celda de la tabla usa. Los problemas se devuelven ordenados
+    /// por línea.
+    pub fn validate(&self) -> Vec<GrammarIssue> {
+        let table = LL1Table::from_grammar(self);
+        let mut issues = Vec::new();
+
+        self.check_conflicts(&table, &mut issues);
+        self.check_left_recursion(&mut issues);
+        self.check_reachability(&mut issues);
+        self.check_productivity(&mut issues);
+        self.check_unused(&table, &mut issues);
+
+        issues.sort_by_key(|issue| issue.line);
+        issues
+    }
+
+    /// Línea de la primera producción de `nt`, o de la primera que lo usa
+    fn nonterminal_line(&self, nt: NonTerminal) -> usize {
+        self.productions()
+            .iter()
+            .find(|p| p.lhs == nt)
+            .or_else(|| self.productions().iter().find(|p| p.rhs.contains(&Symbol::NonTerminal(nt))))
+            .map(|p| self.line(p.id))
+            .unwrap_or(0)
+    }
+
+    fn check_conflicts(&self, table: &LL1Table, issues: &mut Vec<GrammarIssue>) {
+        // Un problema por no-terminal y juego de producciones, con todos sus terminales
+        let mut groups: ConflictGroups = BTreeMap::new();
+        for conflict in table.conflicts() {
+            let (_, terminals) = groups
+                .entry((conflict.nt, conflict.productions.clone()))
+                .or_insert((conflict.chosen, Vec::new()));
+            terminals.push(terminal_name(&conflict.token));
+        }
+
+        for ((nt, productions), (chosen, terminals)) in groups {
+            let names: Vec<String> = productions.iter().map(|id| format!("[{}]", id)).collect();
+            let line = productions.iter().map(|id| self.line(*id)).max().unwrap_or(0);
+            issues.push(GrammarIssue::new(
+                GrammarIssueKind::Conflict,
+                line,
+                format!(
+                    "M[{}, {}]: producciones {} (la tabla usa [{}])",
+                    nt.as_str(),
+                    terminals.join(" | "),
+                    names.join(", "),
+                    chosen,
+                ),
+            ));
+        }
+    }
+
+    fn check_left_recursion(&self, issues: &mut Vec<GrammarIssue>) {
+        let sets = FirstFollowSets::from_grammar(self);
+        let nullable = |nt: &NonTerminal| sets.is_in_first(*nt, &Symbol::Epsilon);
+
+        // A → B si alguna producción A → α B β tiene α ⇒* ε
+        let mut corners: HashMap<NonTerminal, Vec<(NonTerminal, usize)>> = HashMap::new();
+        for production in self.productions() {
+            for symbol in &production.rhs {
+                match symbol {
+                    Symbol::Epsilon => continue,
+                    Symbol::NonTerminal(nt) => {
+                        corners.entry(production.lhs).or_default().push((*nt, production.id));
+                        if !nullable(nt) {
+                            break;
+                        }
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        let mut reported: HashSet<NonTerminal> = HashSet::new();
+        for start in self.nonterminals() {
+            if reported.contains(&start) {
+                continue;
+            }
+            let Some(cycle) = left_cycle(start, &corners) else { continue };
+
+            let mut path = vec![start.as_str()];
+            path.extend(cycle.iter().map(|(_, to, _)| to.as_str()));
+            let ids: Vec<String> = cycle.iter().map(|(_, _, id)| format!("[{}]", id)).collect();
+            issues.push(GrammarIssue::new(
+                GrammarIssueKind::LeftRecursion,
+                self.line(cycle[0].2),
+                format!("'{}' es recursivo por la izquierda: {} (producciones {})", start.as_str(), path.join(" ⇒ "), ids.join(", ")),
+            ));
+            reported.extend(cycle.iter().map(|(from, _, _)| *from));
+        }
+    }
+
+    fn check_reachability(&self, issues: &mut Vec<GrammarIssue>) {
+        let start = self.start();
+        let mut reached = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(nt) = queue.pop_front() {
+            for production in self.productions().iter().filter(|p| p.lhs == nt) {
+                for symbol in &production.rhs {
+                    if let Symbol::NonTerminal(next) = symbol
+                        && reached.insert(*next)
+                    {
+                        queue.push_back(*next);
+                    }
+                }
+            }
+        }
+
+        for nt in self.nonterminals() {
+            if !reached.contains(&nt) {
+                issues.push(GrammarIssue::new(
+                    GrammarIssueKind::Unreachable,
+                    self.nonterminal_line(nt),
+                    format!("'{}' no se alcanza desde '{}'", nt.as_str(), start.as_str()),
+                ));
+            }
+        }
+    }
+
+    fn check_productivity(&self, issues: &mut Vec<GrammarIssue>) {
+        let mut productive: HashSet<NonTerminal> = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for production in self.productions() {
+                if productive.contains(&production.lhs) {
+                    continue;
+                }
+                let derives = production.rhs.iter().all(|symbol| match symbol {
+                    Symbol::NonTerminal(nt) => productive.contains(nt),
+                    _ => true,
+                });
+                if derives {
+                    productive.insert(production.lhs);
+                    changed = true;
+                }
+            }
+        }
+
+        // `Grammar::parse` ya rechaza los no-terminales sin producciones
+        for nt in self.nonterminals() {
+            if productive.contains(&nt) {
+                continue;
+            }
+            let message = format!("'{}' no deriva ninguna cadena de terminales", nt.as_str());
+            issues.push(GrammarIssue::new(GrammarIssueKind::NonProductive, self.nonterminal_line(nt), message));
+        }
+    }
+
+    fn check_unused(&self, table: &LL1Table, issues: &mut Vec<GrammarIssue>) {
+        for production in self.productions() {
+            if !table.uses(production.id) {
+                issues.push(GrammarIssue::new(
+                    GrammarIssueKind::UnusedProduction,
+                    self.line(production.id),
+                    format!("{} no aparece en ninguna celda de la tabla", production),
+                ));
+            }
+        }
+    }
+}
+
+/// Camino más corto `start ⇒ ... ⇒ start` por esquinas izquierdas, como
+/// pasos (desde, hacia, producción)
+fn left_cycle(
+    start: NonTerminal,
+    corners: &HashMap<NonTerminal, Vec<(NonTerminal, usize)>>,
+) -> Option<Vec<(NonTerminal, NonTerminal, usize)>> {
+    let mut parent: HashMap<NonTerminal, (NonTerminal, usize)> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(nt) = queue.pop_front() {
+        for &(next, id) in corners.get(&nt).into_iter().flatten() {
+            if next == start {
+                let mut steps = vec![(nt, start, id)];
+                let mut current = nt;
+                while current != start {
+                    let (previous, id) = parent[&current];
+                    steps.push((previous, current, id));
+                    current = previous;
+                }
+                steps.reverse();
+                return Some(steps);
+            }
+            if let std::collections::hash_map::Entry::Vacant(entry) = parent.entry(next) {
+                entry.insert((nt, id));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::loader::DEFAULT_GRAMMAR_SOURCE;
+
+    fn validate(source: &str) -> Vec<GrammarIssue> {
+        Grammar::parse(source).unwrap().validate()
+    }
+
+    fn kinds(issues: &[GrammarIssue]) -> Vec<GrammarIssueKind> {
+        issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn test_embedded_grammar_is_ll1() {
+        let issues = validate(DEFAULT_GRAMMAR_SOURCE);
+        assert!(issues.is_empty(), "{:#?}", issues);
+    }
+
+    #[test]
+    fn test_conflicts_grouped_by_productions() {
+        let issues = validate(
+            "[1] Programa → Accesos PUNTO_COMA\n\
+             [2] Accesos → AccesoArreglo\n\
+             [3] Accesos → ε\n\
+             [4] AccesoArreglo → CORCHETE_IZQ NUMERO CORCHETE_DER\n\
+             [5] AccesoArreglo → ε\n",
+        );
+
+        assert_eq!(kinds(&issues), vec![GrammarIssueKind::Conflict]);
+        assert_eq!(issues[0].line, 3);
+        assert_eq!(issues[0].message, "M[Accesos, PUNTO_COMA]: producciones [2], [3] (la tabla usa [3])");
+    }
+
+    #[test]
+    fn test_left_recursion_direct_and_indirect() {
+        let issues = validate(
+            "[1] Programa → Expresion PUNTO\n\
+             [2] Expresion → Expresion OR ExpresionAnd\n\
+             [3] Expresion → ExpresionAnd\n\
+             [4] ExpresionAnd → Accesos ExpresionUnaria NUMERO\n\
+             [5] ExpresionAnd → CADENA\n\
+             [6] Accesos → ε\n\
+             [7] ExpresionUnaria → ExpresionAnd NOT\n",
+        );
+
+        let recursion: Vec<&GrammarIssue> =
+            issues.iter().filter(|i| i.kind == GrammarIssueKind::LeftRecursion).collect();
+        assert_eq!(recursion.len(), 2);
+        assert_eq!(recursion[0].message, "'Expresion' es recursivo por la izquierda: Expresion ⇒ Expresion (producciones [2])");
+        assert_eq!(
+            recursion[1].message,
+            "'ExpresionAnd' es recursivo por la izquierda: ExpresionAnd ⇒ ExpresionUnaria ⇒ ExpresionAnd (producciones [4], [7])"
+        );
+        assert_eq!(recursion[1].line, 4);
+    }
+
+    #[test]
+    fn test_unreachable_nonproductive_and_unused() {
+        let issues = validate(
+            "[1] Programa → PROGRAMA Modulos\n\
+             [2] Modulos → ε\n\
+             [3] Modulos → MODULO Modulo\n\
+             [4] Modulo → MODULO Modulo\n\
+             [5] Sentencias → FIN\n\
+             [6] Direccion → ARRIBA\n\
+             [7] Direccion → ARRIBA Modulo\n",
+        );
+
+        let found: Vec<(GrammarIssueKind, usize)> = issues.iter().map(|i| (i.kind, i.line)).collect();
+        assert!(found.contains(&(GrammarIssueKind::NonProductive, 4)));
+        assert!(found.contains(&(GrammarIssueKind::Unreachable, 5)));
+        assert!(found.contains(&(GrammarIssueKind::Unreachable, 6)));
+        assert!(found.contains(&(GrammarIssueKind::UnusedProduction, 7)));
+
+        assert!(issues.iter().any(|i| i.message == "[7] Direccion → ARRIBA Modulo no aparece en ninguna celda de la tabla"));
+    }
+}
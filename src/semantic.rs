@@ -2,6 +2,7 @@
 // Valida que el programa sea semánticamente correcto
 
 use crate::ast::*;
+use crate::error::SourceMap;
 use std::collections::HashMap;
 
 // ============================================================================
@@ -162,6 +163,20 @@ impl CoaxialSymbol {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ModuloSymbol {
+    pub nombre: String,
+    pub parametros: Vec<Parametro>,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone)]
+pub struct VariableSymbol {
+    pub nombre: String,
+    pub tipo: Type,                       // Por ahora solo Int (define enteros)
+    pub location: Location,
+}
+
 // ============================================================================
 // TABLA DE SÍMBOLOS
 // ============================================================================
@@ -171,7 +186,8 @@ pub struct SymbolTable {
     pub maquinas: HashMap<String, MaquinaSymbol>,
     pub concentradores: HashMap<String, ConcentradorSymbol>,
     pub coaxiales: HashMap<String, CoaxialSymbol>,
-    pub modulos: HashMap<String, Location>,  // Nombre -> ubicación del módulo
+    pub variables: HashMap<String, VariableSymbol>,
+    pub modulos: HashMap<String, ModuloSymbol>,
 }
 
 impl SymbolTable {
@@ -180,10 +196,26 @@ impl SymbolTable {
             maquinas: HashMap::new(),
             concentradores: HashMap::new(),
             coaxiales: HashMap::new(),
+            variables: HashMap::new(),
             modulos: HashMap::new(),
         }
     }
 
+    /// Qué es `nombre` si ya está en uso por un dispositivo o una variable
+    fn clase_de(&self, nombre: &str) -> Option<&'static str> {
+        if self.maquinas.contains_key(nombre) {
+            Some("una máquina")
+        } else if self.concentradores.contains_key(nombre) {
+            Some("un concentrador")
+        } else if self.coaxiales.contains_key(nombre) {
+            Some("un coaxial")
+        } else if self.variables.contains_key(nombre) {
+            Some("una variable")
+        } else {
+            None
+        }
+    }
+
     // ========== Máquinas ==========
 
     pub fn definir_maquina(&mut self, nombre: String, location: Location) -> Result<(), String> {
@@ -196,6 +228,9 @@ impl SymbolTable {
         if self.coaxiales.contains_key(&nombre) {
             return Err(format!("El nombre '{}' ya está en uso por un coaxial", nombre));
         }
+        if self.variables.contains_key(&nombre) {
+            return Err(format!("El nombre '{}' ya está en uso por una variable", nombre));
+        }
 
         self.maquinas.insert(nombre.clone(), MaquinaSymbol {
             nombre,
@@ -225,6 +260,9 @@ impl SymbolTable {
         if self.coaxiales.contains_key(&nombre) {
             return Err(format!("El nombre '{}' ya está en uso por un coaxial", nombre));
         }
+        if self.variables.contains_key(&nombre) {
+            return Err(format!("El nombre '{}' ya está en uso por una variable", nombre));
+        }
 
         // Validar número de puertos (4, 8, 16)
         if puertos != 4 && puertos != 8 && puertos != 16 {
@@ -255,6 +293,9 @@ impl SymbolTable {
         if self.concentradores.contains_key(&nombre) {
             return Err(format!("El nombre '{}' ya está en uso por un concentrador", nombre));
         }
+        if self.variables.contains_key(&nombre) {
+            return Err(format!("El nombre '{}' ya está en uso por una variable", nombre));
+        }
 
         // Validar reglas Ethernet: longitud del cable debe estar entre 3m y 500m
         if longitud < 3 {
@@ -276,19 +317,41 @@ impl SymbolTable {
         self.coaxiales.get_mut(nombre)
     }
 
+    // ========== Variables ==========
+
+    pub fn definir_variable(&mut self, nombre: String, tipo: Type, location: Location) -> Result<(), String> {
+        if self.variables.contains_key(&nombre) {
+            return Err(format!("Variable '{}' ya fue definida", nombre));
+        }
+        if let Some(clase) = self.clase_de(&nombre) {
+            return Err(format!("El nombre '{}' ya está en uso por {}", nombre, clase));
+        }
+
+        self.variables.insert(nombre.clone(), VariableSymbol { nombre, tipo, location });
+        Ok(())
+    }
+
+    pub fn obtener_variable(&self, nombre: &str) -> Option<&VariableSymbol> {
+        self.variables.get(nombre)
+    }
+
     // ========== Módulos ==========
 
-    pub fn definir_modulo(&mut self, nombre: String, location: Location) -> Result<(), String> {
+    pub fn definir_modulo(&mut self, nombre: String, parametros: Vec<Parametro>, location: Location) -> Result<(), String> {
         if self.modulos.contains_key(&nombre) {
             return Err(format!("Módulo '{}' ya fue definido", nombre));
         }
-        self.modulos.insert(nombre, location);
+        self.modulos.insert(nombre.clone(), ModuloSymbol { nombre, parametros, location });
         Ok(())
     }
 
     pub fn existe_modulo(&self, nombre: &str) -> bool {
         self.modulos.contains_key(nombre)
     }
+
+    pub fn obtener_modulo(&self, nombre: &str) -> Option<&ModuloSymbol> {
+        self.modulos.get(nombre)
+    }
 }
 
 // ============================================================================
@@ -317,6 +380,8 @@ pub struct SemanticAnalyzer {
     // Rastrear asignaciones de máquinas a cables coaxiales durante el análisis
     // Mapa: nombre_coaxial -> Vec<(nombre_maquina, posicion)>
     coaxial_assignments: std::collections::HashMap<String, Vec<(String, i32)>>,
+    // Parámetros del módulo que se está analizando; ocultan a los objetos globales
+    parametros: HashMap<String, TipoParametro>,
 }
 
 impl SemanticAnalyzer {
@@ -325,6 +390,7 @@ impl SemanticAnalyzer {
             symbol_table: SymbolTable::new(),
             errors: Vec::new(),
             coaxial_assignments: std::collections::HashMap::new(),
+            parametros: HashMap::new(),
         }
     }
 
@@ -335,16 +401,18 @@ impl SemanticAnalyzer {
         // Paso 2a: Registrar todos los módulos primero (sin analizar contenido)
         // Esto permite que los módulos se llamen entre sí sin importar el orden
         for modulo in &program.modulos {
-            if let Err(msg) = self.symbol_table.definir_modulo(modulo.nombre.clone(), modulo.location.clone()) {
+            if let Err(msg) = self.symbol_table.definir_modulo(
+                modulo.nombre.clone(),
+                modulo.parametros.clone(),
+                modulo.location.clone()
+            ) {
                 self.errors.push(SemanticError::new(msg, modulo.location.clone()));
             }
         }
 
         // Paso 2b: Ahora analizar el contenido de los módulos
         for modulo in &program.modulos {
-            for stmt in &modulo.sentencias {
-                self.analyze_statement(stmt);
-            }
+            self.analyze_module_body(modulo);
         }
 
         // Paso 3: Analizar sentencias principales
@@ -392,20 +460,47 @@ impl SemanticAnalyzer {
                 self.errors.push(SemanticError::new(msg, coax.location.clone()));
             }
         }
+
+        // Definir variables enteras
+        for var in &defs.enteros {
+            if let Err(msg) = self.symbol_table.definir_variable(var.nombre.clone(), Type::Int, var.location.clone()) {
+                self.errors.push(SemanticError::new(msg, var.location.clone()));
+            }
+        }
     }
 
     // ========== Análisis de Módulos ==========
 
     fn analyze_module(&mut self, modulo: &Modulo) {
         // Registrar módulo
-        if let Err(msg) = self.symbol_table.definir_modulo(modulo.nombre.clone(), modulo.location.clone()) {
+        if let Err(msg) = self.symbol_table.definir_modulo(
+            modulo.nombre.clone(),
+            modulo.parametros.clone(),
+            modulo.location.clone()
+        ) {
             self.errors.push(SemanticError::new(msg, modulo.location.clone()));
         }
 
-        // Analizar sentencias del módulo
+        self.analyze_module_body(modulo);
+    }
+
+    /// Analiza las sentencias del módulo con sus parámetros en alcance
+    fn analyze_module_body(&mut self, modulo: &Modulo) {
+        self.parametros.clear();
+        for parametro in &modulo.parametros {
+            if self.parametros.insert(parametro.nombre.clone(), parametro.tipo).is_some() {
+                self.errors.push(SemanticError::new(
+                    format!("Parámetro '{}' repetido en el módulo '{}'", parametro.nombre, modulo.nombre),
+                    parametro.location.clone()
+                ));
+            }
+        }
+
         for stmt in &modulo.sentencias {
             self.analyze_statement(stmt);
         }
+
+        self.parametros.clear();
     }
 
     // ========== Análisis de Sentencias ==========
@@ -428,8 +523,10 @@ impl SemanticAnalyzer {
                 self.check_coaxial_exists(coaxial, location);
                 self.check_concentrador_exists(concentrador, location);
 
-                // Validar que el concentrador tenga salida coaxial
-                if let Some(conc) = self.symbol_table.obtener_concentrador(concentrador) {
+                // Validar que el concentrador tenga salida coaxial (un parámetro
+                // se conoce hasta la llamada)
+                if let Some(conc) = self.symbol_table.obtener_concentrador(concentrador)
+                    .filter(|_| !self.parametros.contains_key(concentrador)) {
                     if !conc.tiene_coaxial {
                         self.errors.push(SemanticError::new(
                             format!("El concentrador '{}' no tiene salida para coaxial", concentrador),
@@ -460,7 +557,8 @@ impl SemanticAnalyzer {
                 let tipo_pos = self.check_expression(posicion, &Type::Int, location);
 
                 // Validar reglas Ethernet para colocar máquina en coaxial
-                if tipo_pos == Type::Int {
+                let hay_parametros = self.parametros.contains_key(maquina) || self.parametros.contains_key(coaxial);
+                if tipo_pos == Type::Int && !hay_parametros {
                     if let Expr::Numero(pos_val) = posicion {
                         self.validate_maquina_coaxial_placement(maquina, coaxial, *pos_val, location);
                     }
@@ -476,13 +574,20 @@ impl SemanticAnalyzer {
                 self.check_expression(contenido, &Type::Unknown, location);
             }
 
-            Statement::Si { condicion, entonces, sino, location } => {
+            Statement::Si { condicion, entonces, sino_si, sino, location } => {
                 self.check_expression(condicion, &Type::Bool, location);
 
                 for stmt in entonces {
                     self.analyze_statement(stmt);
                 }
 
+                for rama in sino_si {
+                    self.check_expression(&rama.condicion, &Type::Bool, &rama.location);
+                    for stmt in &rama.sentencias {
+                        self.analyze_statement(stmt);
+                    }
+                }
+
                 if let Some(sino_stmts) = sino {
                     for stmt in sino_stmts {
                         self.analyze_statement(stmt);
@@ -490,17 +595,145 @@ impl SemanticAnalyzer {
                 }
             }
 
-            Statement::LlamadaModulo { nombre, location } => {
-                if !self.symbol_table.existe_modulo(nombre) {
+            Statement::LlamadaModulo { nombre, argumentos, location } => {
+                let parametros = match self.symbol_table.obtener_modulo(nombre) {
+                    Some(modulo) => modulo.parametros.clone(),
+                    None => {
+                        self.errors.push(SemanticError::new(
+                            format!("Módulo '{}' no está definido", nombre),
+                            location.clone()
+                        ));
+                        return;
+                    }
+                };
+
+                if argumentos.len() != parametros.len() {
                     self.errors.push(SemanticError::new(
-                        format!("Módulo '{}' no está definido", nombre),
+                        format!("El módulo '{}' espera {} argumento(s) pero se le pasaron {}",
+                                nombre, parametros.len(), argumentos.len()),
                         location.clone()
                     ));
+                    return;
+                }
+
+                for (posicion, (argumento, parametro)) in argumentos.iter().zip(&parametros).enumerate() {
+                    self.check_argumento(nombre, posicion + 1, argumento, parametro, location);
+                }
+            }
+
+            Statement::Mientras { condicion, cuerpo, location } => {
+                self.check_expression(condicion, &Type::Bool, location);
+
+                for stmt in cuerpo {
+                    self.analyze_statement(stmt);
+                }
+            }
+
+            Statement::Para { variable, desde, hasta, cuerpo, location } => {
+                self.check_variable_destino(variable, location);
+
+                for limite in [desde, hasta] {
+                    let tipo = self.infer_expression_type(limite, location);
+                    if tipo != Type::Int && tipo != Type::Unknown {
+                        self.errors.push(SemanticError::new(
+                            format!("Los límites del ciclo 'para' deben ser de tipo 'Int' pero se encontró '{}'",
+                                    tipo.to_string()),
+                            location.clone()
+                        ));
+                    }
+                }
+
+                for stmt in cuerpo {
+                    self.analyze_statement(stmt);
+                }
+            }
+
+            Statement::Asignacion { variable, valor, location } => {
+                let tipo_valor = self.infer_expression_type(valor, location);
+
+                if let Some(tipo) = self.check_variable_destino(variable, location) {
+                    // Las variables no aceptan conversiones: un entero no recibe una condición
+                    if tipo_valor != tipo && tipo_valor != Type::Unknown {
+                        self.errors.push(SemanticError::new(
+                            format!("No se puede asignar un valor de tipo '{}' a la variable '{}' de tipo '{}'",
+                                    tipo_valor.to_string(),
+                                    variable,
+                                    tipo.to_string()),
+                            location.clone()
+                        ));
+                    }
                 }
             }
         }
     }
 
+    /// Tipo de la variable que recibe un valor (asignación o contador de `para`);
+    /// reporta el error si no está declarada o si el nombre es de un dispositivo
+    fn check_variable_destino(&mut self, variable: &str, location: &Location) -> Option<Type> {
+        // Los parámetros enteros son variables locales del módulo
+        if let Some(tipo) = self.parametros.get(variable).copied() {
+            if tipo == TipoParametro::Entero {
+                return Some(Type::Int);
+            }
+            self.errors.push(SemanticError::new(
+                format!("No se puede asignar a '{}': es un parámetro de tipo {}, no una variable", variable, tipo.nombre()),
+                location.clone()
+            ));
+            return None;
+        }
+
+        if let Some(simbolo) = self.symbol_table.obtener_variable(variable) {
+            return Some(simbolo.tipo.clone());
+        }
+
+        let message = match self.symbol_table.clase_de(variable) {
+            Some(clase) => format!("No se puede asignar a '{}': es {}, no una variable", variable, clase),
+            None => format!("Variable '{}' no está declarada (use define enteros {};)", variable, variable),
+        };
+        self.errors.push(SemanticError::new(message, location.clone()));
+        None
+    }
+
+    /// Un argumento de objeto debe nombrar un objeto de la clase del parámetro;
+    /// uno entero, cualquier expresión entera
+    fn check_argumento(&mut self, modulo: &str, posicion: usize, argumento: &Expr, parametro: &Parametro, location: &Location) {
+        let esperado = tipo_de_parametro(parametro.tipo);
+
+        if parametro.tipo == TipoParametro::Entero {
+            let tipo = self.infer_expression_type(argumento, location);
+            if tipo != Type::Int && tipo != Type::Unknown {
+                self.errors.push(SemanticError::new(
+                    format!("Argumento {} de '{}': el parámetro '{}' es entero pero se recibió un valor de tipo '{}'",
+                            posicion, modulo, parametro.nombre, tipo.to_string()),
+                    location.clone()
+                ));
+            }
+            return;
+        }
+
+        let Expr::Identificador(objeto) = argumento else {
+            self.errors.push(SemanticError::new(
+                format!("Argumento {} de '{}': el parámetro '{}' es de tipo {} y requiere el nombre de un objeto",
+                        posicion, modulo, parametro.nombre, parametro.tipo.nombre()),
+                location.clone()
+            ));
+            return;
+        };
+
+        match self.tipo_objeto(objeto) {
+            Some(tipo) if tipo == esperado => {}
+            Some(tipo) => self.errors.push(SemanticError::new(
+                format!("Argumento {} de '{}': el parámetro '{}' es de tipo {} pero '{}' es de tipo '{}'",
+                        posicion, modulo, parametro.nombre, parametro.tipo.nombre(), objeto, tipo.to_string()),
+                location.clone()
+            )),
+            None => self.errors.push(SemanticError::new(
+                format!("Identificador '{}' no está definido", objeto),
+                location.clone()
+            )),
+        }
+    }
+
     // ========== Validación de Expresiones ==========
 
     fn check_expression(&mut self, expr: &Expr, expected_type: &Type, location: &Location) -> Type {
@@ -529,24 +762,22 @@ impl SemanticAnalyzer {
 
             Expr::Identificador(nombre) => {
                 // Verificar que el identificador existe
-                if self.symbol_table.obtener_maquina(nombre).is_some() {
-                    Type::Maquina
-                } else if self.symbol_table.obtener_concentrador(nombre).is_some() {
-                    Type::Concentrador
-                } else if self.symbol_table.obtener_coaxial(nombre).is_some() {
-                    Type::Coaxial
-                } else {
-                    self.errors.push(SemanticError::new(
-                        format!("Identificador '{}' no está definido", nombre),
-                        location.clone()
-                    ));
-                    Type::Unknown
+                match self.tipo_objeto(nombre) {
+                    Some(tipo) => tipo,
+                    None => {
+                        self.errors.push(SemanticError::new(
+                            format!("Identificador '{}' no está definido", nombre),
+                            location.clone()
+                        ));
+                        Type::Unknown
+                    }
                 }
             }
 
             Expr::AccesoCampo { objeto, campo } => {
                 // Validar acceso a campos de concentradores y coaxiales
-                if let Some(_) = self.symbol_table.obtener_concentrador(objeto) {
+                let tipo_objeto = self.tipo_objeto(objeto);
+                if tipo_objeto == Some(Type::Concentrador) {
                     match campo.as_str() {
                         "puertos" | "disponibles" | "presente" | "coaxial" => Type::Int,
                         _ => {
@@ -558,7 +789,7 @@ impl SemanticAnalyzer {
                             Type::Unknown
                         }
                     }
-                } else if let Some(_) = self.symbol_table.obtener_coaxial(objeto) {
+                } else if tipo_objeto == Some(Type::Coaxial) {
                     match campo.as_str() {
                         "longitud" | "completo" | "num" | "presente" => Type::Int,
                         _ => {
@@ -586,7 +817,7 @@ impl SemanticAnalyzer {
                 if objeto.contains('.') {
                     let parts: Vec<&str> = objeto.split('.').collect();
                     if parts.len() == 2 && parts[1] == "p" {
-                        if self.symbol_table.obtener_concentrador(parts[0]).is_some() {
+                        if self.tipo_objeto(parts[0]) == Some(Type::Concentrador) {
                             Type::Bool
                         } else {
                             self.errors.push(SemanticError::new(
@@ -636,11 +867,51 @@ impl SemanticAnalyzer {
                 self.check_expression(expr, &Type::Bool, location);
                 Type::Bool
             }
+
+            Expr::Aritmetico { izq, op, der } => {
+                self.check_arithmetic_operand(izq, op.simbolo(), location);
+                self.check_arithmetic_operand(der, op.simbolo(), location);
+                Type::Int
+            }
+
+            Expr::Negativo(expr) => {
+                self.check_arithmetic_operand(expr, "-", location);
+                Type::Int
+            }
+        }
+    }
+
+    /// Los operandos aritméticos deben ser enteros (no valen cadenas, objetos
+    /// ni condiciones)
+    fn check_arithmetic_operand(&mut self, expr: &Expr, op: &str, location: &Location) {
+        let tipo = self.infer_expression_type(expr, location);
+        if tipo != Type::Int && tipo != Type::Unknown {
+            self.errors.push(SemanticError::new(
+                format!("El operador '{}' requiere operandos de tipo 'Int' pero se encontró '{}'",
+                        op,
+                        tipo.to_string()),
+                location.clone()
+            ));
         }
     }
 
     // ========== Helpers ==========
 
+    /// Tipo de un nombre: parámetro del módulo actual, objeto o variable global
+    fn tipo_objeto(&self, nombre: &str) -> Option<Type> {
+        if let Some(tipo) = self.parametros.get(nombre) {
+            Some(tipo_de_parametro(*tipo))
+        } else if self.symbol_table.obtener_maquina(nombre).is_some() {
+            Some(Type::Maquina)
+        } else if self.symbol_table.obtener_concentrador(nombre).is_some() {
+            Some(Type::Concentrador)
+        } else if self.symbol_table.obtener_coaxial(nombre).is_some() {
+            Some(Type::Coaxial)
+        } else {
+            self.symbol_table.obtener_variable(nombre).map(|v| v.tipo.clone())
+        }
+    }
+
     fn types_are_compatible(&self, actual: &Type, expected: &Type) -> bool {
         // Unknown es compatible con todo (permisivo)
         if actual == &Type::Unknown || expected == &Type::Unknown {
@@ -664,7 +935,7 @@ impl SemanticAnalyzer {
     }
 
     fn check_maquina_exists(&mut self, nombre: &str, location: &Location) {
-        if self.symbol_table.obtener_maquina(nombre).is_none() {
+        if self.tipo_objeto(nombre) != Some(Type::Maquina) {
             self.errors.push(SemanticError::new(
                 format!("Máquina '{}' no está definida", nombre),
                 location.clone()
@@ -673,7 +944,7 @@ impl SemanticAnalyzer {
     }
 
     fn check_concentrador_exists(&mut self, nombre: &str, location: &Location) {
-        if self.symbol_table.obtener_concentrador(nombre).is_none() {
+        if self.tipo_objeto(nombre) != Some(Type::Concentrador) {
             self.errors.push(SemanticError::new(
                 format!("Concentrador '{}' no está definido", nombre),
                 location.clone()
@@ -682,7 +953,7 @@ impl SemanticAnalyzer {
     }
 
     fn check_coaxial_exists(&mut self, nombre: &str, location: &Location) {
-        if self.symbol_table.obtener_coaxial(nombre).is_none() {
+        if self.tipo_objeto(nombre) != Some(Type::Coaxial) {
             self.errors.push(SemanticError::new(
                 format!("Coaxial '{}' no está definido", nombre),
                 location.clone()
@@ -691,8 +962,7 @@ impl SemanticAnalyzer {
     }
 
     fn check_object_exists(&mut self, nombre: &str, location: &Location) {
-        if self.symbol_table.obtener_maquina(nombre).is_none()
-            && self.symbol_table.obtener_concentrador(nombre).is_none() {
+        if !matches!(self.tipo_objeto(nombre), Some(Type::Maquina | Type::Concentrador)) {
             self.errors.push(SemanticError::new(
                 format!("Objeto '{}' no está definido (no es máquina ni concentrador)", nombre),
                 location.clone()
@@ -705,9 +975,7 @@ impl SemanticAnalyzer {
         // - Máquina (conexión normal)
         // - Concentrador (cascada de hubs)
         // - Coaxial (para conectar cable coaxial a un hub)
-        if self.symbol_table.obtener_maquina(nombre).is_none()
-            && self.symbol_table.obtener_concentrador(nombre).is_none()
-            && self.symbol_table.obtener_coaxial(nombre).is_none() {
+        if !matches!(self.tipo_objeto(nombre), Some(Type::Maquina | Type::Concentrador | Type::Coaxial)) {
             self.errors.push(SemanticError::new(
                 format!("'{}' no está definido (debe ser una máquina, concentrador o coaxial)", nombre),
                 location.clone()
@@ -757,12 +1025,23 @@ impl SemanticAnalyzer {
     }
 }
 
+/// Tipo con el que se analiza un parámetro de módulo
+fn tipo_de_parametro(tipo: TipoParametro) -> Type {
+    match tipo {
+        TipoParametro::Maquina => Type::Maquina,
+        TipoParametro::Concentrador => Type::Concentrador,
+        TipoParametro::Coaxial => Type::Coaxial,
+        TipoParametro::Entero => Type::Int,
+    }
+}
+
 // ============================================================================
 // HELPER: Reportar errores semánticos
 // ============================================================================
 
-pub fn report_semantic_errors(errors: &[SemanticError], source: &str, filename: &str) {
-    use crate::error::{Diagnostic, report_errors};
+/// Cada error se muestra con el archivo de su ubicación (principal o incluido)
+pub fn report_semantic_errors(errors: &[SemanticError], sources: &SourceMap) {
+    use crate::error::{Diagnostic, report_errors_in};
 
     let diagnostics: Vec<Diagnostic> = errors.iter().map(|err| {
         Diagnostic::semantic_error(
@@ -770,8 +1049,155 @@ pub fn report_semantic_errors(errors: &[SemanticError], source: &str, filename:
             err.location.column,
             err.location.length,
             err.message.clone()
-        )
+        ).in_file(err.location.archivo)
     }).collect();
 
-    report_errors(&diagnostics, source, filename);
+    report_errors_in(&diagnostics, sources);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer_new::tokenize;
+    use crate::parser_ll1::PredictiveParser;
+
+    /// Mensajes de los errores semánticos de `source` (vacío si no hay)
+    fn errores(source: &str) -> Vec<String> {
+        let program = PredictiveParser::new(tokenize(source).unwrap()).parse().unwrap();
+        match SemanticAnalyzer::new().analyze(&program) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|e| e.message).collect(),
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_operands_must_be_int() {
+        let source = "programa p;\ndefine maquinas A;\ndefine enteros x;\ninicio\n\
+                      escribe(x * 2 + -x % 3);\n\
+                      escribe(1 + \"a\");\n\
+                      escribe(A * 2);\n\
+                      escribe(-(1 < 2));\nfin.";
+        assert_eq!(
+            errores(source),
+            [
+                "El operador '+' requiere operandos de tipo 'Int' pero se encontró 'String'",
+                "El operador '*' requiere operandos de tipo 'Int' pero se encontró 'Maquina'",
+                "El operador '-' requiere operandos de tipo 'Int' pero se encontró 'Bool'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assignment_rules() {
+        let source = "programa p;\ndefine maquinas A;\ndefine concentradores hub = 4;\ndefine enteros x, y;\ninicio\n\
+                      x = 3;\n\
+                      y = x * 2 + 1;\n\
+                      z = 3;\n\
+                      x = A;\n\
+                      x = \"hola\";\n\
+                      x = 1 < 2;\n\
+                      A = 1;\n\
+                      hub = x;\nfin.";
+        assert_eq!(
+            errores(source),
+            [
+                "Variable 'z' no está declarada (use define enteros z;)",
+                "No se puede asignar un valor de tipo 'Maquina' a la variable 'x' de tipo 'Int'",
+                "No se puede asignar un valor de tipo 'String' a la variable 'x' de tipo 'Int'",
+                "No se puede asignar un valor de tipo 'Bool' a la variable 'x' de tipo 'Int'",
+                "No se puede asignar a 'A': es una máquina, no una variable",
+                "No se puede asignar a 'hub': es un concentrador, no una variable",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variable_names_are_unique() {
+        let errores = errores("programa p;\ndefine maquinas A;\ndefine enteros A, x, x;\ninicio\nfin.");
+        assert_eq!(errores.len(), 2, "{:?}", errores);
+        assert!(errores[0].contains("'A'"), "{:?}", errores);
+        assert!(errores[1].contains("'x'"), "{:?}", errores);
+    }
+
+    #[test]
+    fn test_loop_checks() {
+        let source = "programa p;\ndefine maquinas A;\ndefine enteros i;\ninicio\n\
+                      para i = 1 hasta 2 * 3 inicio fin\n\
+                      para i = 1 hasta \"diez\" inicio fin\n\
+                      para i = A hasta 3 inicio fin\n\
+                      para A = 1 hasta 3 inicio fin\n\
+                      para j = 1 hasta 3 inicio fin\nfin.";
+        assert_eq!(
+            errores(source),
+            [
+                "Los límites del ciclo 'para' deben ser de tipo 'Int' pero se encontró 'String'",
+                "Los límites del ciclo 'para' deben ser de tipo 'Int' pero se encontró 'Maquina'",
+                "No se puede asignar a 'A': es una máquina, no una variable",
+                "Variable 'j' no está declarada (use define enteros j;)",
+            ]
+        );
+
+        // La condición de un mientras es booleana (un entero también vale)
+        assert!(errores("programa p;\ndefine enteros i;\ninicio\n  mientras (i) inicio i = 0; fin\nfin.").is_empty());
+        assert_eq!(
+            errores("programa p;\ninicio\n  mientras (\"si\") inicio fin\nfin."),
+            ["Incompatibilidad de tipos: se esperaba 'Bool' pero se encontró 'String'"]
+        );
+    }
+
+    const CONECTA: &str = "programa p;\ndefine maquinas A;\ndefine concentradores hub = 4;\ndefine enteros x;\n\
+                           modulo conecta(maquina m, concentrador c, entero p);\ninicio\n  uneMaquinaPuerto(m, c, p);\nfin\n\
+                           modulo nada;\ninicio\nfin\n";
+
+    #[test]
+    fn test_module_call_arity() {
+        let source = format!("{}inicio\n  conecta(A, hub, 1);\n  conecta(A);\n  nada(1);\n  nada;\n  falta(A);\nfin.", CONECTA);
+        assert_eq!(
+            errores(&source),
+            [
+                "El módulo 'conecta' espera 3 argumento(s) pero se le pasaron 1",
+                "El módulo 'nada' espera 0 argumento(s) pero se le pasaron 1",
+                "Módulo 'falta' no está definido",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_argument_types() {
+        let source = format!(
+            "{}inicio\n  conecta(hub, hub, 1);\n  conecta(A, 2 + 2, x);\n  conecta(A, hub, \"uno\");\n  conecta(Q, hub, x * 2);\nfin.",
+            CONECTA
+        );
+        assert_eq!(
+            errores(&source),
+            [
+                "Argumento 1 de 'conecta': el parámetro 'm' es de tipo maquina pero 'hub' es de tipo 'Concentrador'",
+                "Argumento 2 de 'conecta': el parámetro 'c' es de tipo concentrador y requiere el nombre de un objeto",
+                "Argumento 3 de 'conecta': el parámetro 'p' es entero pero se recibió un valor de tipo 'String'",
+                "Identificador 'Q' no está definido",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_parameter_scope() {
+        // Dentro del módulo `hub` es la máquina recibida y oculta al concentrador
+        // global; fuera del módulo los parámetros no existen
+        let source = "programa p;\ndefine maquinas A;\ndefine concentradores hub = 4, otro = 4;\n\
+                      modulo m(maquina hub, entero n);\ninicio\n\
+                        asignaPuerto(hub, otro);\n\
+                        n = n + 1;\n\
+                        hub = 1;\nfin\n\
+                      inicio\n  m(A, 1);\n  coloca(hub, 1, 1);\n  coloca(n, 1, 1);\nfin.";
+        assert_eq!(
+            errores(source),
+            [
+                "No se puede asignar a 'hub': es un parámetro de tipo maquina, no una variable",
+                "Objeto 'n' no está definido (no es máquina ni concentrador)",
+            ]
+        );
+
+        let repetido = "programa p;\nmodulo m(entero n, entero n);\ninicio\nfin\ninicio\nfin.";
+        assert_eq!(errores(repetido), ["Parámetro 'n' repetido en el módulo 'm'"]);
+    }
 }
@@ -0,0 +1,21 @@
+// tests/token_table.rs
+// La tabla de tokens recorta los lexemas largos por caracteres, no por bytes
+
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_long_unicode_lexeme_is_abbreviated() {
+    // 39 caracteres; el byte 30 cae a mitad de la primera 'ñ'
+    let nombre = format!("{}{}", "m".repeat(29), "ñ".repeat(10));
+    let source = format!("programa p;\ndefine maquinas {};\ninicio\n  coloca({}, 1, 2);\nfin.", nombre, nombre);
+    let path = std::env::temp_dir().join(format!("token_table_{}.net", std::process::id()));
+    fs::write(&path, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_interprete")).arg(&path).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let recortado: String = nombre.chars().take(30).collect();
+    assert!(stdout.contains(&format!("{}...", recortado)), "{}", stdout);
+}
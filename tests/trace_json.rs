@@ -0,0 +1,62 @@
+// tests/trace_json.rs
+// `--trace-parse json` debe escribir en stdout solo un documento JSON válido
+
+use std::fs;
+use std::process::Command;
+
+/// Ejecuta el intérprete con `--trace-parse json` (y `extra`) sobre `source`
+fn trace(nombre: &str, source: &str, extra: &[&str]) -> (std::process::Output, serde_json::Value) {
+    let path = std::env::temp_dir().join(format!("trace_json_{}_{}.net", nombre, std::process::id()));
+    fs::write(&path, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_interprete"))
+        .arg(&path)
+        .args(["--trace-parse", "json"])
+        .args(extra)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    let json = serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("stdout no es JSON ({}):\n{}", e, stdout));
+    (output, json)
+}
+
+const VALIDO: &str = "programa p;\ndefine maquinas A;\ninicio\n  coloca(A, 1, 2);\nfin.";
+
+#[test]
+fn test_json_trace_is_the_whole_stdout() {
+    let (output, json) = trace("valido", VALIDO, &[]);
+    assert!(output.status.success());
+
+    let steps = json.as_array().unwrap();
+    assert_eq!(steps[0]["step"], 1);
+    assert_eq!(steps[0]["stack"], serde_json::json!(["EOF", "Programa"]));
+    assert_eq!(steps[0]["action"], "expand");
+    assert!(steps[0]["rule"].as_str().unwrap().starts_with("[1] Programa →"));
+    assert_eq!(steps.last().unwrap()["action"], "accept");
+}
+
+#[test]
+fn test_json_trace_with_syntax_error() {
+    let (output, json) = trace("error", "programa p\ninicio\nfin.", &[]);
+    assert!(!output.status.success());
+
+    // La traza llega hasta el error; el diagnóstico va a stderr
+    let steps = json.as_array().unwrap();
+    assert!(steps.iter().any(|step| step["action"] == "error"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Error Sintáctico"));
+}
+
+#[test]
+fn test_json_trace_with_config_overrides() {
+    let root = env!("CARGO_MANIFEST_DIR");
+    let automaton = format!("{}/config/automaton.aut", root);
+    let grammar = format!("{}/docs/gramatica.txt", root);
+
+    // Los avisos de --automaton y --grammar van a stderr, no al documento
+    let (output, json) = trace("config", VALIDO, &["--automaton", &automaton, "--grammar", &grammar]);
+    assert!(output.status.success());
+    assert_eq!(json.as_array().unwrap().last().unwrap()["action"], "accept");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Autómata:") && stderr.contains("Gramática:"));
+}